@@ -1,4 +1,4 @@
-//! Example demonstrating dotenvy integration with procenv.
+//! Example demonstrating `.env` file loading with procenv.
 //!
 //! This example shows three ways to use the `#[env_config(dotenv)]` attribute:
 //! 1. Default: `#[env_config(dotenv)]` - loads .env from current directory
@@ -38,7 +38,7 @@ struct Config {
 
 fn main() -> Result<(), procenv::Error> {
     // The .env file is automatically loaded by from_env()
-    // No need to call dotenvy::dotenv() manually!
+    // No need to load it manually!
     let config = Config::from_env()?;
 
     println!("=== Config loaded from .env ===\n");
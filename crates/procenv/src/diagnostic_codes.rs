@@ -74,6 +74,10 @@ pub const FILE_NOT_FOUND: &str = "procenv::file::not_found";
 #[cfg(feature = "file")]
 pub const FILE_PARSE_ERROR: &str = "procenv::file::parse_error";
 
+/// Invalid glob pattern for a configuration directory.
+#[cfg(feature = "file")]
+pub const FILE_INVALID_GLOB: &str = "procenv::file::invalid_glob";
+
 /// Required field missing from file.
 #[cfg(feature = "file")]
 pub const FILE_MISSING_FIELD: &str = "procenv::file::missing_field";
@@ -0,0 +1,410 @@
+//! Lossless dotenv editing.
+
+use std::fmt;
+use std::path::Path;
+
+use super::parser::{self, Spans};
+use super::{DotenvError, Entry};
+
+/// A dotenv file that can be edited without losing its formatting.
+///
+/// Comments, blank lines, `export` prefixes and the order of entries are
+/// kept as written; an edit only touches the key or value it changes.
+/// Updated values keep their quoting style where the new value allows it.
+///
+/// Values are parsed like [`parse`](super::parse), except that references
+/// are only resolved from earlier entries, not the process environment, so
+/// [`get`](Self::get) reflects the file alone.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use procenv::dotenv::Document;
+///
+/// let mut env = Document::read(".env")?;
+/// if env.get("SESSION_SECRET").is_none() {
+///     env.set("SESSION_SECRET", &generate_secret())?;
+/// }
+/// env.rename("DB_URL", "DATABASE_URL")?;
+/// env.write(".env")?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Document {
+    /// The file content, edited in place.
+    content: String,
+
+    /// Entries in file order, including duplicates.
+    entries: Vec<Entry>,
+
+    /// Location of each entry in `content`.
+    spans: Vec<Spans>,
+}
+
+impl Document {
+    /// Parse dotenv content.
+    ///
+    /// # Errors
+    ///
+    /// Returns a syntax error if any line is malformed, like
+    /// [`parse`](super::parse).
+    pub fn parse(content: impl Into<String>) -> Result<Self, DotenvError> {
+        Self::parse_named(".env", content.into())
+    }
+
+    /// Read and parse a dotenv file.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`read`](super::read).
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, DotenvError> {
+        let path = path.as_ref();
+        let content = super::read_to_string(path)?;
+        Self::parse_named(&path.display().to_string(), content)
+    }
+
+    /// Write the document to a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotenvError::WriteError`] if the file can't be written.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), DotenvError> {
+        let path = path.as_ref();
+        std::fs::write(path, &self.content).map_err(|source| DotenvError::WriteError {
+            path: path.display().to_string(),
+            source,
+        })
+    }
+
+    /// The value of `key`, from its last definition.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.key == key)
+            .map(|entry| entry.value.as_str())
+    }
+
+    /// The defined keys, in the order they are first defined.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(index, entry)| {
+                self.entries[..*index]
+                    .iter()
+                    .all(|earlier| earlier.key != entry.key)
+            })
+            .map(|(_, entry)| entry.key.as_str())
+    }
+
+    /// Set `key` to `value`.
+    ///
+    /// An existing key is updated in its last definition, keeping its
+    /// quoting style unless the value needs quotes or can't be written in
+    /// that style. A new key is appended at the end of the file. The value
+    /// is written literally: `$` is escaped rather than expanded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotenvError::InvalidKey`] if `key` isn't a valid name.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), DotenvError> {
+        validate(key)?;
+
+        if let Some(spans) = self.last(key).map(|index| self.spans[index].clone()) {
+            let mut written = Quote::of(&self.content[spans.value.clone()]).write(value);
+            // An unquoted value must be separated from an inline comment
+            if self.content[spans.value.end..].starts_with('#') && !written.ends_with(['"', '\'']) {
+                written.push(' ');
+            }
+            self.content.replace_range(spans.value, &written);
+        } else {
+            let newline = self.newline();
+            if !self.content.is_empty() && !self.content.ends_with(['\n', '\r']) {
+                self.content.push_str(newline);
+            }
+            self.content.push_str(key);
+            self.content.push('=');
+            self.content.push_str(&Quote::None.write(value));
+            self.content.push_str(newline);
+        }
+
+        self.reparse();
+        Ok(())
+    }
+
+    /// Remove every definition of `key`, returning its value.
+    ///
+    /// The lines defining `key` are removed, including inline comments;
+    /// comments on lines of their own are kept.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let value = self.get(key)?.to_string();
+
+        // Back to front, so the spans of earlier entries stay valid
+        for index in (0..self.entries.len()).rev() {
+            if self.entries[index].key == key {
+                let lines = self.spans[index].lines.clone();
+                let rest = &self.content[lines.end..];
+                let line_break = if rest.starts_with("\r\n") {
+                    2
+                } else {
+                    usize::from(rest.starts_with(['\n', '\r']))
+                };
+                self.content
+                    .replace_range(lines.start..lines.end + line_break, "");
+            }
+        }
+
+        self.reparse();
+        Some(value)
+    }
+
+    /// Rename every definition of `from` to `to`.
+    ///
+    /// Returns `false`, leaving the document unchanged, if `from` isn't
+    /// defined or `to` already is. References to `from` in other values are
+    /// not rewritten.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotenvError::InvalidKey`] if `to` isn't a valid name.
+    pub fn rename(&mut self, from: &str, to: &str) -> Result<bool, DotenvError> {
+        validate(to)?;
+
+        if self.get(from).is_none() || self.get(to).is_some() {
+            return Ok(false);
+        }
+
+        for index in (0..self.entries.len()).rev() {
+            if self.entries[index].key == from {
+                self.content
+                    .replace_range(self.spans[index].key.clone(), to);
+            }
+        }
+
+        self.reparse();
+        Ok(true)
+    }
+
+    /// The document content, as it would be written.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.content
+    }
+
+    fn parse_named(name: &str, content: String) -> Result<Self, DotenvError> {
+        let parsed = parser::parse(&content, &|_| None);
+        super::check(name, &content, parsed.errors)?;
+
+        Ok(Self {
+            content,
+            entries: parsed.entries,
+            spans: parsed.spans,
+        })
+    }
+
+    /// Refresh the entries after an edit.
+    fn reparse(&mut self) {
+        let parsed = parser::parse(&self.content, &|_| None);
+        debug_assert!(
+            parsed.errors.is_empty(),
+            "edit produced malformed dotenv content: {:?}",
+            parsed.errors
+        );
+
+        self.entries = parsed.entries;
+        self.spans = parsed.spans;
+    }
+
+    /// Index of the last definition of `key`.
+    fn last(&self, key: &str) -> Option<usize> {
+        self.entries.iter().rposition(|entry| entry.key == key)
+    }
+
+    /// The line break used by the file, `\n` unless it uses `\r\n`.
+    fn newline(&self) -> &'static str {
+        if self.content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        }
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.content)
+    }
+}
+
+fn validate(key: &str) -> Result<(), DotenvError> {
+    if parser::is_key(key) {
+        Ok(())
+    } else {
+        Err(DotenvError::InvalidKey {
+            key: key.to_string(),
+        })
+    }
+}
+
+/// The quoting style of a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quote {
+    None,
+    Single,
+    Double,
+}
+
+impl Quote {
+    /// The style of a value as written.
+    fn of(raw: &str) -> Self {
+        if raw.starts_with('\'') {
+            Self::Single
+        } else if raw.starts_with('"') {
+            Self::Double
+        } else {
+            Self::None
+        }
+    }
+
+    /// Write `value` in this style, falling back to double quotes where
+    /// the style can't represent it.
+    fn write(self, value: &str) -> String {
+        match self {
+            Self::None if is_plain(value) => value.to_string(),
+            Self::Single if !value.contains('\'') => format!("'{value}'"),
+            _ => {
+                let mut written = String::with_capacity(value.len() + 2);
+                written.push('"');
+                for c in value.chars() {
+                    match c {
+                        '\\' | '"' | '$' => {
+                            written.push('\\');
+                            written.push(c);
+                        }
+                        '\r' => written.push_str("\\r"),
+                        other => written.push(other),
+                    }
+                }
+                written.push('"');
+                written
+            }
+        }
+    }
+}
+
+/// Whether `value` reads back unchanged without quotes.
+fn is_plain(value: &str) -> bool {
+    value.trim() == value && !value.contains(['\n', '\r', '\\', '$', '#', '\'', '"'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = concat!(
+        "# Database\n",
+        "export DB_HOST=localhost   # dev only\n",
+        "DB_PASS='s3cret'\n",
+        "\n",
+        "# Logging\n",
+        "LOG_FORMAT=\"json\"\n",
+        "EMPTY= # set me\n",
+    );
+
+    #[test]
+    fn test_unchanged_document_round_trips() {
+        let document = Document::parse(CONTENT).unwrap();
+
+        assert_eq!(document.to_string(), CONTENT);
+        assert_eq!(document.get("DB_HOST"), Some("localhost"));
+        assert_eq!(
+            document.keys().collect::<Vec<_>>(),
+            ["DB_HOST", "DB_PASS", "LOG_FORMAT", "EMPTY"]
+        );
+    }
+
+    #[test]
+    fn test_set_keeps_formatting_and_quoting() {
+        let mut document = Document::parse(CONTENT).unwrap();
+        document.set("DB_HOST", "db.internal").unwrap();
+        document.set("DB_PASS", "n3w").unwrap();
+        document.set("LOG_FORMAT", "$pretty").unwrap();
+        document.set("EMPTY", "filled").unwrap();
+        document.set("NEW_KEY", "two words").unwrap();
+
+        assert_eq!(
+            document.as_str(),
+            concat!(
+                "# Database\n",
+                "export DB_HOST=db.internal   # dev only\n",
+                "DB_PASS='n3w'\n",
+                "\n",
+                "# Logging\n",
+                "LOG_FORMAT=\"\\$pretty\"\n",
+                "EMPTY= filled # set me\n",
+                "NEW_KEY=two words\n",
+            )
+        );
+        assert_eq!(document.get("LOG_FORMAT"), Some("$pretty"));
+    }
+
+    #[test]
+    fn test_set_quotes_values_that_need_it() {
+        let mut document = Document::parse("PLAIN=a\nSINGLE='b'").unwrap();
+        document.set("PLAIN", " padded # \"x\"").unwrap();
+        document.set("SINGLE", "it's").unwrap();
+
+        assert_eq!(
+            document.as_str(),
+            "PLAIN=\" padded # \\\"x\\\"\"\nSINGLE=\"it's\""
+        );
+        assert_eq!(document.get("PLAIN"), Some(" padded # \"x\""));
+        assert_eq!(document.get("SINGLE"), Some("it's"));
+    }
+
+    #[test]
+    fn test_remove_and_rename() {
+        let mut document = Document::parse("A=1\r\n# keep\r\nB=2\r\nA=3\r\n").unwrap();
+
+        assert_eq!(document.remove("A"), Some("3".to_string()));
+        assert_eq!(document.remove("A"), None);
+        assert_eq!(document.as_str(), "# keep\r\nB=2\r\n");
+
+        assert!(document.rename("B", "C").unwrap());
+        assert!(!document.rename("B", "C").unwrap());
+        document.set("D", "4").unwrap();
+        assert_eq!(document.as_str(), "# keep\r\nC=2\r\nD=4\r\n");
+    }
+
+    #[test]
+    fn test_invalid_keys_are_rejected() {
+        let mut document = Document::parse("A=1").unwrap();
+
+        let error = document.set("1BAD", "x").unwrap_err();
+        assert!(matches!(error, DotenvError::InvalidKey { .. }));
+        let error = document.rename("A", "NO SPACES").unwrap_err();
+        assert!(matches!(error, DotenvError::InvalidKey { .. }));
+        assert_eq!(document.as_str(), "A=1");
+    }
+
+    #[test]
+    fn test_read_and_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        std::fs::write(&path, "# generated\nPORT=8080").unwrap();
+
+        let mut document = Document::read(&path).unwrap();
+        document.set("SECRET", "abc").unwrap();
+        document.write(&path).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# generated\nPORT=8080\nSECRET=abc\n"
+        );
+        assert!(matches!(
+            Document::parse("BROKEN").unwrap_err(),
+            DotenvError::Syntax { .. }
+        ));
+    }
+}
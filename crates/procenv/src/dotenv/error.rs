@@ -0,0 +1,124 @@
+//! Dotenv error types with rich diagnostics.
+
+use miette::{Diagnostic, NamedSource, SourceSpan};
+
+/// Error type for reading and parsing dotenv files.
+///
+/// Syntax errors point at the malformed part of the line:
+///
+/// ```text
+/// Error: procenv::dotenv::syntax_error
+///   × dotenv syntax error in .env at line 3, column 9
+///    ╭─[.env:3:9]
+///  2 │ PORT=8080
+///  3 │ API_KEY="abc
+///    ·         ──┬─
+///    ·           ╰── unterminated double-quoted value
+///    ╰────
+///   help: add the closing `"`
+/// ```
+#[derive(Debug, Diagnostic, thiserror::Error)]
+#[non_exhaustive]
+pub enum DotenvError {
+    /// Dotenv file not found
+    #[error("dotenv file not found: {path}")]
+    #[diagnostic(
+        code(procenv::dotenv::not_found),
+        help("ensure the file exists at the specified path")
+    )]
+    NotFound {
+        /// Path to the missing file
+        path: String,
+    },
+
+    /// Failed to read a dotenv file
+    #[error("failed to read dotenv file: {path}")]
+    #[diagnostic(
+        code(procenv::dotenv::read_error),
+        help("check file permissions and ensure it's readable")
+    )]
+    ReadError {
+        /// Path to the file
+        path: String,
+
+        /// The underlying I/O error
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Failed to write a dotenv file
+    #[error("failed to write dotenv file: {path}")]
+    #[diagnostic(
+        code(procenv::dotenv::write_error),
+        help("check that the directory exists and is writable")
+    )]
+    WriteError {
+        /// Path to the file
+        path: String,
+
+        /// The underlying I/O error
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// An invalid variable name passed to [`Document`](super::Document)
+    #[error("invalid dotenv variable name: `{key}`")]
+    #[diagnostic(
+        code(procenv::dotenv::invalid_key),
+        help("names start with a letter or `_`, followed by letters, digits, `_` or `.`")
+    )]
+    InvalidKey {
+        /// The rejected name
+        key: String,
+    },
+
+    /// A malformed line
+    #[error("dotenv syntax error in {path} at line {line}, column {column}")]
+    #[diagnostic(code(procenv::dotenv::syntax_error))]
+    Syntax {
+        /// Path to the file
+        path: String,
+
+        /// Line of the error (1-based)
+        line: usize,
+
+        /// Column of the error (1-based, in characters)
+        column: usize,
+
+        /// The source file content for display
+        #[source_code]
+        src: NamedSource<String>,
+
+        /// The location of the error
+        #[label("{message}")]
+        span: SourceSpan,
+
+        /// Description of what went wrong
+        message: String,
+
+        /// Suggestion for how to fix
+        #[help]
+        help: String,
+    },
+
+    /// Several malformed lines
+    #[error("{} dotenv syntax errors in {path}", errors.len())]
+    #[diagnostic(code(procenv::dotenv::multiple_errors), help("fix all listed lines"))]
+    Multiple {
+        /// Path to the file
+        path: String,
+
+        /// One [`DotenvError::Syntax`] per malformed line
+        #[related]
+        errors: Vec<Self>,
+    },
+
+    /// Errors in several files of a layered load
+    #[error("{} dotenv files failed to load", errors.len())]
+    #[diagnostic(code(procenv::dotenv::multiple_files), help("fix all listed files"))]
+    Files {
+        /// One error per failing file
+        #[related]
+        errors: Vec<Self>,
+    },
+}
@@ -0,0 +1,434 @@
+//! Dotenv file parsing and loading.
+//!
+//! This module implements the dotenv syntax used by both the derive's
+//! `#[env_config(dotenv)]` loading and the [`DotenvProvider`]. It is enabled
+//! with the `dotenv` feature flag.
+//!
+//! # Syntax
+//!
+//! ```text
+//! # Comments start with `#`
+//! export PORT=8080                  # `export` is optional
+//! NAME = unquoted value             # trailing whitespace is trimmed
+//! TOOLS=C:\tools                    # unquoted backslashes are literal
+//! LITERAL='no $expansion or \n'     # single quotes are taken literally
+//! GREETING="hello\tworld"           # double quotes support \n \r \t \\ \" \$
+//! DATA_DIR=$HOME/data               # `$NAME` and `${NAME}` are expanded
+//! LOG_LEVEL=${LOG_LEVEL:-info}      # `${NAME:-default}` if unset or empty
+//! CERT="-----BEGIN CERTIFICATE-----
+//! MIIB...
+//! -----END CERTIFICATE-----"        # quoted values may span lines
+//! ```
+//!
+//! References are resolved from the process environment first, then from
+//! earlier entries. Within a file the last definition of a key wins.
+//!
+//! # Layering
+//!
+//! Several files are layered with [`read_layered`]: a key defined in more
+//! than one file takes its value from the first file listed, like
+//! `#[env_config(dotenv = [...])]` and `dotenvy`.
+//!
+//! # Editing
+//!
+//! [`Document`] edits a file in place, keeping its comments, blank lines,
+//! quoting and order:
+//!
+//! ```rust,ignore
+//! use procenv::dotenv::Document;
+//!
+//! let mut env = Document::read(".env")?;
+//! env.set("SESSION_SECRET", &generate_secret())?;
+//! env.write(".env")?;
+//! ```
+//!
+//! # Errors
+//!
+//! Malformed lines are not skipped: every one of them is reported as a
+//! [`DotenvError::Syntax`] diagnostic pointing at its line and column.
+//!
+//! [`DotenvProvider`]: crate::DotenvProvider
+
+// DotenvError is intentionally large to provide rich miette diagnostics with source spans
+#![allow(clippy::result_large_err)]
+
+mod document;
+mod error;
+mod parser;
+
+pub use document::Document;
+pub use error::DotenvError;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use miette::NamedSource;
+
+/// A `KEY=value` entry of a dotenv file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The variable name.
+    pub key: String,
+
+    /// The value, with quotes removed and escapes and references resolved.
+    pub value: String,
+
+    /// Line the entry starts on (1-based).
+    pub line: usize,
+}
+
+/// Parse dotenv content.
+///
+/// Diagnostics refer to the content as `.env`; use [`read`] to parse a
+/// file under its own name.
+///
+/// # Errors
+///
+/// Returns [`DotenvError::Syntax`] for a malformed line, or
+/// [`DotenvError::Multiple`] if there are several.
+pub fn parse(content: &str) -> Result<Vec<Entry>, DotenvError> {
+    parse_named(".env", content, &|name| crate::env::var(name).ok())
+}
+
+/// Read and parse a dotenv file.
+///
+/// # Errors
+///
+/// Returns [`DotenvError::NotFound`] if the file doesn't exist,
+/// [`DotenvError::ReadError`] if it can't be read, and a syntax error if
+/// any line is malformed.
+pub fn read(path: impl AsRef<Path>) -> Result<Vec<Entry>, DotenvError> {
+    let path = path.as_ref();
+    let content = read_to_string(path)?;
+    parse_named(&path.display().to_string(), &content, &|name| {
+        crate::env::var(name).ok()
+    })
+}
+
+/// Load a dotenv file into the process environment.
+///
+/// Variables that are already set keep their values, so the real
+/// environment takes precedence over the file.
+///
+/// # Errors
+///
+/// Returns the same errors as [`read`]. Nothing is loaded if the file has
+/// a malformed line.
+pub fn load(path: impl AsRef<Path>) -> Result<(), DotenvError> {
+    apply(read(path)?);
+    Ok(())
+}
+
+/// Load dotenv files for the derive's `#[env_config(dotenv)]` support.
+///
+/// Relative paths are looked up in the current directory and its parents;
+/// files that aren't found are skipped. Otherwise this loads like
+/// [`read_layered`]: nothing is loaded if any file fails to read or parse.
+#[doc(hidden)]
+pub fn load_files(paths: &[&str]) -> Result<(), crate::Error> {
+    let files: Vec<DotenvFile> = paths
+        .iter()
+        .filter_map(|path| find(Path::new(path)))
+        .map(DotenvFile::optional)
+        .collect();
+
+    let values = read_layered(&files)?;
+    apply_values(
+        values
+            .into_iter()
+            .map(|(key, layered)| (key, layered.value))
+            .collect(),
+    );
+    Ok(())
+}
+
+/// A dotenv file in a layered load, e.g. via
+/// [`DotenvProvider::layered`](crate::DotenvProvider::layered).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotenvFile {
+    /// Path to the file.
+    path: PathBuf,
+
+    /// Whether a missing file is an error.
+    required: bool,
+}
+
+impl DotenvFile {
+    /// A file that must exist.
+    pub fn required(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            required: true,
+        }
+    }
+
+    /// A file that is skipped if it doesn't exist.
+    pub fn optional(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            required: false,
+        }
+    }
+
+    /// The path to the file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true` if a missing file is an error.
+    #[must_use]
+    pub const fn is_required(&self) -> bool {
+        self.required
+    }
+}
+
+/// A value after layering, with the file that defined it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayeredValue {
+    /// The value.
+    pub value: String,
+
+    /// The file the value was read from.
+    pub path: PathBuf,
+}
+
+/// Read dotenv files in precedence order: a key defined in several files
+/// takes its value from the first of them.
+///
+/// Later files may reference variables from earlier ones. Missing optional
+/// files are skipped.
+///
+/// # Errors
+///
+/// Every missing required file, unreadable file and malformed file is
+/// reported: as that file's error if there is one, or as
+/// [`DotenvError::Files`] if there are several.
+pub fn read_layered(files: &[DotenvFile]) -> Result<HashMap<String, LayeredValue>, DotenvError> {
+    let mut values: HashMap<String, LayeredValue> = HashMap::new();
+    let mut errors: Vec<DotenvError> = Vec::new();
+
+    for file in files {
+        let content = match read_to_string(&file.path) {
+            Ok(content) => content,
+            Err(DotenvError::NotFound { .. }) if !file.required => continue,
+            Err(error) => {
+                errors.push(error);
+                continue;
+            }
+        };
+
+        let lookup = |name: &str| {
+            crate::env::var(name)
+                .ok()
+                .or_else(|| values.get(name).map(|layered| layered.value.clone()))
+        };
+        match parse_named(&file.path.display().to_string(), &content, &lookup) {
+            Ok(entries) => {
+                // Within a file the last definition wins
+                let file_values: HashMap<String, String> = entries
+                    .into_iter()
+                    .map(|entry| (entry.key, entry.value))
+                    .collect();
+                for (key, value) in file_values {
+                    values.entry(key).or_insert_with(|| LayeredValue {
+                        value,
+                        path: file.path.clone(),
+                    });
+                }
+            }
+            Err(error) => errors.push(error),
+        }
+    }
+
+    match errors.len() {
+        0 => Ok(values),
+        1 => Err(errors.remove(0)),
+        _ => Err(DotenvError::Files { errors }),
+    }
+}
+
+/// Read a dotenv file, keeping the well-formed entries of malformed files.
+///
+/// Used by the hot reload watcher, where a half-edited file should not
+/// unset every variable it defines. `lookup` resolves `${VAR}` references
+/// as in [`parser::parse`]. Returns `None` if the file can't be read.
+pub(crate) fn read_lenient(
+    path: &Path,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Option<Vec<Entry>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    Some(parser::parse(&content, lookup).entries)
+}
+
+/// Parse content, turning syntax errors into diagnostics named `name`.
+fn parse_named(
+    name: &str,
+    content: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<Vec<Entry>, DotenvError> {
+    let parsed = parser::parse(content, lookup);
+    check(name, content, parsed.errors)?;
+    Ok(parsed.entries)
+}
+
+/// Turn syntax errors in the content named `name` into a diagnostic.
+fn check(name: &str, content: &str, errors: Vec<parser::SyntaxError>) -> Result<(), DotenvError> {
+    let mut errors: Vec<DotenvError> = errors
+        .into_iter()
+        .map(|error| DotenvError::Syntax {
+            path: name.to_string(),
+            line: parser::line_of(content, error.span.start),
+            column: parser::column_of(content, error.span.start),
+            src: NamedSource::new(name, content.to_string()),
+            span: error.span.into(),
+            message: error.message,
+            help: error.help,
+        })
+        .collect();
+
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.remove(0)),
+        _ => Err(DotenvError::Multiple {
+            path: name.to_string(),
+            errors,
+        }),
+    }
+}
+
+fn read_to_string(path: &Path) -> Result<String, DotenvError> {
+    std::fs::read_to_string(path).map_err(|source| {
+        if source.kind() == std::io::ErrorKind::NotFound {
+            DotenvError::NotFound {
+                path: path.display().to_string(),
+            }
+        } else {
+            DotenvError::ReadError {
+                path: path.display().to_string(),
+                source,
+            }
+        }
+    })
+}
+
+/// Find `path` in the current directory or its parents, like `dotenvy`.
+pub(crate) fn find(path: &Path) -> Option<PathBuf> {
+    if path.is_absolute() {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+
+    let cwd = std::env::current_dir().ok()?;
+    cwd.ancestors()
+        .map(|dir| dir.join(path))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Set the entries that aren't already in the environment.
+fn apply(entries: Vec<Entry>) {
+    apply_values(
+        entries
+            .into_iter()
+            .map(|entry| (entry.key, entry.value))
+            .collect(),
+    );
+}
+
+/// Set the values that aren't already in the environment.
+///
+/// During a hot reload they go into the watcher's overlay instead of the
+/// process environment (see [`crate::env`]).
+fn apply_values(values: HashMap<String, String>) {
+    for (key, value) in values {
+        let unset = matches!(crate::env::var(&key), Err(std::env::VarError::NotPresent));
+        if unset && !crate::env::set_overlaid(&key, &value) {
+            // SAFETY: same contract as `dotenvy`: dotenv files are loaded at
+            // startup, before other threads read the environment.
+            unsafe { std::env::set_var(key, value) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syntax_errors_render_with_location() {
+        let error = parse("PORT=8080\nAPI_KEY=\"abc\n").unwrap_err();
+
+        let DotenvError::Syntax {
+            line,
+            column,
+            ref message,
+            ..
+        } = error
+        else {
+            panic!("expected a syntax error, got {error:?}");
+        };
+        assert_eq!((line, column), (2, 9));
+        assert_eq!(message, "unterminated double-quoted value");
+        assert_eq!(
+            error.to_string(),
+            "dotenv syntax error in .env at line 2, column 9"
+        );
+    }
+
+    #[test]
+    fn test_multiple_errors_are_reported() {
+        let error = parse("A\nB\nC=ok").unwrap_err();
+        let DotenvError::Multiple { errors, .. } = error else {
+            panic!("expected multiple errors");
+        };
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn test_read_layered_first_file_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join(".env");
+        let local = dir.path().join(".env.local");
+        std::fs::write(&base, "LAYERED_A=base\nLAYERED_B=base\n").unwrap();
+        std::fs::write(&local, "LAYERED_A=local\nLAYERED_C=${LAYERED_B}-local\n").unwrap();
+
+        let values = read_layered(&[
+            DotenvFile::required(&base),
+            DotenvFile::required(&local),
+            DotenvFile::optional(dir.path().join(".env.missing")),
+        ])
+        .unwrap();
+
+        assert_eq!(values["LAYERED_A"].value, "base");
+        assert_eq!(values["LAYERED_A"].path, base);
+        assert_eq!(values["LAYERED_C"].value, "base-local");
+        assert_eq!(values["LAYERED_C"].path, local);
+    }
+
+    #[test]
+    fn test_read_layered_accumulates_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let malformed = dir.path().join(".env");
+        std::fs::write(&malformed, "NOT A LINE\n").unwrap();
+
+        let error = read_layered(&[
+            DotenvFile::required(&malformed),
+            DotenvFile::required(dir.path().join(".env.missing")),
+            // A directory exists but can't be read as a file
+            DotenvFile::optional(dir.path()),
+        ])
+        .unwrap_err();
+
+        let DotenvError::Files { errors } = error else {
+            panic!("expected errors for several files, got {error:?}");
+        };
+        assert!(matches!(errors[0], DotenvError::Syntax { .. }));
+        assert!(matches!(errors[1], DotenvError::NotFound { .. }));
+        assert!(matches!(errors[2], DotenvError::ReadError { .. }));
+    }
+
+    #[test]
+    fn test_read_missing_file() {
+        let error = read("/nonexistent/procenv/.env").unwrap_err();
+        assert!(matches!(error, DotenvError::NotFound { .. }));
+    }
+}
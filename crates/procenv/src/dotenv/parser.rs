@@ -0,0 +1,578 @@
+//! Dotenv syntax parser.
+//!
+//! Parses the whole content rather than line by line, so double- and
+//! single-quoted values can span lines. Malformed lines are recorded and
+//! skipped, so every error in a file is reported at once.
+
+use std::ops::Range;
+
+use super::Entry;
+
+/// A malformed line, before it is turned into a diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    /// Byte range of the offending text.
+    pub span: Range<usize>,
+    /// Description of what went wrong.
+    pub message: String,
+    /// Suggestion for how to fix it.
+    pub help: String,
+}
+
+impl SyntaxError {
+    fn new(span: Range<usize>, message: impl Into<String>, help: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+            help: help.into(),
+        }
+    }
+}
+
+/// Where an entry is in the source, for lossless editing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spans {
+    /// From the start of the entry's first line to the end of its last
+    /// line, excluding the line break.
+    pub lines: Range<usize>,
+    /// The variable name.
+    pub key: Range<usize>,
+    /// The value as written, including quotes.
+    pub value: Range<usize>,
+}
+
+/// The outcome of parsing: the well-formed entries and the errors.
+#[derive(Debug, Default)]
+pub struct Parsed {
+    /// Entries in file order, including duplicates.
+    pub entries: Vec<Entry>,
+    /// Location of each entry, in the same order.
+    pub spans: Vec<Spans>,
+    /// One error per malformed line.
+    pub errors: Vec<SyntaxError>,
+}
+
+/// Parse dotenv content.
+///
+/// `lookup` resolves `${VAR}` references; names it doesn't know fall back
+/// to earlier entries of the same content, then to the empty string.
+pub fn parse(content: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Parsed {
+    let mut parser = Parser {
+        src: content,
+        pos: 0,
+        lookup,
+        parsed: Parsed::default(),
+    };
+    parser.run();
+    parser.parsed
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    /// Byte offset of the next unread character.
+    pos: usize,
+    lookup: &'a dyn Fn(&str) -> Option<String>,
+    parsed: Parsed,
+}
+
+impl Parser<'_> {
+    fn run(&mut self) {
+        while self.pos < self.src.len() {
+            self.skip_blanks();
+
+            match self.peek() {
+                None => break,
+                Some('\n' | '\r') => self.pos += 1,
+                Some('#') => self.skip_line(),
+                Some(_) => {
+                    let line = line_of(self.src, self.pos);
+                    if let Err(error) = self.entry(line) {
+                        self.parsed.errors.push(error);
+                        self.skip_line();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Parse `[export] KEY = VALUE [# comment]`.
+    fn entry(&mut self, line: usize) -> Result<(), SyntaxError> {
+        if self.rest().starts_with("export") && self.rest()[6..].starts_with([' ', '\t']) {
+            self.pos += 6;
+            self.skip_blanks();
+        }
+
+        let key_start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+        {
+            self.pos += 1;
+        }
+        let key = &self.src[key_start..self.pos];
+
+        if key.is_empty() || key.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+            let end = if key.is_empty() {
+                self.char_end()
+            } else {
+                self.pos
+            };
+            return Err(SyntaxError::new(
+                key_start..end,
+                "expected a variable name",
+                "names start with a letter or `_`, followed by letters, digits, `_` or `.`",
+            ));
+        }
+
+        self.skip_blanks();
+        match self.peek() {
+            Some('=') => self.pos += 1,
+            None | Some('\n' | '\r') => {
+                return Err(SyntaxError::new(
+                    key_start..self.pos,
+                    format!("expected `=` after `{key}`"),
+                    format!("assign a value, e.g. `{key}=value`, or `{key}=` for an empty one"),
+                ));
+            }
+            Some(_) => {
+                return Err(SyntaxError::new(
+                    self.pos..self.char_end(),
+                    "invalid character in variable name",
+                    "names may only contain letters, digits, `_` and `.`",
+                ));
+            }
+        }
+        self.skip_blanks();
+
+        let value_start = self.pos;
+        let (value, value_end) = match self.peek() {
+            Some(quote @ ('\'' | '"')) => {
+                let value = if quote == '"' {
+                    self.double_quoted()?
+                } else {
+                    self.single_quoted()?
+                };
+                let value_end = self.pos;
+                self.end_of_quoted()?;
+                (value, value_end)
+            }
+            _ => self.unquoted()?,
+        };
+
+        self.parsed.spans.push(Spans {
+            lines: line_start(self.src, key_start)..self.pos,
+            key: key_start..key_start + key.len(),
+            value: value_start..value_end,
+        });
+        self.parsed.entries.push(Entry {
+            key: key.to_string(),
+            value,
+            line,
+        });
+        Ok(())
+    }
+
+    /// A single-quoted value: taken literally, without escapes or expansion.
+    fn single_quoted(&mut self) -> Result<String, SyntaxError> {
+        let open = self.pos;
+        self.pos += 1;
+
+        let Some(len) = self.rest().find('\'') else {
+            return Err(self.unterminated(open, '\''));
+        };
+        let value = self.rest()[..len].to_string();
+        self.pos += len + 1;
+
+        Ok(value)
+    }
+
+    /// A double-quoted value: supports escapes and expansion.
+    fn double_quoted(&mut self) -> Result<String, SyntaxError> {
+        let open = self.pos;
+        self.pos += 1;
+        let mut value = String::new();
+
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.unterminated(open, '"'));
+            };
+            self.pos += c.len_utf8();
+
+            match c {
+                '"' => break,
+                '\\' => match self.peek() {
+                    Some(escaped) => {
+                        self.pos += escaped.len_utf8();
+                        match escaped {
+                            'n' => value.push('\n'),
+                            'r' => value.push('\r'),
+                            't' => value.push('\t'),
+                            '\\' | '"' | '$' | '\'' => value.push(escaped),
+                            other => {
+                                value.push('\\');
+                                value.push(other);
+                            }
+                        }
+                    }
+                    None => return Err(self.unterminated(open, '"')),
+                },
+                '$' => self.expand(&mut value)?,
+                other => value.push(other),
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// An unquoted value: runs to the end of the line or an inline comment.
+    /// References are expanded, but there are no escapes.
+    ///
+    /// Also returns the end of the value as written, without trailing blanks.
+    fn unquoted(&mut self) -> Result<(String, usize), SyntaxError> {
+        let mut value = String::new();
+        // Length of `value` without trailing blanks
+        let mut trimmed = 0;
+        let mut end = self.pos;
+
+        while let Some(c) = self.peek() {
+            if c == '\n' || c == '\r' {
+                break;
+            }
+            // A `#` starts a comment only after whitespace (or right after `=`)
+            if c == '#' && (value.is_empty() || value.len() > trimmed) {
+                self.skip_line();
+                break;
+            }
+            self.pos += c.len_utf8();
+
+            // Backslashes are literal, so Windows paths need no quoting
+            match c {
+                '$' => self.expand(&mut value)?,
+                other => value.push(other),
+            }
+
+            if !matches!(c, ' ' | '\t') {
+                trimmed = value.len();
+                end = self.pos;
+            }
+        }
+
+        value.truncate(trimmed);
+        Ok((value, end))
+    }
+
+    /// Expand `$NAME`, `${NAME}` or `${NAME:-default}` after a `$`.
+    fn expand(&mut self, value: &mut String) -> Result<(), SyntaxError> {
+        let dollar = self.pos - 1;
+
+        if self.peek() == Some('{') {
+            let Some(len) = self
+                .rest()
+                .find(['}', '\n'])
+                .filter(|len| self.rest()[*len..].starts_with('}'))
+            else {
+                return Err(SyntaxError::new(
+                    dollar..self.line_end(),
+                    "unterminated `${` expansion",
+                    "close the reference with `}`, e.g. `${NAME}`",
+                ));
+            };
+            let src = self.src;
+            let inner = &src[self.pos + 1..self.pos + len];
+            self.pos += len + 1;
+
+            let (name, default) = match inner.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (inner, None),
+            };
+            if !is_name(name) {
+                return Err(SyntaxError::new(
+                    dollar..self.pos,
+                    format!("invalid variable name `{name}` in expansion"),
+                    "names start with a letter or `_`, followed by letters, digits or `_`",
+                ));
+            }
+
+            match self.resolve(name) {
+                Some(resolved) if !resolved.is_empty() || default.is_none() => {
+                    value.push_str(&resolved);
+                }
+                _ => value.push_str(default.unwrap_or_default()),
+            }
+        } else if self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        {
+            let start = self.pos;
+            while self
+                .peek()
+                .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                self.pos += 1;
+            }
+            let name = &self.src[start..self.pos];
+            value.push_str(&self.resolve(name).unwrap_or_default());
+        } else {
+            value.push('$');
+        }
+
+        Ok(())
+    }
+
+    /// Resolve a referenced variable.
+    fn resolve(&self, name: &str) -> Option<String> {
+        (self.lookup)(name).or_else(|| {
+            self.parsed
+                .entries
+                .iter()
+                .rev()
+                .find(|entry| entry.key == name)
+                .map(|entry| entry.value.clone())
+        })
+    }
+
+    /// Only blanks or a comment may follow a closing quote.
+    fn end_of_quoted(&mut self) -> Result<(), SyntaxError> {
+        self.skip_blanks();
+
+        match self.peek() {
+            None | Some('\n' | '\r') => Ok(()),
+            Some('#') => {
+                self.skip_line();
+                Ok(())
+            }
+            Some(_) => Err(SyntaxError::new(
+                self.pos..self.line_end(),
+                "unexpected characters after the closing quote",
+                "quote the whole value, or put a space before an inline `#` comment",
+            )),
+        }
+    }
+
+    /// Report an unterminated quote, resuming after the line it opened on.
+    fn unterminated(&mut self, open: usize, quote: char) -> SyntaxError {
+        let kind = if quote == '"' { "double" } else { "single" };
+        let end = line_end(self.src, open);
+        self.pos = open;
+
+        SyntaxError::new(
+            open..end,
+            format!("unterminated {kind}-quoted value"),
+            format!("add the closing `{quote}`"),
+        )
+    }
+
+    fn rest(&self) -> &str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// End of the character at the current position.
+    fn char_end(&self) -> usize {
+        self.pos + self.peek().map_or(0, char::len_utf8)
+    }
+
+    fn line_end(&self) -> usize {
+        line_end(self.src, self.pos)
+    }
+
+    fn skip_blanks(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_line(&mut self) {
+        self.pos = self.line_end();
+    }
+}
+
+/// Byte offset of the end of the line containing `pos`.
+fn line_end(src: &str, pos: usize) -> usize {
+    src[pos..]
+        .find(['\n', '\r'])
+        .map_or(src.len(), |len| pos + len)
+}
+
+/// Byte offset of the start of the line containing `pos`.
+fn line_start(src: &str, pos: usize) -> usize {
+    src[..pos].rfind(['\n', '\r']).map_or(0, |i| i + 1)
+}
+
+/// Line (1-based) containing byte offset `pos`.
+pub fn line_of(src: &str, pos: usize) -> usize {
+    src[..pos].matches('\n').count() + 1
+}
+
+/// Column (1-based, in characters) of byte offset `pos`.
+pub fn column_of(src: &str, pos: usize) -> usize {
+    let line_start = src[..pos].rfind('\n').map_or(0, |i| i + 1);
+    src[line_start..pos].chars().count() + 1
+}
+
+/// Whether `key` is a valid variable name for an entry.
+pub fn is_key(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// Whether `name` is a valid variable name for expansion.
+fn is_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_ok(content: &str) -> Vec<(String, String)> {
+        let parsed = parse(content, &|_| None);
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        parsed
+            .entries
+            .into_iter()
+            .map(|entry| (entry.key, entry.value))
+            .collect()
+    }
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_unquoted_values() {
+        let content = "A=1\nB = two words  \nC=#not a comment\nD=value # comment\nE=\nexport F=exported\nG=a#b";
+
+        assert_eq!(
+            parse_ok(content),
+            pairs(&[
+                ("A", "1"),
+                ("B", "two words"),
+                ("C", ""),
+                ("D", "value"),
+                ("E", ""),
+                ("F", "exported"),
+                ("G", "a#b"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_unquoted_backslashes_are_literal() {
+        let content = "PATH_HINT=C:\\tools\\bin\nSHARE=\\\\server\\$USER\nTRAILING=dir\\\n";
+        let lookup = |name: &str| (name == "USER").then(|| "app".to_string());
+        let parsed = parse(content, &lookup);
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+
+        let values: Vec<&str> = parsed.entries.iter().map(|e| e.value.as_str()).collect();
+        assert_eq!(values, ["C:\\tools\\bin", "\\\\server\\app", "dir\\"]);
+    }
+
+    #[test]
+    fn test_quoted_values() {
+        let content = concat!(
+            "SINGLE='no $EXPANSION \\n here' # comment\n",
+            "DOUBLE=\"tab\\there \\\"quoted\\\" \\$HOME\"\n",
+            "PEM=\"-----BEGIN KEY-----\nabc\n-----END KEY-----\"\n",
+            "AFTER=1\n",
+        );
+
+        assert_eq!(
+            parse_ok(content),
+            pairs(&[
+                ("SINGLE", "no $EXPANSION \\n here"),
+                ("DOUBLE", "tab\there \"quoted\" $HOME"),
+                ("PEM", "-----BEGIN KEY-----\nabc\n-----END KEY-----"),
+                ("AFTER", "1"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_expansion() {
+        let lookup = |name: &str| (name == "HOME").then(|| "/home/app".to_string());
+        let content = concat!(
+            "DIR=$HOME/data\n",
+            "LOG=\"${DIR}/log\"\n",
+            "LEVEL=$",
+            "{LEVEL:-info}\n",
+            "EMPTY=${UNSET}\n",
+            "PRICE=5$",
+        );
+        let parsed = parse(content, &lookup);
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+
+        let values: Vec<&str> = parsed.entries.iter().map(|e| e.value.as_str()).collect();
+        assert_eq!(
+            values,
+            ["/home/app/data", "/home/app/data/log", "info", "", "5$"]
+        );
+    }
+
+    #[test]
+    fn test_errors_are_collected() {
+        let content = "GOOD=1\nNO_EQUALS\n1BAD=x\nQUOTE=\"open\nTRAIL='x' y\nLAST=ok";
+        let parsed = parse(content, &|_| None);
+
+        let keys: Vec<&str> = parsed.entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["GOOD", "LAST"]);
+
+        let messages: Vec<&str> = parsed.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "expected `=` after `NO_EQUALS`",
+                "expected a variable name",
+                "unterminated double-quoted value",
+                "unexpected characters after the closing quote",
+            ]
+        );
+
+        let quote = &parsed.errors[2];
+        assert_eq!(&content[quote.span.clone()], "\"open");
+        assert_eq!(line_of(content, quote.span.start), 4);
+        assert_eq!(column_of(content, quote.span.start), 7);
+    }
+
+    #[test]
+    fn test_entries_record_lines() {
+        let parsed = parse("# header\n\nA=1\nB=\"x\ny\"\nC=3", &|_| None);
+        let lines: Vec<usize> = parsed.entries.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [3, 4, 6]);
+    }
+
+    #[test]
+    fn test_entries_record_spans() {
+        let content = "A=1\n  export B = 'two' # note\nC=x y  # trailing\n";
+        let parsed = parse(content, &|_| None);
+
+        let spans: Vec<(&str, &str, &str)> = parsed
+            .spans
+            .iter()
+            .map(|spans| {
+                (
+                    &content[spans.lines.clone()],
+                    &content[spans.key.clone()],
+                    &content[spans.value.clone()],
+                )
+            })
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                ("A=1", "A", "1"),
+                ("  export B = 'two' # note", "B", "'two'"),
+                ("C=x y  # trailing", "C", "x y"),
+            ]
+        );
+    }
+}
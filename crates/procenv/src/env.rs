@@ -0,0 +1,139 @@
+//! Environment variable lookup.
+//!
+//! Generated code and the built-in providers read variables through [`var`]
+//! and [`vars`] rather than their [`std::env`](mod@std::env) counterparts. Outside of a hot
+//! reload the two are the same. While the watcher reloads a configuration
+//! with watched dotenv files, it installs the values of those files as an
+//! overlay on the reloading thread, so edits take effect without the process
+//! environment being modified.
+//!
+//! Reload functions that read variables themselves should use these
+//! functions to see the same values as the derive.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::env::VarError;
+use std::ffi::OsStr;
+
+/// Variables replaced while reloading: `Some` sets a value, `None` unsets it.
+type Overlay = HashMap<String, Option<String>>;
+
+thread_local! {
+    static OVERLAY: RefCell<Option<Overlay>> = const { RefCell::new(None) };
+}
+
+/// Read an environment variable.
+///
+/// Like [`std::env::var`], but sees the dotenv values installed by the
+/// watcher during a reload.
+///
+/// # Errors
+///
+/// Returns [`VarError::NotPresent`] if the variable is unset and
+/// [`VarError::NotUnicode`] if its value is not valid unicode.
+pub fn var(name: impl AsRef<OsStr>) -> Result<String, VarError> {
+    let name = name.as_ref();
+    let overlaid = OVERLAY.with_borrow(|overlay| {
+        overlay
+            .as_ref()
+            .zip(name.to_str())
+            .and_then(|(overlay, name)| overlay.get(name).cloned())
+    });
+
+    match overlaid {
+        Some(Some(value)) => Ok(value),
+        Some(None) => Err(VarError::NotPresent),
+        None => std::env::var(name),
+    }
+}
+
+/// All environment variables with unicode names and values.
+///
+/// Like [`std::env::vars`], but sees the dotenv values installed by the
+/// watcher during a reload. Variables that aren't valid unicode are skipped.
+#[must_use]
+pub fn vars() -> Vec<(String, String)> {
+    let process = std::env::vars_os()
+        .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)));
+
+    OVERLAY.with_borrow(|overlay| match overlay {
+        None => process.collect(),
+        Some(overlay) => process
+            .filter(|(key, _)| !overlay.contains_key(key))
+            .chain(
+                overlay
+                    .iter()
+                    .filter_map(|(key, value)| Some((key.clone(), value.clone()?))),
+            )
+            .collect(),
+    })
+}
+
+/// Set `name` in the active overlay.
+///
+/// Returns `false` if no overlay is installed on this thread.
+#[cfg(feature = "dotenv")]
+pub(crate) fn set_overlaid(name: &str, value: &str) -> bool {
+    OVERLAY.with_borrow_mut(|overlay| {
+        overlay.as_mut().is_some_and(|overlay| {
+            overlay.insert(name.to_string(), Some(value.to_string()));
+            true
+        })
+    })
+}
+
+/// Run `f` with `overlay` in front of the process environment on this thread.
+#[cfg(feature = "watch")]
+pub(crate) fn with_overlay<R>(overlay: Overlay, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous overlay, even if `f` panics.
+    struct Restore(Option<Overlay>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            OVERLAY.with_borrow_mut(|overlay| *overlay = previous);
+        }
+    }
+
+    let previous = OVERLAY.with_borrow_mut(|current| current.replace(overlay));
+    let _restore = Restore(previous);
+    f()
+}
+
+#[cfg(all(test, feature = "watch"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay_shadows_process_env() {
+        unsafe {
+            std::env::set_var("PROCENV_OVERLAY_KEPT", "process");
+            std::env::set_var("PROCENV_OVERLAY_UNSET", "process");
+        }
+
+        let overlay = HashMap::from([
+            (
+                "PROCENV_OVERLAY_SET".to_string(),
+                Some("overlay".to_string()),
+            ),
+            ("PROCENV_OVERLAY_UNSET".to_string(), None),
+        ]);
+        with_overlay(overlay, || {
+            assert_eq!(var("PROCENV_OVERLAY_SET").unwrap(), "overlay");
+            assert_eq!(var("PROCENV_OVERLAY_KEPT").unwrap(), "process");
+            assert!(var("PROCENV_OVERLAY_UNSET").is_err());
+
+            let vars: HashMap<String, String> = vars().into_iter().collect();
+            assert_eq!(vars["PROCENV_OVERLAY_SET"], "overlay");
+            assert!(!vars.contains_key("PROCENV_OVERLAY_UNSET"));
+        });
+
+        assert!(var("PROCENV_OVERLAY_SET").is_err());
+        assert_eq!(var("PROCENV_OVERLAY_UNSET").unwrap(), "process");
+
+        unsafe {
+            std::env::remove_var("PROCENV_OVERLAY_KEPT");
+            std::env::remove_var("PROCENV_OVERLAY_UNSET");
+        }
+    }
+}
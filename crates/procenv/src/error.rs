@@ -12,6 +12,7 @@
 //! | [`Error::Parse`] | Value failed to parse as expected type |
 //! | [`Error::Multiple`] | Multiple configuration errors accumulated |
 //! | [`Error::File`] | Configuration file error (with `file` feature) |
+//! | [`Error::Dotenv`] | Dotenv file error (with `dotenv` feature) |
 //! | [`Error::InvalidProfile`] | Invalid profile name specified |
 //! | [`Error::Provider`] | Custom provider operation failed |
 //! | [`Error::Validation`] | Validation constraint violated (with `validator` feature) |
@@ -51,6 +52,8 @@ use std::fmt::{self, Debug, Display, Formatter};
 
 use miette::Diagnostic;
 
+#[cfg(feature = "dotenv")]
+use crate::dotenv::DotenvError;
 #[cfg(feature = "file")]
 use crate::file;
 
@@ -280,7 +283,7 @@ pub enum Error {
         /// All accumulated errors.
         /// miette renders these as related diagnostics.
         #[related]
-        errors: Vec<Error>,
+        errors: Vec<Self>,
     },
 
     /// An error occurred while loading a configuration file.
@@ -295,6 +298,18 @@ pub enum Error {
         source: file::FileError,
     },
 
+    /// An error occurred while loading a dotenv file.
+    ///
+    /// Syntax errors carry the file content, so miette displays the
+    /// malformed line. The error is boxed to keep `Error` small.
+    #[cfg(feature = "dotenv")]
+    #[diagnostic(transparent)]
+    Dotenv {
+        /// The underlying dotenv error with source location.
+        #[diagnostic_source]
+        source: Box<DotenvError>,
+    },
+
     /// An invalid profile was specified.
     ///
     /// This occurs when the profile environment variable contains a value
@@ -399,6 +414,15 @@ impl From<file::FileError> for Error {
     }
 }
 
+#[cfg(feature = "dotenv")]
+impl From<DotenvError> for Error {
+    fn from(source: DotenvError) -> Self {
+        Self::Dotenv {
+            source: Box::new(source),
+        }
+    }
+}
+
 // Manual Display impl for secret masking
 // Note: For fancy formatted output, use `miette::Report::from(error)`
 impl Display for Error {
@@ -434,6 +458,11 @@ impl Display for Error {
                 write!(f, "configuration file error: {source}")
             }
 
+            #[cfg(feature = "dotenv")]
+            Self::Dotenv { source } => {
+                write!(f, "dotenv file error: {source}")
+            }
+
             Self::InvalidProfile { profile, var, .. } => {
                 write!(f, "invalid profile '{profile}' for {var}")
             }
@@ -541,6 +570,9 @@ impl Debug for Error {
             #[cfg(feature = "file")]
             Self::File { source } => write!(f, "{source:?}"),
 
+            #[cfg(feature = "dotenv")]
+            Self::Dotenv { source } => write!(f, "{source:?}"),
+
             Self::InvalidProfile {
                 profile,
                 var,
@@ -635,6 +667,9 @@ impl StdError for Error {
             #[cfg(feature = "file")]
             Self::File { source } => Some(source),
 
+            #[cfg(feature = "dotenv")]
+            Self::Dotenv { source } => Some(source.as_ref()),
+
             _ => None,
         }
     }
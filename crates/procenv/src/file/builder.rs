@@ -1,14 +1,22 @@
 //! Configuration builder for layered loading.
 
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use serde::{Serialize, de::DeserializeOwned};
 use serde_json as SJSON;
 
-use crate::Error;
+use crate::{Error, MaybeRedacted};
 
+use super::env_path::{EnvKeyCase, EnvListStyle, EnvPathStyle, PathSegment};
 use super::error::FileError;
-use super::origin::OriginTracker;
+use super::format::FileFormat;
+use super::merge::{Merge, MergeStrategies};
+use super::origin::{EnvOrigin, OriginTracker};
+use super::parser::{FormatParser, FormatRegistry};
+use super::schema::ValueType;
+use super::strict::KnownKeys;
 use super::utils::FileUtils;
 
 /// Builder for layered configuration loading.
@@ -20,7 +28,8 @@ use super::utils::FileUtils;
 /// # Layering Order
 ///
 /// 1. **Defaults** - Initial values set via [`defaults()`](Self::defaults)
-/// 2. **Config files** - Added via [`file()`](Self::file) or [`file_optional()`](Self::file_optional)
+/// 2. **Config files** - Added via [`file()`](Self::file), [`file_optional()`](Self::file_optional)
+///    or [`dir()`](Self::dir), in the order they were added
 /// 3. **Environment variables** - Filtered by [`env_prefix()`](Self::env_prefix)
 ///
 /// # Example
@@ -65,14 +74,82 @@ use super::utils::FileUtils;
 /// ```
 ///
 /// The result will have `database.host = "localhost"` and `database.port = 5433`.
+/// Arrays are replaced rather than merged; use
+/// [`merge_strategy()`](Self::merge_strategy) to append to them instead.
+///
+/// # Includes
+///
+/// A file can pull in shared files with two reserved top-level keys:
+///
+/// ```toml
+/// _extends = "../common.toml"          # a single base file
+/// _include = ["base.toml", "db/*.toml"] # files or globs, in order
+/// ```
+///
+/// Paths are relative to the including file, and the last component of an
+/// `_include` entry may be a glob. Included files are merged before the file
+/// that names them (`_extends` first), so the including file wins. They may
+/// include further files; a cycle is a [`FileError::IncludeCycle`] error.
+/// Every included file is tracked in the [`OriginTracker`], so errors point
+/// into the file a value came from. The directives themselves are removed
+/// from the merged configuration.
+///
+/// # Environment Provenance
+///
+/// Values set by [`env_prefix()`](Self::env_prefix) variables or
+/// [`env_mapping()`](Self::env_mapping)s are tracked with the variable that
+/// set them, so a type mismatch names the variable and its value instead of
+/// pointing into a file. Values at [`secret()`](Self::secret) paths are
+/// redacted, and [`dotenv_origin()`](Self::dotenv_origin) attributes
+/// variables to the `.env` file that set them.
+///
+/// # Value Types
+///
+/// Environment values are strings, so they are coerced to JSON before being
+/// merged. By default the type is guessed with
+/// [`FileUtils::coerce_value()`], which turns `APP_VERSION=1.10` into the
+/// float `1.1`. Registering the type of a path with
+/// [`value_type()`](Self::value_type) coerces its values to that type
+/// instead; the derive registers the type of every field.
 pub struct ConfigBuilder {
     base: SJSON::Value,
-    files: Vec<(PathBuf, bool)>,
+    files: Vec<FileLayer>,
     env_prefix: Option<String>,
-    env_separator: String,
+    /// How environment variable names map to paths
+    env_style: EnvPathStyle,
     origins: OriginTracker,
     /// Direct field-to-env-var mappings for custom var names (`field_path`, `env_var`)
     env_mappings: Vec<(String, String)>,
+    /// Parsers for config files, by extension
+    formats: FormatRegistry,
+    /// How layers are merged, by path
+    merge_strategies: MergeStrategies,
+    /// Paths whose environment values are redacted in errors
+    secrets: HashSet<String>,
+    /// `.env` files that environment variables may have been loaded from
+    #[cfg(feature = "dotenv")]
+    dotenv_files: Vec<PathBuf>,
+    /// Keys the configuration defines, to detect unknown ones
+    known_keys: KnownKeys,
+    /// Prefixed environment variables read elsewhere, which are not unknown
+    known_env_vars: Vec<String>,
+    /// Whether unknown keys are errors
+    strict: bool,
+    /// Types environment values are coerced to, by path
+    value_types: HashMap<String, ValueType>,
+}
+
+/// A `.env` file and its `(key, value)` entries.
+type DotenvEntries = (PathBuf, Vec<(String, String)>);
+
+/// A file-based configuration layer.
+#[derive(Clone, Debug)]
+enum FileLayer {
+    /// A single file (`true` if required), with an optional format override.
+    File(PathBuf, bool, Option<FileFormat>),
+
+    /// All files in a directory whose names match a glob pattern.
+    Dir(PathBuf, String),
 }
 
 impl Default for ConfigBuilder {
@@ -89,9 +166,18 @@ impl ConfigBuilder {
             base: SJSON::Value::Object(SJSON::Map::new()),
             files: Vec::new(),
             env_prefix: None,
-            env_separator: "_".to_string(),
+            env_style: EnvPathStyle::default(),
             origins: OriginTracker::new(),
             env_mappings: Vec::new(),
+            formats: FormatRegistry::default(),
+            merge_strategies: MergeStrategies::default(),
+            secrets: HashSet::new(),
+            #[cfg(feature = "dotenv")]
+            dotenv_files: Vec::new(),
+            known_keys: KnownKeys::default(),
+            known_env_vars: Vec::new(),
+            strict: false,
+            value_types: HashMap::new(),
         }
     }
 
@@ -200,7 +286,8 @@ impl ConfigBuilder {
     /// ```
     #[must_use]
     pub fn file<P: AsRef<Path>>(mut self, path: P) -> Self {
-        self.files.push((path.as_ref().to_path_buf(), true));
+        self.files
+            .push(FileLayer::File(path.as_ref().to_path_buf(), true, None));
 
         self
     }
@@ -223,7 +310,157 @@ impl ConfigBuilder {
     /// ```
     #[must_use]
     pub fn file_optional<P: AsRef<Path>>(mut self, path: P) -> Self {
-        self.files.push((path.as_ref().to_path_buf(), false));
+        self.files
+            .push(FileLayer::File(path.as_ref().to_path_buf(), false, None));
+
+        self
+    }
+
+    /// Adds a required configuration file parsed as `format`.
+    ///
+    /// Use this for files whose extension does not name their format, such
+    /// as `/etc/myapp/config` or secrets mounted without an extension. The
+    /// format takes precedence over the one chosen from the extension.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let builder = ConfigBuilder::new()
+    ///     .file_with_format("/etc/myapp/config", FileFormat::Toml);
+    /// ```
+    #[must_use]
+    pub fn file_with_format<P: AsRef<Path>>(mut self, path: P, format: FileFormat) -> Self {
+        self.files.push(FileLayer::File(
+            path.as_ref().to_path_buf(),
+            true,
+            Some(format),
+        ));
+
+        self
+    }
+
+    /// Adds an optional configuration file parsed as `format`.
+    ///
+    /// Like [`file_with_format()`](Self::file_with_format), but skipped if
+    /// the file does not exist.
+    #[must_use]
+    pub fn file_optional_with_format<P: AsRef<Path>>(
+        mut self,
+        path: P,
+        format: FileFormat,
+    ) -> Self {
+        self.files.push(FileLayer::File(
+            path.as_ref().to_path_buf(),
+            false,
+            Some(format),
+        ));
+
+        self
+    }
+
+    /// Detects the format of files with an unrecognized extension from
+    /// their content.
+    ///
+    /// Off by default, so a file without a known extension is a
+    /// [`FileError::UnknownFormat`] error. When enabled, JSON, JSON5, TOML
+    /// and YAML are tried in turn (as enabled); see [`FileFormat::sniff`].
+    /// If none of them parse the file, [`build()`](Self::build) returns a
+    /// [`FileError::UndetectedFormat`] listing why each was rejected.
+    ///
+    /// Files with a registered extension, and files added with
+    /// [`file_with_format()`](Self::file_with_format), are never sniffed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let builder = ConfigBuilder::new()
+    ///     .sniff_formats(true)
+    ///     .file("/run/secrets/app");  // JSON, TOML or YAML
+    /// ```
+    #[must_use]
+    pub const fn sniff_formats(mut self, enabled: bool) -> Self {
+        self.formats.set_sniff(enabled);
+
+        self
+    }
+
+    /// Sets how values at `path` are merged across layers.
+    ///
+    /// Layers are deep-merged by default: objects are merged key by key, and
+    /// arrays and scalars from later layers replace earlier ones. The
+    /// strategy applies to the value at the dotted `path` and everything
+    /// below it, unless a deeper path has its own. An empty path sets the
+    /// strategy for the whole configuration, e.g. [`Merge::Patch`] to let
+    /// `null` delete keys.
+    ///
+    /// Strategies apply to config files, directory fragments and
+    /// [`env_prefix()`](Self::env_prefix) variables.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use procenv::file::{ConfigBuilder, Merge};
+    ///
+    /// let builder = ConfigBuilder::new()
+    ///     .merge_strategy("plugins", Merge::Append)
+    ///     .file("config.toml")
+    ///     .file_optional("config.local.toml");  // Adds to `plugins`
+    /// ```
+    #[must_use]
+    pub fn merge_strategy(mut self, path: impl Into<String>, strategy: Merge) -> Self {
+        self.merge_strategies.set(path, strategy);
+
+        self
+    }
+
+    /// Adds a directory of configuration fragments (`conf.d` style).
+    ///
+    /// Every file directly inside `path` whose name matches the glob
+    /// `pattern` is merged as its own layer, in lexical order of file names.
+    /// This lets deployments drop numbered fragments like `10-db.toml` and
+    /// `20-cache.toml` that override each other predictably. Each fragment
+    /// is tracked separately in the [`OriginTracker`].
+    ///
+    /// A missing directory contributes no fragments. The pattern only
+    /// applies to file names; subdirectories are not searched.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Directory containing the fragments
+    /// * `pattern` - Glob pattern for fragment file names (e.g., `"*.toml"`)
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let builder = ConfigBuilder::new()
+    ///     .file("/etc/myapp/config.toml")
+    ///     .dir("/etc/myapp/conf.d", "*.toml");  // Fragments override the base
+    /// ```
+    #[must_use]
+    pub fn dir<P: AsRef<Path>>(mut self, path: P, pattern: impl Into<String>) -> Self {
+        self.files
+            .push(FileLayer::Dir(path.as_ref().to_path_buf(), pattern.into()));
+
+        self
+    }
+
+    /// Registers a parser for additional file extensions.
+    ///
+    /// Files are parsed by the parser registered for their extension. A
+    /// parser registered here takes precedence over the built-in ones and
+    /// earlier registrations for the extensions it claims, so this can also
+    /// replace how a built-in format is parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let builder = ConfigBuilder::new()
+    ///     .format(RonFormat)
+    ///     .file("config.ron");
+    /// ```
+    #[must_use]
+    pub fn format(mut self, parser: impl FormatParser + 'static) -> Self {
+        self.formats.register(Arc::new(parser));
 
         self
     }
@@ -250,15 +487,61 @@ impl ConfigBuilder {
     ///
     /// When reading environment variables for nested configuration, this separator
     /// is used to split the variable name into nested keys. Default is "_".
+    /// A separator written twice is a literal separator within a key.
     ///
     /// # Example
     ///
     /// With separator "_" and prefix "APP_":
     /// - `APP_DATABASE_HOST` becomes `database.host`
+    /// - `APP_MAX__CONNECTIONS` becomes `max_connections`
+    /// - `APP_SERVERS_0_HOST` becomes `servers[0].host`
     #[must_use]
     pub fn env_separator(mut self, separator: impl Into<String>) -> Self {
-        self.env_separator = separator.into();
+        self.env_style.separator = separator.into();
+
+        self
+    }
 
+    /// Set how keys in environment variable names are cased.
+    ///
+    /// Keys are lowercased by default. With [`EnvKeyCase::Preserve`] they
+    /// are used as written, for keys with uppercase letters.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // APP_logLevel sets `logLevel`
+    /// let builder = ConfigBuilder::new()
+    ///     .env_prefix("APP_")
+    ///     .env_key_case(EnvKeyCase::Preserve);
+    /// ```
+    #[must_use]
+    pub const fn env_key_case(mut self, key_case: EnvKeyCase) -> Self {
+        self.env_style.key_case = key_case;
+        self
+    }
+
+    /// Set how lists are set from environment variables.
+    ///
+    /// With [`EnvListStyle::Delimited`], the default with `","`, numeric
+    /// segments are plain keys and a list is set by one variable, split on
+    /// the delimiter at paths with the [`value_type()`](Self::value_type)
+    /// [`ValueType::List`]. With [`EnvListStyle::Indexed`], numeric segments
+    /// address list elements: `APP_SERVERS__1__HOST` sets `servers[1].host`,
+    /// editing the list loaded from files or adding to its end.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // APP_HOSTS=a;b sets `hosts` to ["a", "b"]
+    /// let builder = ConfigBuilder::new()
+    ///     .env_prefix("APP_")
+    ///     .env_list_style(EnvListStyle::Delimited(";"))
+    ///     .value_type("hosts", ValueType::list(ValueType::String));
+    /// ```
+    #[must_use]
+    pub const fn env_list_style(mut self, list_style: EnvListStyle) -> Self {
+        self.env_style.list_style = list_style;
         self
     }
 
@@ -293,6 +576,130 @@ impl ConfigBuilder {
         self
     }
 
+    /// Marks the value at `path`, and everything below it, as secret.
+    ///
+    /// Secret values set by environment variables are redacted from type
+    /// mismatch errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let config: MyConfig = ConfigBuilder::new()
+    ///     .env_prefix("APP_")
+    ///     .secret("database.password")
+    ///     .build()?;
+    /// ```
+    #[must_use]
+    pub fn secret(mut self, path: impl Into<String>) -> Self {
+        self.secrets.insert(path.into());
+        self
+    }
+
+    /// Registers the keys the configuration defines, as dotted paths.
+    ///
+    /// Keys loaded from files, and [`env_prefix()`](Self::env_prefix)
+    /// variables, that are not known, below a known key, or an object
+    /// containing one are then listed by [`OriginTracker::unknown_keys()`].
+    /// Variables named by an [`env_mapping()`](Self::env_mapping) are never
+    /// unknown.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let builder = ConfigBuilder::new()
+    ///     .file("config.toml")
+    ///     .known_keys(["name", "database.host", "database.port"]);
+    /// ```
+    #[must_use]
+    pub fn known_keys<I, K>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        self.known_keys.extend(keys.into_iter().map(Into::into));
+        self
+    }
+
+    /// Registers an [`env_prefix()`](Self::env_prefix) variable that is read
+    /// elsewhere, such as a profile selector, so it is not an unknown key.
+    #[must_use]
+    pub fn known_env_var(mut self, var: impl Into<String>) -> Self {
+        self.known_env_vars.push(var.into());
+        self
+    }
+
+    /// Makes unknown keys an error.
+    ///
+    /// Every key loaded from a file, and every
+    /// [`env_prefix()`](Self::env_prefix) variable, not among the
+    /// [`known_keys()`](Self::known_keys) is reported, with a suggestion when
+    /// it looks like a typo of a known one. Several are reported together as
+    /// [`FileError::UnknownKeys`]. Has no effect without known keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // `databse.port` in config.toml, or `APP_PROT=80`, is now an error
+    /// let config: Config = ConfigBuilder::new()
+    ///     .file("config.toml")
+    ///     .env_prefix("APP_")
+    ///     .known_keys(["database.port", "port"])
+    ///     .strict(true)
+    ///     .build()?;
+    /// ```
+    #[must_use]
+    pub const fn strict(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Sets the type that environment values for `path` are coerced to.
+    ///
+    /// Applies to [`env_prefix()`](Self::env_prefix) variables and
+    /// [`env_mapping()`](Self::env_mapping)s. Values at other paths are still
+    /// guessed with [`FileUtils::coerce_value()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use procenv::file::{ConfigBuilder, ValueType};
+    ///
+    /// // APP_VERSION=1.10 stays "1.10" rather than becoming 1.1
+    /// let config: Config = ConfigBuilder::new()
+    ///     .env_prefix("APP_")
+    ///     .value_type("version", ValueType::String)
+    ///     .build()?;
+    /// ```
+    #[must_use]
+    pub fn value_type(mut self, path: impl Into<String>, value_type: ValueType) -> Self {
+        self.value_types.insert(path.into(), value_type);
+        self
+    }
+
+    /// Attributes environment variables loaded from the `.env` file at
+    /// `path` to that file.
+    ///
+    /// Type mismatch errors for a variable whose value matches the file's
+    /// name the file as well as the variable. Files are checked in the order
+    /// they were added; unreadable files are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// procenv::dotenv::load(".env")?;
+    ///
+    /// let config: MyConfig = ConfigBuilder::new()
+    ///     .env_prefix("APP_")
+    ///     .dotenv_origin(".env")
+    ///     .build()?;
+    /// ```
+    #[cfg(feature = "dotenv")]
+    #[must_use]
+    pub fn dotenv_origin(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dotenv_files.push(path.into());
+        self
+    }
+
     /// Merges all configuration sources and returns the raw JSON value.
     ///
     /// This is a lower-level method that returns the merged JSON value
@@ -309,47 +716,263 @@ impl ConfigBuilder {
     ///
     /// Returns a [`FileError`] if a required file is missing or cannot be parsed.
     pub fn merge(mut self) -> Result<(SJSON::Value, OriginTracker), FileError> {
-        // Layer files
-        for (path, required) in self.files.clone() {
-            if let Some((file_value, content, format)) =
-                FileUtils::parse_file_with_content(&path, required)?
-            {
-                // Track origins before merging
-                self.origins
-                    .add_source(path.display().to_string(), content, format);
-                self.origins.track_value(&file_value, "");
+        // Layer files and directory fragments
+        for layer in std::mem::take(&mut self.files) {
+            match layer {
+                FileLayer::File(path, required, format) => {
+                    self.merge_file(&path, required, format)?;
+                }
 
-                FileUtils::deep_merge(&mut self.base, file_value);
+                FileLayer::Dir(dir, pattern) => {
+                    for path in FileUtils::list_dir_matching(&dir, &pattern)? {
+                        self.merge_file(&path, true, None)?;
+                    }
+                }
             }
         }
 
+        // Variables are attributed to their `.env` file by value
+        let dotenv = self.dotenv_entries();
+
+        // Paths, variables and values of the environment variables applied
+        let mut env_set = Vec::new();
+
         // Layer environment variables using prefix/separator convention
-        if let Some(prefix) = &self.env_prefix {
-            let env_value = FileUtils::env_to_value(prefix, &self.env_separator);
+        if let Some(prefix) = self.env_prefix.clone() {
+            let mut env_map = SJSON::Map::new();
+            let mut indexed = Vec::new();
 
-            if let SJSON::Value::Object(map) = &env_value
-                && !map.is_empty()
-            {
-                FileUtils::deep_merge(&mut self.base, env_value);
+            for (path, var, value) in FileUtils::prefixed_env_vars(&prefix, &self.env_style) {
+                // Mapped variables are applied by their mapping alone
+                if self.env_mappings.iter().any(|(_, mapped)| *mapped == var) {
+                    continue;
+                }
+
+                // Paths into lists are applied to the merged lists below
+                if path
+                    .iter()
+                    .any(|segment| matches!(segment, PathSegment::Index(_)))
+                {
+                    indexed.push((path, var, value));
+                    continue;
+                }
+
+                let dotted = PathSegment::join(&path);
+                FileUtils::insert_nested(&mut env_map, &path, self.coerce_env(&dotted, &value))
+                    .map_err(|conflict| {
+                        FileUtils::env_conflict_error(&path, var.clone(), conflict, &env_set)
+                    })?;
+                env_set.push((dotted, var, value));
             }
+
+            if !env_map.is_empty() {
+                self.merge_strategies
+                    .merge(&mut self.base, SJSON::Value::Object(env_map));
+            }
+
+            for (path, var, value) in indexed {
+                let dotted = PathSegment::join(&path);
+                let typed_value = self.coerce_env(&dotted, &value);
+                self.insert_env(&path, var.clone(), typed_value, &env_set)?;
+                env_set.push((dotted, var, value));
+            }
+
+            for (path, var, value) in &env_set {
+                self.track_env(path, var.clone(), value, &dotenv);
+            }
+        }
+
+        if !self.known_keys.is_empty() {
+            self.check_unknown_keys()?;
         }
 
         // Layer direct env mappings (highest priority for env overrides)
         // These handle custom var names and no_prefix fields
-        for (field_path, env_var) in &self.env_mappings {
-            if let Ok(value) = std::env::var(env_var) {
-                let typed_value = FileUtils::coerce_value(&value);
-                let parts: Vec<&str> = field_path.split('.').collect();
-
-                if let SJSON::Value::Object(ref mut map) = self.base {
-                    FileUtils::insert_nested(map, &parts, typed_value);
-                }
+        for (field_path, env_var) in std::mem::take(&mut self.env_mappings) {
+            if let Ok(value) = crate::env::var(&env_var) {
+                let typed_value = self.coerce_env(&field_path, &value);
+                let path = PathSegment::keys(field_path.split('.'));
+                self.insert_env(&path, env_var.clone(), typed_value, &env_set)?;
+                self.track_env(&field_path, env_var.clone(), &value, &dotenv);
+                env_set.push((field_path, env_var, value));
             }
         }
 
         Ok((self.base, self.origins))
     }
 
+    /// Record keys and variables that are not known, failing in strict mode.
+    fn check_unknown_keys(&mut self) -> Result<(), FileError> {
+        let mut unknown = self.known_keys.unknown_file_keys(&self.origins);
+
+        if let Some(prefix) = &self.env_prefix {
+            let mapped: Vec<&str> = self
+                .env_mappings
+                .iter()
+                .map(|(_, var)| var)
+                .chain(&self.known_env_vars)
+                .map(String::as_str)
+                .collect();
+            unknown.extend(
+                self.known_keys
+                    .unknown_env_vars(prefix, &self.env_style, &mapped),
+            );
+        }
+
+        self.origins.unknown = unknown
+            .iter()
+            .filter_map(|error| match error {
+                FileError::UnknownKey { key, .. } => Some(key.clone()),
+                FileError::UnknownEnvVar { var, .. } => Some(var.clone()),
+                _ => None,
+            })
+            .collect();
+
+        if !self.strict || unknown.is_empty() {
+            return Ok(());
+        }
+
+        Err(if unknown.len() == 1 {
+            unknown.remove(0)
+        } else {
+            FileError::UnknownKeys { errors: unknown }
+        })
+    }
+
+    /// Coerce an environment value for `path` to its registered type, or
+    /// guess one.
+    fn coerce_env(&self, path: &str, value: &str) -> SJSON::Value {
+        match (self.value_types.get(path), self.env_style.list_style) {
+            (Some(ValueType::List(item)), EnvListStyle::Delimited(delimiter)) => {
+                item.coerce_list(value, delimiter)
+            }
+            (Some(value_type), _) => value_type.coerce(value),
+            (None, _) => FileUtils::coerce_value(value),
+        }
+    }
+
+    /// Insert a value set by an environment variable into the merged
+    /// configuration.
+    fn insert_env(
+        &mut self,
+        path: &[PathSegment],
+        var: String,
+        value: SJSON::Value,
+        env_set: &[(String, String, String)],
+    ) -> Result<(), FileError> {
+        if let SJSON::Value::Object(ref mut map) = self.base {
+            FileUtils::insert_nested(map, path, value)
+                .map_err(|conflict| FileUtils::env_conflict_error(path, var, conflict, env_set))?;
+        }
+
+        Ok(())
+    }
+
+    /// Record that an environment variable set the value at `path`.
+    fn track_env(&mut self, path: &str, var: String, value: &str, dotenv: &[DotenvEntries]) {
+        let secret = self.secrets.iter().any(|secret| {
+            path.strip_prefix(secret.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+        });
+
+        let dotenv_file = dotenv
+            .iter()
+            .find(|(_, entries)| entries.iter().any(|(key, v)| *key == var && v == value))
+            .map(|(file, _)| file.clone());
+
+        self.origins.track_env(
+            path,
+            EnvOrigin {
+                var,
+                value: MaybeRedacted::new(value, secret),
+                dotenv_file,
+            },
+        );
+    }
+
+    /// Read the variables of every [`dotenv_origin()`](Self::dotenv_origin) file.
+    #[cfg(feature = "dotenv")]
+    fn dotenv_entries(&self) -> Vec<DotenvEntries> {
+        self.dotenv_files
+            .iter()
+            .filter_map(|file| {
+                let entries = crate::dotenv::read(file).ok()?;
+                let entries = entries.into_iter().map(|e| (e.key, e.value)).collect();
+                Some((file.clone(), entries))
+            })
+            .collect()
+    }
+
+    #[cfg(not(feature = "dotenv"))]
+    #[expect(
+        clippy::unused_self,
+        reason = "only reads files with the dotenv feature"
+    )]
+    const fn dotenv_entries(&self) -> Vec<DotenvEntries> {
+        Vec::new()
+    }
+
+    /// Parse a single file and merge it into the base, tracking its origin.
+    fn merge_file(
+        &mut self,
+        path: &Path,
+        required: bool,
+        format: Option<FileFormat>,
+    ) -> Result<(), FileError> {
+        self.merge_included(path, required, format, &mut Vec::new())
+    }
+
+    /// Merge a file after the files named by its include directives.
+    ///
+    /// `chain` holds the files currently being included, outermost first,
+    /// to detect cycles.
+    fn merge_included(
+        &mut self,
+        path: &Path,
+        required: bool,
+        format: Option<FileFormat>,
+        chain: &mut Vec<PathBuf>,
+    ) -> Result<(), FileError> {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+        if let Some(start) = chain.iter().position(|seen| *seen == canonical) {
+            let cycle: Vec<String> = chain[start..]
+                .iter()
+                .chain([&canonical])
+                .map(|path| path.display().to_string())
+                .collect();
+
+            return Err(FileError::IncludeCycle {
+                chain: cycle.join(" -> "),
+            });
+        }
+
+        let Some((mut file_value, spans, content, format)) =
+            FileUtils::parse_file_with_content(path, required, &self.formats, format)?
+        else {
+            return Ok(());
+        };
+
+        // Included files are layered first, so this file overrides them
+        let includes = FileUtils::take_includes(&mut file_value, path, &content, &*format)?;
+
+        chain.push(canonical);
+        for include in includes {
+            self.merge_included(&include, true, None, chain)?;
+        }
+        chain.pop();
+
+        // Track origins where the merge placed each value
+        self.origins
+            .add_source(path.display().to_string(), content.into(), format, spans);
+        let placements = self
+            .merge_strategies
+            .merge(&mut self.base, file_value.clone());
+        self.origins.track_value(&file_value, "", &placements);
+
+        Ok(())
+    }
+
     /// Merges all configuration sources and returns the raw JSON value.
     ///
     /// Unlike [`build()`](Self::build), this method does NOT deserialize
@@ -440,9 +1063,19 @@ impl ConfigBuilder {
             let path = e.path().to_string();
             let inner_msg = e.inner().to_string();
 
+            // Values from environment variables name the variable
+            if let Some(env) = origins.env_source(&path) {
+                return Error::from(FileUtils::env_type_mismatch_error(&path, &inner_msg, env));
+            }
+
             // Try to find the origin and create a span error
             if let Some(origin) = origins.find_origin(&path)
-                && let Some(file_error) = FileUtils::type_mismatch_error(&path, &inner_msg, origin)
+                && let Some(file_error) = FileUtils::type_mismatch_error(
+                    &path,
+                    origins.path_in_file(&path),
+                    &inner_msg,
+                    origin,
+                )
             {
                 return Error::from(file_error);
             }
@@ -0,0 +1,311 @@
+//! Paths in environment variable names.
+//!
+//! An [`env_prefix`](super::ConfigBuilder::env_prefix) variable's name,
+//! without the prefix, is split on the separator into a key path. With the
+//! separator `__`:
+//!
+//! | Variable | Path |
+//! |----------|------|
+//! | `APP_DATABASE__HOST` | `database.host` |
+//! | `APP_HTTP__2` | `http.2` |
+//! | `APP_MAX_CONNECTIONS` | `max_connections` |
+//!
+//! A separator written twice is a literal separator within a key, so with
+//! the separator `_`, `APP_MAX__CONNECTIONS` is `max_connections`. Numeric
+//! segments are keys unless [`EnvListStyle::Indexed`] is set, which makes
+//! those after the first list indices (`APP_SERVERS__1__HOST` sets
+//! `servers[1].host`), and keys are lowercased unless
+//! [`EnvKeyCase::Preserve`] is set.
+
+use std::fmt;
+
+use serde_json as SJSON;
+
+/// How the keys in environment variable names are cased.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EnvKeyCase {
+    /// Keys are lowercased: `APP_LOG_LEVEL` sets `log.level`.
+    #[default]
+    Lower,
+    /// Keys are used as written: `APP_logLevel` sets `logLevel`.
+    Preserve,
+}
+
+/// How lists are set from environment variables.
+///
+/// The default is `Delimited(",")`, so numeric segments such as the `2` in
+/// `APP_HTTP_2` stay object keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnvListStyle {
+    /// Numeric segments address list elements, so `APP_SERVERS__0__HOST`
+    /// sets `servers[0].host`. Whole lists are given as JSON arrays or
+    /// comma-separated items.
+    Indexed,
+    /// Numeric segments are plain keys, and values for paths typed as
+    /// [`ValueType::List`](super::ValueType::List) are split on the
+    /// delimiter.
+    Delimited(&'static str),
+}
+
+impl Default for EnvListStyle {
+    fn default() -> Self {
+        Self::Delimited(",")
+    }
+}
+
+/// A segment of a key path.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PathSegment {
+    /// A key in an object.
+    Key(String),
+    /// An element of a list.
+    Index(usize),
+}
+
+impl PathSegment {
+    /// A path of object keys.
+    pub fn keys<'a>(keys: impl IntoIterator<Item = &'a str>) -> Vec<Self> {
+        keys.into_iter()
+            .map(|key| Self::Key(key.to_string()))
+            .collect()
+    }
+
+    /// Format a path the way [`OriginTracker`](super::OriginTracker) does,
+    /// such as `servers[1].host`.
+    #[must_use]
+    pub fn join(path: &[Self]) -> String {
+        path.iter()
+            .enumerate()
+            .map(|(position, segment)| match segment {
+                Self::Key(key) if position == 0 => key.clone(),
+                Self::Key(key) => format!(".{key}"),
+                Self::Index(index) => format!("[{index}]"),
+            })
+            .collect()
+    }
+}
+
+/// A value that could not be inserted at a path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathConflict {
+    /// The path runs into a value that is not the object or list it needs.
+    Shape {
+        /// Where the value is.
+        path: String,
+        /// What the value is, such as "a string".
+        found: &'static str,
+        /// What the path needs there, "an object" or "a list".
+        expected: &'static str,
+    },
+
+    /// The path skips past the end of a list.
+    Gap {
+        /// Where the list is.
+        path: String,
+        /// How many items the list has.
+        len: usize,
+        /// The index the path addresses.
+        index: usize,
+    },
+}
+
+impl PathConflict {
+    /// The path runs into `found` where `next` needs a collection.
+    pub(super) fn shape(path: &[PathSegment], found: &SJSON::Value, next: &PathSegment) -> Self {
+        let found = match found {
+            SJSON::Value::Null => "null",
+            SJSON::Value::Bool(_) => "a boolean",
+            SJSON::Value::Number(_) => "a number",
+            SJSON::Value::String(_) => "a string",
+            SJSON::Value::Array(_) => "a list",
+            SJSON::Value::Object(_) => "an object",
+        };
+        let expected = match next {
+            PathSegment::Key(_) => "an object",
+            PathSegment::Index(_) => "a list",
+        };
+
+        Self::Shape {
+            path: PathSegment::join(path),
+            found,
+            expected,
+        }
+    }
+
+    /// Where the conflicting value is.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Shape { path, .. } | Self::Gap { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for PathConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path().is_empty() {
+            f.write_str("the root ")?;
+        } else {
+            write!(f, "`{}` ", self.path())?;
+        }
+
+        match self {
+            Self::Shape {
+                found, expected, ..
+            } => write!(f, "is {found}, not {expected}"),
+            Self::Gap { len: 1, index, .. } => {
+                write!(f, "has 1 item, so index {index} would leave a gap")
+            }
+            Self::Gap { len, index, .. } => {
+                write!(f, "has {len} items, so index {index} would leave a gap")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PathConflict {}
+
+/// How variable names map to key paths.
+#[derive(Clone, Debug)]
+pub struct EnvPathStyle {
+    pub separator: String,
+    pub key_case: EnvKeyCase,
+    pub list_style: EnvListStyle,
+}
+
+impl Default for EnvPathStyle {
+    fn default() -> Self {
+        Self::new("_")
+    }
+}
+
+impl EnvPathStyle {
+    /// Lowercased keys and comma-delimited lists, split on `separator`.
+    pub fn new(separator: impl Into<String>) -> Self {
+        Self {
+            separator: separator.into(),
+            key_case: EnvKeyCase::default(),
+            list_style: EnvListStyle::default(),
+        }
+    }
+
+    /// The key path of a variable name, without its prefix.
+    pub fn parse(&self, name: &str) -> Vec<PathSegment> {
+        let sep = self.separator.as_str();
+        let mut raw = Vec::new();
+        let mut current = String::new();
+        let mut rest = name;
+
+        while !sep.is_empty()
+            && let Some(at) = rest.find(sep)
+        {
+            current.push_str(&rest[..at]);
+            rest = &rest[at + sep.len()..];
+
+            if let Some(after) = rest.strip_prefix(sep) {
+                current.push_str(sep);
+                rest = after;
+            } else {
+                raw.push(std::mem::take(&mut current));
+            }
+        }
+
+        current.push_str(rest);
+        raw.push(current);
+
+        raw.into_iter()
+            .enumerate()
+            .map(|(position, segment)| {
+                let is_index = position > 0
+                    && self.list_style == EnvListStyle::Indexed
+                    && segment.bytes().all(|byte| byte.is_ascii_digit());
+
+                match segment.parse() {
+                    Ok(index) if is_index => PathSegment::Index(index),
+                    _ => PathSegment::Key(match self.key_case {
+                        EnvKeyCase::Lower => segment.to_lowercase(),
+                        EnvKeyCase::Preserve => segment,
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    /// The variable that sets the dotted key path `path`.
+    pub fn var_name(&self, prefix: &str, path: &str) -> String {
+        let doubled = self.separator.repeat(2);
+        let keys: Vec<String> = path
+            .split('.')
+            .map(|key| {
+                let key = if self.separator.is_empty() {
+                    key.to_string()
+                } else {
+                    key.replace(&self.separator, &doubled)
+                };
+
+                match self.key_case {
+                    EnvKeyCase::Lower => key.to_uppercase(),
+                    EnvKeyCase::Preserve => key,
+                }
+            })
+            .collect();
+
+        format!("{prefix}{}", keys.join(&self.separator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(style: &EnvPathStyle, name: &str) -> String {
+        PathSegment::join(&style.parse(name))
+    }
+
+    #[test]
+    fn test_parse_env_paths() {
+        let style = EnvPathStyle {
+            list_style: EnvListStyle::Indexed,
+            ..EnvPathStyle::new("__")
+        };
+
+        assert_eq!(parsed(&style, "DATABASE__HOST"), "database.host");
+        assert_eq!(parsed(&style, "SERVERS__1__HOST"), "servers[1].host");
+        assert_eq!(parsed(&style, "MAX_CONNECTIONS"), "max_connections");
+        assert_eq!(parsed(&style, "A____B"), "a__b");
+        assert_eq!(parsed(&style, "2__0"), "2[0]");
+
+        let style = EnvPathStyle {
+            list_style: EnvListStyle::Indexed,
+            ..EnvPathStyle::new("_")
+        };
+        assert_eq!(parsed(&style, "MAX__CONNECTIONS_1"), "max_connections[1]");
+    }
+
+    #[test]
+    fn test_parse_numeric_segments_as_keys_by_default() {
+        let style = EnvPathStyle::new("_");
+
+        assert_eq!(parsed(&style, "HTTP_2"), "http.2");
+        assert_eq!(parsed(&style, "DB_REPLICA_1"), "db.replica.1");
+    }
+
+    #[test]
+    fn test_parse_with_key_case_and_list_style() {
+        let style = EnvPathStyle {
+            key_case: EnvKeyCase::Preserve,
+            list_style: EnvListStyle::Delimited(","),
+            ..EnvPathStyle::new("__")
+        };
+
+        assert_eq!(parsed(&style, "logLevel__0"), "logLevel.0");
+        assert_eq!(
+            style.var_name("APP_", "logLevel.max__x"),
+            "APP_logLevel__max____x"
+        );
+
+        let style = EnvPathStyle::new("_");
+        assert_eq!(style.var_name("APP_", "db.max_conn"), "APP_DB_MAX__CONN");
+    }
+}
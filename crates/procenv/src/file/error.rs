@@ -2,6 +2,10 @@
 
 use miette::{Diagnostic, NamedSource, SourceSpan};
 
+use crate::MaybeRedacted;
+
+use super::env_path::PathConflict;
+
 /// Error type for file parsing operations with rich diagnostics.
 ///
 /// This enum represents all file-related errors that can occur during
@@ -59,18 +63,87 @@ pub enum FileError {
     #[error("unknown configuration file format: .{extension}")]
     #[diagnostic(
         code(procenv::file::unknown_format),
-        help("supported formats: .json, .toml, .yaml, .yml")
+        help(
+            "enable the feature for this format, register a `FormatParser` for the extension, \
+             or set the format with `file_with_format`"
+        )
     )]
     UnknownFormat {
         /// The file extension that wasn't recognized
         extension: String,
     },
 
+    /// No format could parse a file whose format was sniffed
+    #[error("could not detect the format of {path}")]
+    #[diagnostic(code(procenv::file::undetected_format))]
+    UndetectedFormat {
+        /// Path to the file
+        path: String,
+
+        /// Which formats were tried, and how to set one explicitly
+        #[help]
+        help: String,
+
+        /// Why each format rejected the file
+        #[related]
+        attempts: Vec<Self>,
+    },
+
+    /// An `_include` or `_extends` directive whose value is not a path
+    #[error("invalid `{directive}` in {path}")]
+    #[diagnostic(
+        code(procenv::file::invalid_include),
+        help("`_include` takes a path or glob, or a list of them; `_extends` takes a single path")
+    )]
+    InvalidInclude {
+        /// The directive key
+        directive: &'static str,
+
+        /// Path to the file containing the directive
+        path: String,
+
+        /// The source file content for display
+        #[source_code]
+        src: NamedSource<String>,
+
+        /// The location of the directive's value, if the parser can find it
+        #[label("{message}")]
+        span: Option<SourceSpan>,
+
+        /// Description of what went wrong
+        message: String,
+    },
+
+    /// A file includes itself, directly or through other files
+    #[error("include cycle: {chain}")]
+    #[diagnostic(
+        code(procenv::file::include_cycle),
+        help("remove the `_include` or `_extends` directive that leads back to an earlier file")
+    )]
+    IncludeCycle {
+        /// The files in the cycle, starting and ending with the same file
+        chain: String,
+    },
+
+    /// Invalid glob pattern for a configuration directory
+    #[error("invalid glob pattern `{pattern}`: {message}")]
+    #[diagnostic(
+        code(procenv::file::invalid_glob),
+        help("use `*`, `?` and `[...]` to match fragment file names, e.g. \"*.toml\"")
+    )]
+    InvalidGlob {
+        /// The pattern that failed to compile
+        pattern: String,
+
+        /// Description of what went wrong
+        message: String,
+    },
+
     /// Parse error with source location
     #[error("{format} parse error in {path}")]
     #[diagnostic(code(procenv::file::parse_error))]
     Parse {
-        /// Format name (JSON, TOML, YAML/YML)
+        /// Format name (JSON, TOML, YAML, or the parser's name)
         format: &'static str,
 
         /// Path to the file
@@ -131,4 +204,97 @@ pub enum FileError {
         #[help]
         help: String,
     },
+
+    /// An environment variable whose path runs into another value
+    #[error("{var} cannot set `{path_str}`: {conflict}")]
+    #[diagnostic(code(procenv::file::env_conflict))]
+    EnvConflict {
+        /// The path the variable sets (e.g., `servers[1].host`)
+        path_str: String,
+
+        /// The environment variable
+        var: String,
+
+        /// The value the path runs into
+        conflict: PathConflict,
+
+        /// The variable that set the conflicting value, if known
+        #[help]
+        help: Option<String>,
+    },
+
+    /// A key in a config file that the configuration does not define
+    #[error("unknown key `{key}` in {file_path}")]
+    #[diagnostic(code(procenv::file::unknown_key))]
+    UnknownKey {
+        /// The dotted path of the key
+        key: String,
+
+        /// The path to the file
+        file_path: String,
+
+        /// The source file content for display
+        #[source_code]
+        src: NamedSource<String>,
+
+        /// The location of the key's value, if the parser recorded it
+        #[label("not a known key")]
+        span: Option<SourceSpan>,
+
+        /// The known key it was probably meant to be
+        #[help]
+        help: Option<String>,
+    },
+
+    /// An environment variable with the configured prefix that no key uses
+    #[error("unknown environment variable {var}")]
+    #[diagnostic(code(procenv::file::unknown_env_var))]
+    UnknownEnvVar {
+        /// Name of the variable
+        var: String,
+
+        /// The variable it was probably meant to be
+        #[help]
+        help: Option<String>,
+    },
+
+    /// Several unknown keys or environment variables, in strict mode
+    #[error("{} unknown configuration keys", errors.len())]
+    #[diagnostic(
+        code(procenv::file::unknown_keys),
+        help("fix or remove each key, or turn off strict mode")
+    )]
+    UnknownKeys {
+        /// An [`UnknownKey`](Self::UnknownKey) or
+        /// [`UnknownEnvVar`](Self::UnknownEnvVar) error for each
+        #[related]
+        errors: Vec<Self>,
+    },
+
+    /// Type mismatch in a value set by an environment variable
+    #[error(
+        "type mismatch at `{path_str}` from {var}={value}{}: {message}",
+        dotenv_file.as_ref().map(|file| format!(" (set in {file})")).unwrap_or_default()
+    )]
+    #[diagnostic(code(procenv::file::env_type_mismatch))]
+    EnvTypeMismatch {
+        /// The JSON path where the error occurred (e.g., "database.port")
+        path_str: String,
+
+        /// The environment variable that set the value
+        var: String,
+
+        /// The variable's value, redacted for secrets
+        value: MaybeRedacted,
+
+        /// The `.env` file that set the variable, if known
+        dotenv_file: Option<String>,
+
+        /// Description of what went wrong
+        message: String,
+
+        /// Suggestion for how to fix
+        #[help]
+        help: String,
+    },
 }
@@ -8,9 +8,14 @@
 //! | Format | Feature | Extensions |
 //! |--------|---------|------------|
 //! | JSON | `file` (always) | `.json` |
+//! | JSON5 / JSONC | `json5` | `.json5`, `.jsonc` |
 //! | TOML | `toml` | `.toml` |
 //! | YAML | `yaml` | `.yaml`, `.yml` |
 //!
+//! Each format implements [`FormatParser`], like the INI and `.properties`
+//! parsers and any format registered with
+//! [`ConfigBuilder::format`](super::ConfigBuilder::format).
+//!
 //! # Format Detection
 //!
 //! The format is automatically detected from the file extension when using
@@ -27,9 +32,20 @@
 //! assert_eq!(FileFormat::from_path(Path::new("config.yaml")), Some(FileFormat::Yaml));
 //! assert_eq!(FileFormat::from_path(Path::new("config.txt")), None);
 //! ```
+//!
+//! Files without a recognized extension, such as `/etc/myapp/config`, can be
+//! given a format with [`ConfigBuilder::file_with_format`](super::ConfigBuilder::file_with_format),
+//! or have it detected from their content with
+//! [`ConfigBuilder::sniff_formats`](super::ConfigBuilder::sniff_formats).
 
 use std::path::Path;
 
+use serde_json as SJSON;
+
+use super::parser::{FormatError, FormatParser, ValueSpans};
+use super::spans;
+use super::utils::FileUtils;
+
 /// Supported configuration file formats.
 ///
 /// The format is automatically detected from the file extension when using
@@ -40,6 +56,7 @@ use std::path::Path;
 /// # Feature Flags
 ///
 /// - `file` - Enables JSON support (always included)
+/// - `json5` - Enables JSON5 / JSONC support
 /// - `toml` - Enables TOML support
 /// - `yaml` - Enables YAML support
 ///
@@ -62,6 +79,15 @@ pub enum FileFormat {
     /// Always available with the `file` feature.
     Json,
 
+    /// JSON5 format (`.json5` or `.jsonc` extension).
+    ///
+    /// A superset of JSON with comments, trailing commas, unquoted keys
+    /// and single-quoted strings, which also covers JSONC.
+    ///
+    /// Requires the `json5` feature flag.
+    #[cfg(feature = "json5")]
+    Json5,
+
     /// TOML format (`.toml` extension).
     ///
     /// Requires the `toml` feature flag.
@@ -97,6 +123,9 @@ impl FileFormat {
         match ext.to_lowercase().as_str() {
             "json" => Some(Self::Json),
 
+            #[cfg(feature = "json5")]
+            "json5" | "jsonc" => Some(Self::Json5),
+
             #[cfg(feature = "toml")]
             "toml" => Some(Self::Toml),
 
@@ -107,12 +136,80 @@ impl FileFormat {
         }
     }
 
+    /// Detects the file format from the content.
+    ///
+    /// Tries JSON, JSON5, TOML and YAML in that order (as enabled) and
+    /// returns the first format that parses the content into a table of
+    /// settings. Returns `None` if none of them do.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use procenv::file::FileFormat;
+    ///
+    /// assert_eq!(FileFormat::sniff("{\"port\": 8080}"), Some(FileFormat::Json));
+    /// assert_eq!(FileFormat::sniff("port = 8080"), Some(FileFormat::Toml));
+    /// assert_eq!(FileFormat::sniff("port: 8080"), Some(FileFormat::Yaml));
+    /// ```
+    #[must_use]
+    pub fn sniff(content: &str) -> Option<Self> {
+        Self::detect(content).ok().map(|(format, ..)| format)
+    }
+
+    /// Parses `content` with the first format that accepts it.
+    ///
+    /// On failure, returns the error from every format that was tried.
+    pub(crate) fn detect(
+        content: &str,
+    ) -> Result<(Self, SJSON::Value, ValueSpans), Vec<(Self, FormatError)>> {
+        let mut attempts = Vec::new();
+
+        for format in Self::sniff_order() {
+            match format.parse_spanned(content) {
+                Ok((value @ SJSON::Value::Object(_), spans)) => return Ok((format, value, spans)),
+
+                Ok((other, _)) => attempts.push((
+                    format,
+                    FormatError::new(format!(
+                        "parsed as {}, not a table of settings",
+                        value_kind(&other)
+                    )),
+                )),
+
+                Err(e) => attempts.push((format, e)),
+            }
+        }
+
+        Err(attempts)
+    }
+
+    /// Formats tried by [`sniff`](Self::sniff), strictest first.
+    ///
+    /// YAML comes last because almost any text is a valid YAML scalar.
+    fn sniff_order() -> Vec<Self> {
+        let mut order = vec![Self::Json];
+
+        #[cfg(feature = "json5")]
+        order.push(Self::Json5);
+
+        #[cfg(feature = "toml")]
+        order.push(Self::Toml);
+
+        #[cfg(feature = "yaml")]
+        order.push(Self::Yaml);
+
+        order
+    }
+
     /// Get the format name for error messages.
     #[must_use]
     pub const fn name(&self) -> &'static str {
         match self {
             Self::Json => "JSON",
 
+            #[cfg(feature = "json5")]
+            Self::Json5 => "JSON5",
+
             #[cfg(feature = "toml")]
             Self::Toml => "TOML",
 
@@ -121,3 +218,83 @@ impl FileFormat {
         }
     }
 }
+
+impl FormatParser for FileFormat {
+    fn name(&self) -> &'static str {
+        Self::name(self)
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            Self::Json => &["json"],
+
+            #[cfg(feature = "json5")]
+            Self::Json5 => &["json5", "jsonc"],
+
+            #[cfg(feature = "toml")]
+            Self::Toml => &["toml"],
+
+            #[cfg(feature = "yaml")]
+            Self::Yaml => &["yaml", "yml"],
+        }
+    }
+
+    fn parse(&self, content: &str) -> Result<SJSON::Value, FormatError> {
+        match self {
+            Self::Json => {
+                serde_json::from_str(content).map_err(|e| FileUtils::json_parse_error(&e, content))
+            }
+
+            #[cfg(feature = "json5")]
+            Self::Json5 => {
+                json5::from_str(content).map_err(|e| FileUtils::json5_parse_error(&e, content))
+            }
+
+            #[cfg(feature = "toml")]
+            Self::Toml => {
+                let toml_value: toml::Value =
+                    toml::from_str(content).map_err(|e| FileUtils::toml_parse_error(&e))?;
+                Ok(FileUtils::toml_to_json(toml_value))
+            }
+
+            #[cfg(feature = "yaml")]
+            Self::Yaml => serde_saphyr::from_str(content)
+                .map_err(|e| FileUtils::yaml_parse_error(&e, content)),
+        }
+    }
+
+    fn parse_spanned(&self, content: &str) -> Result<(SJSON::Value, ValueSpans), FormatError> {
+        let value = self.parse(content)?;
+
+        let spans = match self {
+            Self::Json => spans::json(content),
+
+            #[cfg(feature = "json5")]
+            Self::Json5 => spans::json(content),
+
+            #[cfg(feature = "toml")]
+            Self::Toml => spans::toml(content),
+
+            #[cfg(feature = "yaml")]
+            Self::Yaml => spans::yaml(content),
+        };
+
+        Ok((value, spans))
+    }
+
+    fn find_field_offset(&self, content: &str, path: &str) -> Option<usize> {
+        FileUtils::find_field_offset(content, path, *self)
+    }
+}
+
+/// Describes a non-object value for sniffing errors.
+const fn value_kind(value: &SJSON::Value) -> &'static str {
+    match value {
+        SJSON::Value::Null => "an empty document",
+        SJSON::Value::Bool(_) => "a boolean",
+        SJSON::Value::Number(_) => "a number",
+        SJSON::Value::String(_) => "a string",
+        SJSON::Value::Array(_) => "an array",
+        SJSON::Value::Object(_) => "a table",
+    }
+}
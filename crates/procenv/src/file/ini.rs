@@ -0,0 +1,149 @@
+//! INI file support.
+//!
+//! Sections become nested objects and dotted section names nest further,
+//! so `[database.pool]` maps to `database.pool`. Keys before the first
+//! section are top-level. INI has no value types, so values are coerced
+//! like environment variables: `8080` is a number and `true` a boolean.
+//!
+//! ```ini
+//! name = api
+//!
+//! [database]
+//! host = localhost
+//! port = 5432
+//!
+//! [database.pool]
+//! size = 10
+//! ```
+
+use ::ini::Ini;
+use serde_json as SJSON;
+
+use super::env_path::PathSegment;
+use super::parser::{FormatError, FormatParser};
+use super::utils::FileUtils;
+
+/// Parser for `.ini` files.
+///
+/// Registered by default when the `ini` feature is enabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IniFormat;
+
+impl FormatParser for IniFormat {
+    fn name(&self) -> &'static str {
+        "INI"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["ini"]
+    }
+
+    fn parse(&self, content: &str) -> Result<SJSON::Value, FormatError> {
+        let ini = Ini::load_from_str(content).map_err(|e| {
+            FormatError::new(e.msg.to_string())
+                .with_line_column(content, e.line, e.col)
+                .with_help("check for unclosed `[section]` headers or quotes")
+        })?;
+
+        let mut root = SJSON::Map::new();
+
+        for (section, properties) in &ini {
+            let section: Vec<&str> =
+                section.map_or_else(Vec::new, |name| name.split('.').collect());
+
+            for (key, value) in properties {
+                let mut parts = section.clone();
+                parts.push(key);
+                FileUtils::insert_nested(
+                    &mut root,
+                    &PathSegment::keys(parts),
+                    FileUtils::coerce_value(value),
+                )
+                .map_err(|conflict| {
+                    FormatError::new(format!("cannot set `{key}`: {conflict}"))
+                        .with_help("a key cannot be both a value and a section")
+                })?;
+            }
+        }
+
+        Ok(SJSON::Value::Object(root))
+    }
+
+    fn find_field_offset(&self, content: &str, path: &str) -> Option<usize> {
+        let (section, key) = match path.rsplit_once('.') {
+            Some((section, key)) => (Some(section), key),
+            None => (None, path),
+        };
+
+        let mut offset = 0;
+        let mut in_section = section.is_none();
+
+        for line in content.split_inclusive('\n') {
+            let trimmed = line.trim();
+
+            if let Some(header) = trimmed.strip_prefix('[') {
+                in_section = header
+                    .strip_suffix(']')
+                    .is_some_and(|name| Some(name.trim()) == section);
+            } else if in_section && let Some(value) = value_offset(line, key, &['=', ':']) {
+                return Some(offset + value);
+            }
+
+            offset += line.len();
+        }
+
+        None
+    }
+}
+
+/// Offset of the value in `line` if it assigns `key` with one of `delimiters`.
+fn value_offset(line: &str, key: &str, delimiters: &[char]) -> Option<usize> {
+    let indent = line.len() - line.trim_start().len();
+    let rest = line[indent..].strip_prefix(key)?;
+    let after_key = rest.trim_start();
+    let after_delimiter = after_key.strip_prefix(delimiters)?.trim_start();
+
+    Some(line.len() - after_delimiter.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str =
+        "name = api\n\n[database]\nhost = localhost\nport = 5432\n\n[database.pool]\nsize = 10\n";
+
+    #[test]
+    fn test_sections_nest() {
+        let value = IniFormat.parse(CONTENT).unwrap();
+
+        assert_eq!(
+            value,
+            SJSON::json!({
+                "name": "api",
+                "database": {"host": "localhost", "port": 5432, "pool": {"size": 10}}
+            })
+        );
+    }
+
+    #[test]
+    fn test_find_field_offset() {
+        let offset = IniFormat
+            .find_field_offset(CONTENT, "database.port")
+            .unwrap();
+        assert_eq!(&CONTENT[offset..offset + 4], "5432");
+
+        let offset = IniFormat
+            .find_field_offset(CONTENT, "database.pool.size")
+            .unwrap();
+        assert_eq!(&CONTENT[offset..offset + 2], "10");
+
+        assert!(IniFormat.find_field_offset(CONTENT, "size").is_none());
+    }
+
+    #[test]
+    fn test_parse_error_has_location() {
+        let error = IniFormat.parse("a = 1\n[broken\n").unwrap_err();
+        assert!(error.span().is_some());
+    }
+}
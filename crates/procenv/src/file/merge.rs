@@ -0,0 +1,298 @@
+//! Per-path merge strategies.
+//!
+//! By default, layers are deep-merged: objects are merged key by key, while
+//! arrays and scalars from a later layer replace earlier ones. A [`Merge`]
+//! strategy registered for a path with
+//! [`ConfigBuilder::merge_strategy`](super::ConfigBuilder::merge_strategy)
+//! changes how the value at that path, and everything below it, is merged:
+//!
+//! ```rust,ignore
+//! let builder = ConfigBuilder::new()
+//!     .merge_strategy("plugins", Merge::Append)     // local files add plugins
+//!     .merge_strategy("cors.origins", Merge::Union) // without duplicates
+//!     .merge_strategy("", Merge::Patch)             // `null` deletes keys everywhere
+//!     .file("config.toml")
+//!     .file_optional("config.local.toml");
+//! ```
+//!
+//! A strategy set on a deeper path takes precedence over one inherited from
+//! an ancestor, and the empty path sets the strategy for the whole
+//! configuration.
+
+use std::collections::HashMap;
+
+use serde_json as SJSON;
+
+/// How a later layer's value is combined with an earlier one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Merge {
+    /// Merge objects key by key; replace arrays and scalars.
+    #[default]
+    Deep,
+
+    /// Replace the value wholesale, even an object.
+    Replace,
+
+    /// Concatenate arrays, earlier layers first.
+    ///
+    /// Other values are merged as with [`Deep`](Self::Deep).
+    Append,
+
+    /// Concatenate arrays, skipping elements that are already present.
+    ///
+    /// Other values are merged as with [`Deep`](Self::Deep).
+    Union,
+
+    /// JSON Merge Patch ([RFC 7396](https://www.rfc-editor.org/rfc/rfc7396)).
+    ///
+    /// Like [`Deep`](Self::Deep), except that a `null` in a later layer
+    /// deletes the key instead of setting it to `null`.
+    Patch,
+}
+
+/// Where [`Append`](Merge::Append) and [`Union`](Merge::Union) placed a
+/// layer's array elements.
+///
+/// Maps an element's path in the layer, e.g. `"plugins[0]"`, to its path in
+/// the merged value, or to `None` when a union skipped it as a duplicate.
+pub type Placements = HashMap<String, Option<String>>;
+
+/// Merge strategies registered by path.
+#[derive(Clone, Debug, Default)]
+pub struct MergeStrategies {
+    /// Strategies by dotted path; `""` is the whole configuration.
+    strategies: HashMap<String, Merge>,
+}
+
+impl MergeStrategies {
+    /// Use `strategy` for the value at `path` and below.
+    pub fn set(&mut self, path: impl Into<String>, strategy: Merge) {
+        self.strategies.insert(path.into(), strategy);
+    }
+
+    /// Merge `overlay` into `base` according to the registered strategies.
+    ///
+    /// Returns where concatenated arrays placed the overlay's elements.
+    pub fn merge(&self, base: &mut SJSON::Value, overlay: SJSON::Value) -> Placements {
+        let mut placements = Placements::new();
+        self.merge_at(base, overlay, "", Merge::Deep, &mut placements);
+        placements
+    }
+
+    /// The strategy for `path`, falling back to the one `inherited` from its parent.
+    fn strategy(&self, path: &str, inherited: Merge) -> Merge {
+        self.strategies.get(path).copied().unwrap_or(inherited)
+    }
+
+    fn merge_at(
+        &self,
+        base: &mut SJSON::Value,
+        overlay: SJSON::Value,
+        path: &str,
+        inherited: Merge,
+        placements: &mut Placements,
+    ) {
+        let strategy = self.strategy(path, inherited);
+
+        if strategy == Merge::Replace {
+            *base = overlay;
+            return;
+        }
+
+        // An object replacing a non-object is merged into an empty one, so
+        // nested strategies (and patch deletions) still apply
+        if overlay.is_object() && !base.is_object() {
+            *base = SJSON::Value::Object(SJSON::Map::new());
+        }
+
+        match (base, overlay) {
+            (SJSON::Value::Array(base_items), SJSON::Value::Array(items))
+                if matches!(strategy, Merge::Append | Merge::Union) =>
+            {
+                for (i, item) in items.into_iter().enumerate() {
+                    let placed =
+                        (strategy == Merge::Append || !base_items.contains(&item)).then(|| {
+                            base_items.push(item);
+                            format!("{path}[{}]", base_items.len() - 1)
+                        });
+
+                    placements.insert(format!("{path}[{i}]"), placed);
+                }
+            }
+
+            (SJSON::Value::Object(base_map), SJSON::Value::Object(overlay_map)) => {
+                for (key, value) in overlay_map {
+                    let child = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+
+                    if value.is_null() && self.strategy(&child, strategy) == Merge::Patch {
+                        base_map.remove(&key);
+                        continue;
+                    }
+
+                    let base_value = base_map.entry(key).or_insert(SJSON::Value::Null);
+                    self.merge_at(base_value, value, &child, strategy, placements);
+                }
+            }
+
+            (base, overlay) => *base = overlay,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn merged(
+        strategies: &[(&str, Merge)],
+        base: SJSON::Value,
+        overlay: SJSON::Value,
+    ) -> SJSON::Value {
+        let mut registry = MergeStrategies::default();
+        for (path, strategy) in strategies {
+            registry.set(*path, *strategy);
+        }
+
+        let mut base = base;
+        registry.merge(&mut base, overlay);
+        base
+    }
+
+    #[test]
+    fn test_default_is_deep_merge() {
+        let result = merged(
+            &[],
+            json!({"a": {"x": 1, "y": 2}, "list": [1, 2], "gone": 1}),
+            json!({"a": {"y": 3}, "list": [3], "gone": null}),
+        );
+
+        assert_eq!(
+            result,
+            json!({"a": {"x": 1, "y": 3}, "list": [3], "gone": null})
+        );
+    }
+
+    #[test]
+    fn test_array_strategies() {
+        let base = json!({"plugins": ["a", "b"], "origins": ["x", "y"], "other": [1]});
+        let overlay = json!({"plugins": ["b", "c"], "origins": ["y", "z", "z"], "other": [2]});
+
+        let result = merged(
+            &[("plugins", Merge::Append), ("origins", Merge::Union)],
+            base,
+            overlay,
+        );
+
+        assert_eq!(
+            result,
+            json!({"plugins": ["a", "b", "b", "c"], "origins": ["x", "y", "z"], "other": [2]})
+        );
+    }
+
+    #[test]
+    fn test_placements_of_concatenated_elements() {
+        let mut registry = MergeStrategies::default();
+        registry.set("plugins", Merge::Append);
+        registry.set("origins", Merge::Union);
+
+        let mut base = json!({"plugins": ["a"], "origins": ["x"], "other": [1]});
+        let placements = registry.merge(
+            &mut base,
+            json!({"plugins": ["b"], "origins": ["x", "y"], "other": [2]}),
+        );
+
+        let expected: Placements = [
+            ("plugins[0]", Some("plugins[1]")),
+            ("origins[0]", None),
+            ("origins[1]", Some("origins[1]")),
+        ]
+        .into_iter()
+        .map(|(from, to)| (from.to_string(), to.map(str::to_string)))
+        .collect();
+        assert_eq!(placements, expected);
+    }
+
+    #[test]
+    fn test_replace_and_inheritance() {
+        let base = json!({"db": {"host": "a", "port": 1, "pool": {"size": 1, "idle": 2}}});
+        let overlay = json!({"db": {"host": "b", "pool": {"size": 5}}});
+
+        let result = merged(
+            &[("db", Merge::Replace), ("db.pool", Merge::Deep)],
+            base.clone(),
+            overlay.clone(),
+        );
+        assert_eq!(result, json!({"db": {"host": "b", "pool": {"size": 5}}}));
+
+        let result = merged(&[("db.pool", Merge::Replace)], base, overlay);
+        assert_eq!(
+            result,
+            json!({"db": {"host": "b", "port": 1, "pool": {"size": 5}}})
+        );
+    }
+
+    #[test]
+    fn test_patch_follows_rfc_7396() {
+        // Examples from RFC 7396, Appendix A
+        let cases = [
+            (json!({"a": "b"}), json!({"a": "c"}), json!({"a": "c"})),
+            (json!({"a": "b"}), json!({"a": null}), json!({})),
+            (
+                json!({"a": "b", "b": "c"}),
+                json!({"a": null}),
+                json!({"b": "c"}),
+            ),
+            (json!({"a": ["b"]}), json!({"a": "c"}), json!({"a": "c"})),
+            (
+                json!({"a": {"b": "c"}}),
+                json!({"a": {"b": "d", "c": null}}),
+                json!({"a": {"b": "d"}}),
+            ),
+            (
+                json!({"a": [{"b": "c"}]}),
+                json!({"a": [1]}),
+                json!({"a": [1]}),
+            ),
+            (
+                json!({"e": null}),
+                json!({"a": 1}),
+                json!({"e": null, "a": 1}),
+            ),
+            (
+                json!([1, 2]),
+                json!({"a": "b", "c": null}),
+                json!({"a": "b"}),
+            ),
+            (
+                json!({}),
+                json!({"a": {"bb": {"ccc": null}}}),
+                json!({"a": {"bb": {}}}),
+            ),
+        ];
+
+        for (base, patch, expected) in cases {
+            assert_eq!(
+                merged(&[("", Merge::Patch)], base, patch.clone()),
+                expected,
+                "{patch}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_patch_on_a_single_path() {
+        let result = merged(
+            &[("features", Merge::Patch)],
+            json!({"features": {"a": true, "b": true}, "name": "x"}),
+            json!({"features": {"a": null}, "name": null}),
+        );
+
+        assert_eq!(result, json!({"features": {"b": true}, "name": null}));
+    }
+}
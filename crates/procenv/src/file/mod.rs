@@ -9,8 +9,33 @@
 //! | Format | Feature Flag | Extensions |
 //! |--------|--------------|------------|
 //! | JSON | `file` (always) | `.json` |
+//! | JSON5 / JSONC | `json5` | `.json5`, `.jsonc` |
 //! | TOML | `toml` | `.toml` |
 //! | YAML | `yaml` | `.yaml`, `.yml` |
+//! | INI | `ini` | `.ini` |
+//! | Java properties | `properties` | `.properties` |
+//!
+//! Other formats can be added by implementing [`FormatParser`] and
+//! registering it with [`ConfigBuilder::format`].
+//!
+//! Files without a recognized extension can be given a format with
+//! [`ConfigBuilder::file_with_format`], or have JSON, TOML or YAML detected
+//! from their content with [`ConfigBuilder::sniff_formats`].
+//!
+//! Files can include shared base files with the `_include` and `_extends`
+//! keys; see [`ConfigBuilder`](ConfigBuilder#includes).
+//!
+//! Arrays from later files replace earlier ones unless a [`Merge`] strategy
+//! such as [`Merge::Append`] is registered for their path with
+//! [`ConfigBuilder::merge_strategy`].
+//!
+//! Misspelled keys are ignored unless [`ConfigBuilder::strict`] (or
+//! `#[env_config(strict)]`) is set, which reports them with a suggestion.
+//!
+//! Prefixed environment variables can reach into keys containing the
+//! separator, and into lists with [`EnvListStyle::Indexed`]: with the
+//! separator `__`, `APP_SERVERS__1__HOST` then sets `servers[1].host`. See
+//! [`ConfigBuilder::env_list_style`] and [`ConfigBuilder::env_key_case`].
 //!
 //! # Layering Priority
 //!
@@ -85,16 +110,34 @@
 #![allow(clippy::result_large_err)]
 
 mod builder;
+mod env_path;
 mod error;
 mod format;
+#[cfg(feature = "ini")]
+mod ini;
+mod merge;
 mod origin;
+mod parser;
+#[cfg(feature = "properties")]
+mod properties;
+mod schema;
+mod spans;
+mod strict;
 mod utils;
 
+#[cfg(feature = "ini")]
+pub use self::ini::IniFormat;
 pub use builder::ConfigBuilder;
 pub use builder::DefaultsSerializationError;
+pub use env_path::{EnvKeyCase, EnvListStyle, PathConflict, PathSegment};
 pub use error::FileError;
 pub use format::FileFormat;
-pub use origin::OriginTracker;
+pub use merge::Merge;
+pub use origin::{EnvOrigin, OriginTracker};
+pub use parser::{FormatError, FormatParser, ValueSpans};
+#[cfg(feature = "properties")]
+pub use properties::PropertiesFormat;
+pub use schema::ValueType;
 pub use utils::FileUtils;
 
 // Re-export serde_json types for macro-generated code
@@ -192,9 +235,10 @@ mod tests {
         let mut map = SJSON::Map::new();
         FileUtils::insert_nested(
             &mut map,
-            &["database", "host"],
+            &PathSegment::keys(["database", "host"]),
             SJSON::Value::String("localhost".into()),
-        );
+        )
+        .unwrap();
 
         assert_eq!(
             map.get("database")
@@ -229,6 +273,14 @@ mod tests {
             );
         }
 
+        #[cfg(feature = "json5")]
+        for path in ["config.json5", "config.jsonc"] {
+            assert_eq!(
+                FileFormat::from_path(Path::new(path)),
+                Some(FileFormat::Json5)
+            );
+        }
+
         assert_eq!(FileFormat::from_path(Path::new("config.txt")), None);
     }
 
@@ -250,4 +302,50 @@ mod tests {
         let off = offset.unwrap();
         assert_eq!(&content[off..off + 5], "\"bad\"");
     }
+
+    #[cfg(feature = "json5")]
+    #[test]
+    fn test_find_field_offset_json5() {
+        let content =
+            "{\n  // comment\n  database: {\n    'host': 'db',\n    port: 'bad',\n  },\n}";
+        let offset = FileUtils::find_field_offset(content, "database.port", FileFormat::Json5);
+        assert!(offset.is_some());
+        let off = offset.unwrap();
+        assert_eq!(&content[off..off + 5], "'bad'");
+    }
+
+    #[cfg(feature = "json5")]
+    #[test]
+    fn test_json5_parse_error_has_span() {
+        let content = "{\n  name: 'app',\n  port: ,\n}";
+        let error = FileUtils::parse_str(content, FileFormat::Json5).unwrap_err();
+
+        let FileError::Parse {
+            format,
+            span,
+            message,
+            ..
+        } = error
+        else {
+            panic!("expected a parse error with a span, got {error:?}");
+        };
+        assert_eq!(format, "JSON5");
+        assert_eq!(span.offset(), content.find(" ,").unwrap() + 1);
+        assert!(!message.contains('\n'), "{message}");
+    }
+
+    #[cfg(all(feature = "toml", feature = "yaml"))]
+    #[test]
+    fn test_sniff_format() {
+        assert_eq!(FileFormat::sniff("{\"a\": 1}"), Some(FileFormat::Json));
+        assert_eq!(
+            FileFormat::sniff("a = 1\n[b]\nc = 2\n"),
+            Some(FileFormat::Toml)
+        );
+        assert_eq!(FileFormat::sniff("a:\n  b: 1\n"), Some(FileFormat::Yaml));
+
+        // Valid YAML, but a scalar rather than a table of settings
+        assert_eq!(FileFormat::sniff("just some text"), None);
+        assert_eq!(FileFormat::sniff("[1, 2]"), None);
+    }
 }
@@ -8,7 +8,9 @@
 //! # How It Works
 //!
 //! 1. As configuration files are loaded, [`OriginTracker`] records which file
-//!    each configuration path (e.g., `"database.port"`) came from
+//!    each configuration path (e.g., `"database.port"`) came from, and where
+//!    in that file its value was defined. Values overridden by environment
+//!    variables are recorded with the variable instead
 //! 2. When deserialization fails, the tracker provides source location info
 //! 3. This enables rich error messages with <file:line> highlighting via miette
 //!
@@ -32,22 +34,44 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::string::String;
+use std::sync::Arc;
 
 use serde_json as SJSON;
 
-use super::format::FileFormat;
+use super::merge::Placements;
+use super::parser::{FormatParser, ValueSpans};
+use crate::{MaybeRedacted, ValueSpan};
 
 /// Tracks the origin of a value for error reporting.
+///
+/// Cloning is cheap: the content and spans are shared by every value from
+/// the same file.
 #[derive(Clone, Debug)]
 pub struct ValueOrigin {
     /// Path to the source file
     pub file_path: String,
 
     /// Original file content
-    pub content: String,
+    pub content: Arc<str>,
+
+    /// Parser the file was read with
+    pub format: Arc<dyn FormatParser>,
+
+    /// Byte ranges of the file's values, recorded by the parser
+    pub spans: Arc<ValueSpans>,
+}
+
+/// The environment variable a value was read from.
+#[derive(Clone, Debug)]
+pub struct EnvOrigin {
+    /// Name of the variable
+    pub var: String,
 
-    /// File format
-    pub format: FileFormat,
+    /// The variable's value, redacted for secrets
+    pub value: MaybeRedacted,
+
+    /// The `.env` file that set the variable, if known
+    pub dotenv_file: Option<PathBuf>,
 }
 
 /// Tracks the origin of configuration values for precise error reporting.
@@ -71,14 +95,29 @@ pub struct ValueOrigin {
 /// if let Some(path) = origins.get_file_source("database.port") {
 ///     println!("database.port defined in: {}", path.display());
 /// }
+///
+/// // And where in the file
+/// if let Some(span) = origins.span_of("database.port") {
+///     println!("at line {}, column {}", span.line, span.column);
+/// }
 /// ```
 #[derive(Clone, Debug, Default)]
 pub struct OriginTracker {
     /// Maps JSON paths (e.g., `"database.port"`) to their source file.
     pub(crate) origins: HashMap<String, ValueOrigin>,
 
+    /// Maps paths of array elements moved by a merge to their path in their
+    /// source file (e.g., `"plugins[2]"` to `"plugins[0]"`).
+    pub(crate) relocated: HashMap<String, String>,
+
     /// List of all source files in priority order (last = highest priority).
     pub(crate) sources: Vec<ValueOrigin>,
+
+    /// Maps paths overridden by environment variables to their variable.
+    pub(crate) env: HashMap<String, EnvOrigin>,
+
+    /// Keys and environment variables the configuration does not define.
+    pub(crate) unknown: Vec<String>,
 }
 
 impl OriginTracker {
@@ -87,45 +126,74 @@ impl OriginTracker {
     }
 
     /// Record a source file.
-    pub(crate) fn add_source(&mut self, file_path: String, content: String, format: FileFormat) {
+    pub(crate) fn add_source(
+        &mut self,
+        file_path: String,
+        content: Arc<str>,
+        format: Arc<dyn FormatParser>,
+        spans: ValueSpans,
+    ) {
         self.sources.push(ValueOrigin {
             file_path,
             content,
             format,
+            spans: Arc::new(spans),
         });
     }
 
     /// Record origins for all paths in a value, attributing to the most recent source.
-    pub(crate) fn track_value(&mut self, value: &SJSON::Value, prefix: &str) {
+    ///
+    /// Array elements concatenated by a merge are recorded where the merge
+    /// `placements` put them, so earlier elements keep their own source.
+    pub(crate) fn track_value(
+        &mut self,
+        value: &SJSON::Value,
+        prefix: &str,
+        placements: &Placements,
+    ) {
         // Get the most recent source, or return early if none
         let Some(source) = self.sources.last().cloned() else {
             return;
         };
 
-        self.track_value_recursive(value, prefix, &source);
+        self.track_value_recursive(value, prefix, prefix, &source, placements);
     }
 
-    fn track_value_recursive(&mut self, value: &SJSON::Value, prefix: &str, source: &ValueOrigin) {
+    fn track_value_recursive(
+        &mut self,
+        value: &SJSON::Value,
+        prefix: &str,
+        file_prefix: &str,
+        source: &ValueOrigin,
+        placements: &Placements,
+    ) {
         match value {
             SJSON::Value::Object(map) => {
                 for (key, val) in map {
-                    let path = if prefix.is_empty() {
-                        key.clone()
+                    let (path, file_path) = if prefix.is_empty() {
+                        (key.clone(), key.clone())
                     } else {
-                        format!("{prefix}.{key}")
+                        (format!("{prefix}.{key}"), format!("{file_prefix}.{key}"))
                     };
 
-                    self.origins.insert(path.clone(), source.clone());
-                    self.track_value_recursive(val, &path, source);
+                    self.record(&path, &file_path, source);
+                    self.track_value_recursive(val, &path, &file_path, source, placements);
                 }
             }
 
             SJSON::Value::Array(arr) => {
                 for (i, val) in arr.iter().enumerate() {
-                    let path = format!("{prefix}[{i}]");
+                    let file_path = format!("{file_prefix}[{i}]");
+
+                    // Elements a union skipped keep the earlier source
+                    let path = match placements.get(&file_path) {
+                        Some(Some(placed)) => placed.clone(),
+                        Some(None) => continue,
+                        None => format!("{prefix}[{i}]"),
+                    };
 
-                    self.origins.insert(path.clone(), source.clone());
-                    self.track_value_recursive(val, &path, source);
+                    self.record(&path, &file_path, source);
+                    self.track_value_recursive(val, &path, &file_path, source, placements);
                 }
             }
 
@@ -135,8 +203,61 @@ impl OriginTracker {
         }
     }
 
+    /// Attribute `path` to `source`, where it is `file_path` in the file.
+    fn record(&mut self, path: &str, file_path: &str, source: &ValueOrigin) {
+        if path == file_path {
+            self.relocated.remove(path);
+        } else {
+            self.relocated
+                .insert(path.to_string(), file_path.to_string());
+        }
+
+        self.origins.insert(path.to_string(), source.clone());
+    }
+
+    /// The path of the value at `path` within the file it came from.
+    ///
+    /// Differs from `path` only for array elements moved by a merge.
+    pub(crate) fn path_in_file<'a>(&'a self, path: &'a str) -> &'a str {
+        self.relocated.get(path).map_or(path, String::as_str)
+    }
+
+    /// Record that the value at `path` was set by an environment variable.
+    ///
+    /// The variable replaces whatever file the path, and anything below it,
+    /// was attributed to.
+    pub(crate) fn track_env(&mut self, path: &str, origin: EnvOrigin) {
+        let nested = |tracked: &str| {
+            tracked
+                .strip_prefix(path)
+                .is_some_and(|rest| rest.starts_with(['.', '[']))
+        };
+
+        self.origins
+            .retain(|tracked, _| tracked != path && !nested(tracked));
+        self.relocated
+            .retain(|tracked, _| tracked != path && !nested(tracked));
+        self.env.retain(|tracked, _| !nested(tracked));
+        self.env.insert(path.to_string(), origin);
+    }
+
+    /// Find the environment variable that set the value at `path`.
+    ///
+    /// Returns `None` if the value did not come from an environment
+    /// variable tracked by [`ConfigBuilder`](super::ConfigBuilder).
+    #[must_use]
+    pub fn env_source(&self, path: &str) -> Option<&EnvOrigin> {
+        self.env.get(path)
+    }
+
     /// Find the origin of a value at the given path.
+    ///
+    /// Returns `None` for values set by environment variables.
     pub(crate) fn find_origin(&self, path: &str) -> Option<&ValueOrigin> {
+        if self.env.contains_key(path) {
+            return None;
+        }
+
         // Try exact match first
         if let Some(origin) = self.origins.get(path) {
             return Some(origin);
@@ -163,6 +284,10 @@ impl OriginTracker {
     /// Does not return a fallback - only returns Some if the field is actually tracked.
     #[must_use]
     pub fn get_file_source(&self, field_name: &str) -> Option<PathBuf> {
+        if self.env.contains_key(field_name) {
+            return None;
+        }
+
         // Only return if we have an exact match or parent match in origins
         // Do NOT use the fallback to most recent source
         if let Some(origin) = self.origins.get(field_name) {
@@ -181,12 +306,37 @@ impl OriginTracker {
         None
     }
 
+    /// Find where the value at `path` was defined in its file.
+    ///
+    /// Returns `None` if the value did not come from a file, or its format
+    /// does not record locations (see
+    /// [`FormatParser::parse_spanned`](super::FormatParser::parse_spanned)).
+    #[must_use]
+    pub fn span_of(&self, path: &str) -> Option<ValueSpan> {
+        let origin = self.origins.get(path)?;
+        let range = origin.spans.get(self.path_in_file(path))?;
+
+        Some(ValueSpan::new(&origin.content, range.clone()))
+    }
+
     /// Check if any files were loaded.
     #[must_use]
     pub const fn has_file_sources(&self) -> bool {
         !self.sources.is_empty()
     }
 
+    /// Keys loaded from files, and prefixed environment variables, that the
+    /// configuration does not define.
+    ///
+    /// Only checked when the known keys are registered with
+    /// [`ConfigBuilder::known_keys`](super::ConfigBuilder::known_keys). Keys
+    /// are dotted paths and variables are their names, e.g.
+    /// `["databse.port", "APP_PROT"]`.
+    #[must_use]
+    pub fn unknown_keys(&self) -> &[String] {
+        &self.unknown
+    }
+
     /// Get all tracked field paths.
     pub fn tracked_fields(&self) -> impl Iterator<Item = &str> {
         self.origins.keys().map(String::as_str)
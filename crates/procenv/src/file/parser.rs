@@ -0,0 +1,318 @@
+//! Pluggable file format parsers.
+//!
+//! Every file loaded by [`ConfigBuilder`](super::ConfigBuilder) goes through
+//! a [`FormatParser`], chosen by the file extension. The built-in
+//! [`FileFormat`]s are parsers themselves; other formats are added by
+//! implementing the trait and registering it with
+//! [`ConfigBuilder::format`](super::ConfigBuilder::format) or
+//! [`FileProviderBuilder::format`](crate::provider::FileProviderBuilder::format).
+//!
+//! # First-Party Parsers
+//!
+//! | Parser | Feature | Extensions |
+//! |--------|---------|------------|
+//! | [`FileFormat::Json`] | `file` (always) | `.json` |
+//! | [`FileFormat::Json5`] | `json5` | `.json5`, `.jsonc` |
+//! | [`FileFormat::Toml`] | `toml` | `.toml` |
+//! | [`FileFormat::Yaml`] | `yaml` | `.yaml`, `.yml` |
+//! | [`IniFormat`](super::IniFormat) | `ini` | `.ini` |
+//! | [`PropertiesFormat`](super::PropertiesFormat) | `properties` | `.properties` |
+//!
+//! Enabled first-party parsers are registered by default. A registered
+//! parser takes precedence over them for the extensions it claims.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use procenv::file::{ConfigBuilder, FormatError, FormatParser};
+//! use serde_json::Value;
+//!
+//! struct Ron;
+//!
+//! impl FormatParser for Ron {
+//!     fn name(&self) -> &'static str {
+//!         "RON"
+//!     }
+//!
+//!     fn extensions(&self) -> &'static [&'static str] {
+//!         &["ron"]
+//!     }
+//!
+//!     fn parse(&self, content: &str) -> Result<Value, FormatError> {
+//!         ron::from_str(content).map_err(|e| {
+//!             FormatError::new(e.code.to_string()).with_line_column(content, e.position.line, e.position.col)
+//!         })
+//!     }
+//! }
+//!
+//! let config: Config = ConfigBuilder::new()
+//!     .format(Ron)
+//!     .file("config.ron")
+//!     .build()?;
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde_json as SJSON;
+
+use super::format::FileFormat;
+use super::utils::FileUtils;
+
+/// Byte ranges of the values in a file, by path.
+///
+/// Paths are dotted, with array elements written as `servers[0]`, the same
+/// paths [`OriginTracker`](super::OriginTracker) tracks.
+pub type ValueSpans = HashMap<String, Range<usize>>;
+
+/// A parser for a configuration file format.
+///
+/// Parsers turn file content into a [`serde_json::Value`], which is then
+/// layered with the other sources. Syntax errors are reported as a
+/// [`FormatError`], which procenv renders as a
+/// [`FileError::Parse`](super::FileError::Parse) pointing into the file.
+pub trait FormatParser: Send + Sync {
+    /// Format name for error messages (e.g., `"INI"`).
+    fn name(&self) -> &'static str;
+
+    /// File extensions handled by this parser, without the leading dot.
+    ///
+    /// Extensions are matched case-insensitively.
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// Parse file content.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FormatError`] if the content is malformed.
+    fn parse(&self, content: &str) -> Result<SJSON::Value, FormatError>;
+
+    /// Parse file content, recording where each value was defined.
+    ///
+    /// The spans let errors and [`ConfigSources`](crate::ConfigSources)
+    /// point at a value's line and column. The default records none.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FormatError`] if the content is malformed.
+    fn parse_spanned(&self, content: &str) -> Result<(SJSON::Value, ValueSpans), FormatError> {
+        Ok((self.parse(content)?, ValueSpans::new()))
+    }
+
+    /// Find the byte offset of the value at a dotted `path` in `content`.
+    ///
+    /// Used to point type mismatch errors at the offending value. The
+    /// default finds nothing, and the error is reported without a location.
+    fn find_field_offset(&self, content: &str, path: &str) -> Option<usize> {
+        let _ = (content, path);
+        None
+    }
+}
+
+impl fmt::Debug for dyn FormatParser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FormatParser").field(&self.name()).finish()
+    }
+}
+
+/// A syntax error reported by a [`FormatParser`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// FormatError::new("expected `=` after the key")
+///     .with_span(12..15)
+///     .with_help("write entries as `key = value`");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatError {
+    message: String,
+    span: Option<Range<usize>>,
+    help: Option<String>,
+}
+
+impl FormatError {
+    /// Create an error without a location.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            span: None,
+            help: None,
+        }
+    }
+
+    /// Point the error at a byte range of the content.
+    ///
+    /// An empty range is widened to the token starting at it.
+    #[must_use]
+    pub const fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Point the error at a 1-based line and column of `content`.
+    #[must_use]
+    pub fn with_line_column(self, content: &str, line: usize, column: usize) -> Self {
+        let offset = FileUtils::line_col_to_offset(content, line, column);
+        self.with_span(offset..offset)
+    }
+
+    /// Suggest how to fix the error.
+    #[must_use]
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Description of what went wrong.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The byte range the error points at, if known.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// The suggestion for how to fix the error, if any.
+    #[must_use]
+    pub fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+/// The parsers available to a builder, looked up by file extension.
+#[derive(Clone, Debug)]
+pub struct FormatRegistry {
+    /// Parsers in registration order; later ones take precedence.
+    parsers: Vec<Arc<dyn FormatParser>>,
+
+    /// Whether to detect the format from the content when no parser
+    /// claims the extension.
+    sniff: bool,
+}
+
+impl Default for FormatRegistry {
+    /// A registry with the enabled first-party parsers.
+    fn default() -> Self {
+        let mut registry = Self {
+            parsers: Vec::new(),
+            sniff: false,
+        };
+
+        registry.register(Arc::new(FileFormat::Json));
+
+        #[cfg(feature = "json5")]
+        registry.register(Arc::new(FileFormat::Json5));
+
+        #[cfg(feature = "toml")]
+        registry.register(Arc::new(FileFormat::Toml));
+
+        #[cfg(feature = "yaml")]
+        registry.register(Arc::new(FileFormat::Yaml));
+
+        #[cfg(feature = "ini")]
+        registry.register(Arc::new(super::IniFormat));
+
+        #[cfg(feature = "properties")]
+        registry.register(Arc::new(super::PropertiesFormat));
+
+        registry
+    }
+}
+
+impl FormatRegistry {
+    /// Add a parser, taking precedence over earlier ones.
+    pub fn register(&mut self, parser: Arc<dyn FormatParser>) {
+        self.parsers.push(parser);
+    }
+
+    /// Detect the format from the content of files with an unknown extension.
+    ///
+    /// See [`FileFormat::sniff`].
+    pub const fn set_sniff(&mut self, enabled: bool) {
+        self.sniff = enabled;
+    }
+
+    /// Whether formats are detected from the content of unknown files.
+    #[must_use]
+    pub const fn sniffs(&self) -> bool {
+        self.sniff
+    }
+
+    /// The parser for `path`'s extension.
+    pub fn for_path(&self, path: &Path) -> Option<Arc<dyn FormatParser>> {
+        let extension = path.extension()?.to_str()?;
+
+        self.parsers
+            .iter()
+            .rev()
+            .find(|parser| {
+                parser
+                    .extensions()
+                    .iter()
+                    .any(|candidate| candidate.eq_ignore_ascii_case(extension))
+            })
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lines;
+
+    impl FormatParser for Lines {
+        fn name(&self) -> &'static str {
+            "lines"
+        }
+
+        fn extensions(&self) -> &'static [&'static str] {
+            &["lines", "json"]
+        }
+
+        fn parse(&self, content: &str) -> Result<SJSON::Value, FormatError> {
+            Ok(content.lines().collect::<Vec<_>>().into())
+        }
+    }
+
+    #[test]
+    fn test_registered_parser_takes_precedence() {
+        let mut registry = FormatRegistry::default();
+        assert_eq!(
+            registry.for_path(Path::new("config.json")).unwrap().name(),
+            "JSON"
+        );
+
+        registry.register(Arc::new(Lines));
+        assert_eq!(
+            registry.for_path(Path::new("config.JSON")).unwrap().name(),
+            "lines"
+        );
+        assert_eq!(
+            registry.for_path(Path::new("a.lines")).unwrap().name(),
+            "lines"
+        );
+        assert!(registry.for_path(Path::new("config.txt")).is_none());
+    }
+
+    #[test]
+    fn test_format_error_from_line_column() {
+        let error = FormatError::new("bad").with_line_column("a = 1\nb = ?\n", 2, 5);
+        assert_eq!(error.span(), Some(10..10));
+    }
+}
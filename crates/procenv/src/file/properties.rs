@@ -0,0 +1,272 @@
+//! Java `.properties` file support.
+//!
+//! Keys are split on `.` into nested objects, so `database.port` maps to
+//! `database.port` in the merged configuration. Values have no types and
+//! are coerced like environment variables.
+//!
+//! ```properties
+//! # Comments start with `#` or `!`
+//! database.host = localhost
+//! database.port: 5432
+//! greeting = hello \
+//!            world
+//! path = C:\\data\\app
+//! ```
+//!
+//! Keys and values are separated by the first unescaped `=`, `:` or
+//! whitespace. A line ending in an unescaped `\` continues on the next
+//! line, and `\t`, `\n`, `\r`, `\f` and `\uXXXX` escapes are supported.
+
+use serde_json as SJSON;
+
+use super::env_path::PathSegment;
+use super::parser::{FormatError, FormatParser};
+use super::utils::FileUtils;
+
+/// Parser for Java `.properties` files.
+///
+/// Registered by default when the `properties` feature is enabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PropertiesFormat;
+
+impl FormatParser for PropertiesFormat {
+    fn name(&self) -> &'static str {
+        "properties"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["properties"]
+    }
+
+    fn parse(&self, content: &str) -> Result<SJSON::Value, FormatError> {
+        let mut root = SJSON::Map::new();
+
+        for line in logical_lines(content) {
+            let (key, value) = split_entry(&line.text);
+            let key = unescape(key, content, &line)?;
+            let value = unescape(value, content, &line)?;
+
+            FileUtils::insert_nested(
+                &mut root,
+                &PathSegment::keys(key.split('.')),
+                FileUtils::coerce_value(&value),
+            )
+            .map_err(|conflict| {
+                FormatError::new(format!("cannot set `{key}`: {conflict}"))
+                    .with_span(line.start..line.start)
+                    .with_help("a key cannot have both a value and nested keys")
+            })?;
+        }
+
+        Ok(SJSON::Value::Object(root))
+    }
+
+    fn find_field_offset(&self, content: &str, path: &str) -> Option<usize> {
+        let mut offset = 0;
+
+        for line in content.split_inclusive('\n') {
+            let indent = line.len() - line.trim_start().len();
+
+            if let Some(rest) = line[indent..].strip_prefix(path)
+                && rest.starts_with([' ', '\t', '=', ':'])
+            {
+                let after_blanks = rest.trim_start_matches([' ', '\t']);
+                let value = after_blanks
+                    .strip_prefix(['=', ':'])
+                    .unwrap_or(after_blanks)
+                    .trim_start_matches([' ', '\t']);
+
+                return Some(offset + line.len() - value.len());
+            }
+
+            offset += line.len();
+        }
+
+        None
+    }
+}
+
+/// An entry after joining continuation lines.
+struct LogicalLine {
+    /// The entry text, without comments or continuation backslashes.
+    text: String,
+
+    /// Byte offset of the entry in the content.
+    start: usize,
+}
+
+/// Split content into entries, skipping blank and comment lines.
+fn logical_lines(content: &str) -> Vec<LogicalLine> {
+    let mut lines = Vec::new();
+    let mut current: Option<LogicalLine> = None;
+    let mut offset = 0;
+
+    for raw in content.split_inclusive('\n') {
+        let line_start = offset;
+        offset += raw.len();
+
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let trimmed = line.trim_start_matches([' ', '\t', '\u{c}']);
+
+        let mut entry = match current.take() {
+            Some(entry) => entry,
+            None if trimmed.is_empty() || trimmed.starts_with(['#', '!']) => continue,
+            None => LogicalLine {
+                text: String::new(),
+                start: line_start + (line.len() - trimmed.len()),
+            },
+        };
+
+        if continues(trimmed) {
+            entry.text.push_str(&trimmed[..trimmed.len() - 1]);
+            current = Some(entry);
+        } else {
+            entry.text.push_str(trimmed);
+            lines.push(entry);
+        }
+    }
+
+    lines.extend(current);
+    lines
+}
+
+/// Whether a line ends in an unescaped backslash.
+fn continues(line: &str) -> bool {
+    line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1
+}
+
+/// Split an entry at the first unescaped `=`, `:` or whitespace.
+fn split_entry(text: &str) -> (&str, &str) {
+    let mut escaped = false;
+
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if matches!(c, '=' | ':' | ' ' | '\t' | '\u{c}') {
+            let rest = text[i..].trim_start_matches([' ', '\t', '\u{c}']);
+            let rest = if matches!(c, '=' | ':') || !rest.starts_with(['=', ':']) {
+                rest.strip_prefix(['=', ':']).unwrap_or(rest)
+            } else {
+                &rest[1..]
+            };
+
+            return (&text[..i], rest.trim_start_matches([' ', '\t', '\u{c}']));
+        }
+    }
+
+    (text, "")
+}
+
+/// Resolve escapes in a key or value of `line`.
+fn unescape(text: &str, content: &str, line: &LogicalLine) -> Result<String, FormatError> {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('f') => result.push('\u{c}'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                let decoded = u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .filter(|_| hex.len() == 4)
+                    .and_then(char::from_u32);
+
+                let Some(decoded) = decoded else {
+                    let end = content[line.start..]
+                        .find(['\n', '\r'])
+                        .map_or(content.len(), |len| line.start + len);
+
+                    return Err(
+                        FormatError::new(format!("invalid unicode escape `\\u{hex}`"))
+                            .with_span(line.start..end)
+                            .with_help("write unicode escapes as `\\u` followed by 4 hex digits"),
+                    );
+                };
+                result.push(decoded);
+            }
+            Some(other) => result.push(other),
+            None => {}
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_nest_and_coerce() {
+        let content = concat!(
+            "# comment\n",
+            "! also a comment\n",
+            "database.host = localhost\n",
+            "database.port: 5432\n",
+            "  debug true\n",
+            "greeting = hello \\\n",
+            "           world\n",
+            "path = C:\\\\data\n",
+            "key\\ with\\ spaces = \\u0041\\tB\n",
+            "empty\n",
+        );
+
+        assert_eq!(
+            PropertiesFormat.parse(content).unwrap(),
+            SJSON::json!({
+                "database": {"host": "localhost", "port": 5432},
+                "debug": true,
+                "greeting": "hello world",
+                "path": "C:\\data",
+                "key with spaces": "A\tB",
+                "empty": "",
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_unicode_escape() {
+        let content = "a = 1\nb = \\u12\n";
+        let error = PropertiesFormat.parse(content).unwrap_err();
+
+        assert_eq!(error.span(), Some(6..14));
+    }
+
+    #[test]
+    fn test_key_with_value_and_nested_keys() {
+        let content = "a = 1\na.b = 2\n";
+        let error = PropertiesFormat.parse(content).unwrap_err();
+
+        assert_eq!(
+            error.message(),
+            "cannot set `a.b`: `a` is a number, not an object"
+        );
+        assert_eq!(error.span().map(|span| span.start), Some(6));
+    }
+
+    #[test]
+    fn test_find_field_offset() {
+        let content = "database.host = localhost\ndatabase.port:5432\n";
+
+        let offset = PropertiesFormat
+            .find_field_offset(content, "database.port")
+            .unwrap();
+        assert_eq!(&content[offset..offset + 4], "5432");
+        assert!(
+            PropertiesFormat
+                .find_field_offset(content, "database")
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Type-directed coercion of environment values.
+//!
+//! Environment variables are strings, so a value that overrides a config key
+//! has to be given a JSON type before it is merged. Without knowing the
+//! target, [`FileUtils::coerce_value`](super::FileUtils::coerce_value) has to
+//! guess, which goes wrong for strings that look like something else:
+//! `APP_VERSION=1.10` becomes the float `1.1` and `APP_ZIP=01234` the integer
+//! `1234`. Once a path's [`ValueType`] is registered with
+//! [`ConfigBuilder::value_type`](super::ConfigBuilder::value_type), its
+//! values are coerced to that type instead.
+
+use serde_json as SJSON;
+
+use super::utils::FileUtils;
+
+/// The JSON type a configuration value deserializes from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ValueType {
+    /// Guessed from the value with
+    /// [`FileUtils::coerce_value`](super::FileUtils::coerce_value).
+    Any,
+    /// Kept as a string, whatever it looks like.
+    String,
+    /// A signed or unsigned integer.
+    Integer,
+    /// A floating-point number; integers are accepted too.
+    Float,
+    /// `true` or `false`, in any case.
+    Bool,
+    /// A JSON array, or comma-separated items of the given type.
+    List(Box<Self>),
+    /// A JSON object.
+    Map,
+}
+
+impl ValueType {
+    /// A list of `item`s.
+    #[must_use]
+    pub fn list(item: Self) -> Self {
+        Self::List(Box::new(item))
+    }
+
+    /// Coerce `s` to this type.
+    ///
+    /// A value that is not valid for the type is kept as a string, so the
+    /// type mismatch is reported when the configuration is deserialized.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use procenv::file::ValueType;
+    /// use serde_json::json;
+    ///
+    /// assert_eq!(ValueType::String.coerce("1.10"), json!("1.10"));
+    /// assert_eq!(ValueType::Integer.coerce("01234"), json!(1234));
+    /// assert_eq!(ValueType::list(ValueType::String).coerce("a, 1"), json!(["a", "1"]));
+    /// assert_eq!(ValueType::Bool.coerce("yes"), json!("yes"));
+    /// ```
+    #[must_use]
+    pub fn coerce(&self, s: &str) -> SJSON::Value {
+        let coerced = match self {
+            Self::Any => Some(FileUtils::coerce_value(s)),
+
+            Self::String => None,
+
+            Self::Integer => s
+                .parse::<i64>()
+                .map(SJSON::Value::from)
+                .or_else(|_| s.parse::<u64>().map(SJSON::Value::from))
+                .ok(),
+
+            Self::Float => s
+                .parse::<f64>()
+                .ok()
+                .and_then(SJSON::Number::from_f64)
+                .map(SJSON::Value::Number),
+
+            Self::Bool => {
+                if s.eq_ignore_ascii_case("true") {
+                    Some(SJSON::Value::Bool(true))
+                } else if s.eq_ignore_ascii_case("false") {
+                    Some(SJSON::Value::Bool(false))
+                } else {
+                    None
+                }
+            }
+
+            Self::List(item) => Some(item.coerce_list(s, ",")),
+
+            Self::Map => SJSON::from_str::<SJSON::Value>(s)
+                .ok()
+                .filter(SJSON::Value::is_object),
+        };
+
+        coerced.unwrap_or_else(|| SJSON::Value::String(s.to_string()))
+    }
+
+    /// Coerce `s` to a list of this type: a JSON array, or items separated
+    /// by `delimiter`.
+    ///
+    /// Items are trimmed and coerced to this type; empty items are dropped.
+    /// A JSON array is taken as written.
+    #[must_use]
+    pub fn coerce_list(&self, s: &str, delimiter: &str) -> SJSON::Value {
+        SJSON::from_str::<SJSON::Value>(s)
+            .ok()
+            .filter(SJSON::Value::is_array)
+            .unwrap_or_else(|| {
+                s.split(delimiter)
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| self.coerce(item))
+                    .collect()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_coerce_to_scalars() {
+        assert_eq!(ValueType::String.coerce("1.10"), json!("1.10"));
+        assert_eq!(ValueType::String.coerce("01234"), json!("01234"));
+        assert_eq!(ValueType::String.coerce("true"), json!("true"));
+
+        assert_eq!(ValueType::Integer.coerce("-7"), json!(-7));
+        assert_eq!(
+            ValueType::Integer.coerce("18446744073709551615"),
+            json!(u64::MAX)
+        );
+        assert_eq!(ValueType::Integer.coerce("80x"), json!("80x"));
+
+        assert_eq!(ValueType::Float.coerce("1.10"), json!(1.1));
+        assert_eq!(ValueType::Float.coerce("2"), json!(2.0));
+        assert_eq!(ValueType::Float.coerce("NaN"), json!("NaN"));
+
+        assert_eq!(ValueType::Bool.coerce("TRUE"), json!(true));
+        assert_eq!(ValueType::Bool.coerce("1"), json!("1"));
+    }
+
+    #[test]
+    fn test_coerce_to_collections() {
+        let any_list = ValueType::list(ValueType::Any);
+        assert_eq!(any_list.coerce("[1, \"a\"]"), json!([1, "a"]));
+        assert_eq!(any_list.coerce("a, 2,,"), json!(["a", 2]));
+        assert_eq!(any_list.coerce(""), json!([]));
+        assert_eq!(
+            ValueType::Any.coerce_list("a;b,c", ";"),
+            json!(["a", "b,c"])
+        );
+
+        // Items are coerced to the item type, not guessed
+        let strings = ValueType::list(ValueType::String);
+        assert_eq!(strings.coerce("01234, true"), json!(["01234", "true"]));
+        let integers = ValueType::list(ValueType::Integer);
+        assert_eq!(integers.coerce("1, 02, x"), json!([1, 2, "x"]));
+
+        assert_eq!(ValueType::Map.coerce(r#"{"a": 1}"#), json!({"a": 1}));
+        assert_eq!(ValueType::Map.coerce("a=1"), json!("a=1"));
+    }
+}
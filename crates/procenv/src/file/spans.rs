@@ -0,0 +1,437 @@
+//! Value locations for the built-in formats.
+//!
+//! Each function walks content that has already parsed successfully and
+//! records the byte range of every value, keyed by the same paths
+//! [`OriginTracker`](super::OriginTracker) uses. Locations are best-effort:
+//! a value that cannot be located is simply left out.
+
+use super::parser::ValueSpans;
+
+/// Path of a key within the object at `parent`.
+fn child_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
+
+/// Path of an element within the array at `parent`.
+fn element_path(parent: &str, index: usize) -> String {
+    format!("{parent}[{index}]")
+}
+
+// ============================================================================
+// JSON and JSON5
+// ============================================================================
+
+/// Locate the values in JSON or JSON5 content.
+///
+/// Lenient enough for JSON5: comments, single-quoted strings, unquoted keys
+/// and trailing commas are all accepted.
+pub fn json(content: &str) -> ValueSpans {
+    let mut scanner = JsonScanner {
+        content,
+        pos: 0,
+        spans: ValueSpans::new(),
+    };
+
+    scanner.value("");
+    scanner.spans
+}
+
+struct JsonScanner<'a> {
+    content: &'a str,
+    pos: usize,
+    spans: ValueSpans,
+}
+
+impl JsonScanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.content.as_bytes().get(self.pos).copied()
+    }
+
+    /// Skip whitespace and comments.
+    fn skip_trivia(&mut self) {
+        loop {
+            let rest = &self.content[self.pos..];
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+
+            if trimmed.starts_with("//") {
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else if let Some(comment) = trimmed.strip_prefix("/*") {
+                self.pos += comment.find("*/").map_or(trimmed.len(), |end| end + 4);
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn value(&mut self, path: &str) {
+        self.skip_trivia();
+        let start = self.pos;
+
+        match self.peek() {
+            Some(b'{') => self.object(path),
+            Some(b'[') => self.array(path),
+            Some(quote @ (b'"' | b'\'')) => self.string(quote),
+            Some(_) => self.scalar(),
+            None => return,
+        }
+
+        if !path.is_empty() {
+            self.spans.insert(path.to_string(), start..self.pos);
+        }
+    }
+
+    fn object(&mut self, path: &str) {
+        self.pos += 1;
+
+        loop {
+            self.skip_trivia();
+            match self.peek() {
+                None => return,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return;
+                }
+                Some(b',') => self.pos += 1,
+                Some(_) => {
+                    let key = self.key();
+                    self.skip_trivia();
+                    if self.peek() != Some(b':') {
+                        return;
+                    }
+                    self.pos += 1;
+                    self.value(&child_path(path, &key));
+                }
+            }
+        }
+    }
+
+    fn array(&mut self, path: &str) {
+        self.pos += 1;
+        let mut index = 0;
+
+        loop {
+            self.skip_trivia();
+            match self.peek() {
+                None => return,
+                Some(b']') => {
+                    self.pos += 1;
+                    return;
+                }
+                Some(b',') => self.pos += 1,
+                Some(_) => {
+                    let before = self.pos;
+                    self.value(&element_path(path, index));
+                    if self.pos == before {
+                        return;
+                    }
+                    index += 1;
+                }
+            }
+        }
+    }
+
+    /// Read an object key, quoted or not.
+    fn key(&mut self) -> String {
+        let start = self.pos;
+
+        if let Some(quote @ (b'"' | b'\'')) = self.peek() {
+            self.string(quote);
+            let raw = &self.content[start + 1..self.pos - 1];
+
+            return if raw.contains('\\') {
+                serde_json::from_str(&format!("\"{raw}\"")).unwrap_or_else(|_| raw.to_string())
+            } else {
+                raw.to_string()
+            };
+        }
+
+        let rest = &self.content[self.pos..];
+        let len = rest
+            .find(|c: char| c == ':' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        self.pos += len;
+        rest[..len].to_string()
+    }
+
+    /// Skip a string, including its quotes.
+    fn string(&mut self, quote: u8) {
+        let bytes = self.content.as_bytes();
+        self.pos += 1;
+
+        while let Some(&byte) = bytes.get(self.pos) {
+            self.pos += if byte == b'\\' { 2 } else { 1 };
+            if byte == quote {
+                break;
+            }
+        }
+
+        self.pos = self.pos.min(bytes.len());
+    }
+
+    /// Skip a number, boolean or `null`.
+    fn scalar(&mut self) {
+        let rest = &self.content[self.pos..];
+        self.pos += rest
+            .find(|c: char| matches!(c, ',' | '}' | ']' | '/') || c.is_whitespace())
+            .unwrap_or(rest.len());
+    }
+}
+
+// ============================================================================
+// TOML
+// ============================================================================
+
+/// Locate the values in TOML content.
+#[cfg(feature = "toml")]
+pub fn toml(content: &str) -> ValueSpans {
+    use toml::de::{DeTable, DeValue};
+
+    fn walk(value: &DeValue<'_>, path: &str, spans: &mut ValueSpans) {
+        match value {
+            DeValue::Table(table) => {
+                for (key, value) in table {
+                    let path = child_path(path, key.get_ref());
+                    spans.insert(path.clone(), value.span());
+                    walk(value.get_ref(), &path, spans);
+                }
+            }
+
+            DeValue::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let path = element_path(path, index);
+                    spans.insert(path.clone(), item.span());
+                    walk(item.get_ref(), &path, spans);
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    let mut spans = ValueSpans::new();
+
+    if let Ok(root) = DeTable::parse(content) {
+        walk(&DeValue::Table(root.into_inner()), "", &mut spans);
+    }
+
+    spans
+}
+
+// ============================================================================
+// YAML
+// ============================================================================
+
+/// Locate the values in the first document of YAML content.
+#[cfg(feature = "yaml")]
+pub fn yaml(content: &str) -> ValueSpans {
+    use saphyr_parser::{Event, Parser};
+
+    /// Where the next key or element of an open collection goes.
+    enum Slot {
+        /// A mapping waiting for its next key.
+        Key,
+        /// A mapping whose next value belongs at this key; `None` for keys
+        /// that are not scalars, whose values are not recorded.
+        Value(Option<String>),
+        /// A sequence and the index of its next element.
+        Element(usize),
+    }
+
+    struct Frame {
+        /// Path of the collection; `None` when it is not recorded.
+        path: Option<String>,
+        slot: Slot,
+        /// Whether the collection is itself a mapping key.
+        is_key: bool,
+    }
+
+    let offsets = CharOffsets::new(content);
+    let mut spans = ValueSpans::new();
+    let mut stack: Vec<Frame> = Vec::new();
+
+    for event in Parser::new_from_str(content) {
+        let Ok((event, span)) = event else {
+            break;
+        };
+        let range = offsets.byte(span.start.index())..offsets.byte(span.end.index());
+
+        // The path of a value starting here, and whether it is a key instead
+        let (path, is_key) = match stack.last_mut() {
+            None => (Some(String::new()), false),
+            Some(frame) => match &mut frame.slot {
+                Slot::Key => (None, true),
+                Slot::Value(key) => {
+                    let path = frame
+                        .path
+                        .as_deref()
+                        .zip(key.take())
+                        .map(|(parent, key)| child_path(parent, &key));
+                    frame.slot = Slot::Key;
+                    (path, false)
+                }
+                Slot::Element(index) => {
+                    let path = frame
+                        .path
+                        .as_deref()
+                        .map(|parent| element_path(parent, *index));
+                    *index += 1;
+                    (path, false)
+                }
+            },
+        };
+
+        match event {
+            Event::Scalar(value, ..) if is_key => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.slot = Slot::Value(Some(value.into_owned()));
+                }
+            }
+
+            Event::Alias(_) if is_key => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.slot = Slot::Value(None);
+                }
+            }
+
+            Event::Scalar(..) | Event::Alias(_) => {
+                if let Some(path) = path.filter(|path| !path.is_empty()) {
+                    spans.insert(path, range);
+                }
+            }
+
+            Event::MappingStart(..) | Event::SequenceStart(..) => {
+                let slot = if matches!(event, Event::MappingStart(..)) {
+                    Slot::Key
+                } else {
+                    Slot::Element(0)
+                };
+
+                if let Some(path) = path.as_ref().filter(|path| !path.is_empty()) {
+                    spans.insert(path.clone(), range);
+                }
+                stack.push(Frame { path, slot, is_key });
+            }
+
+            Event::MappingEnd | Event::SequenceEnd => {
+                let Some(frame) = stack.pop() else {
+                    break;
+                };
+
+                if let Some(path) = frame.path.filter(|path| !path.is_empty())
+                    && let Some(recorded) = spans.get_mut(&path)
+                {
+                    recorded.end = recorded.end.max(range.start);
+                }
+
+                match stack.last_mut() {
+                    Some(parent) if frame.is_key => parent.slot = Slot::Value(None),
+                    None => break,
+                    Some(_) => {}
+                }
+            }
+
+            Event::DocumentEnd => break,
+
+            _ => {}
+        }
+    }
+
+    spans
+}
+
+/// Converts the character indices YAML markers use to byte offsets.
+#[cfg(feature = "yaml")]
+struct CharOffsets {
+    /// Byte offset of each character, then the content length.
+    offsets: Vec<usize>,
+}
+
+#[cfg(feature = "yaml")]
+impl CharOffsets {
+    fn new(content: &str) -> Self {
+        let offsets = content
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain([content.len()])
+            .collect();
+
+        Self { offsets }
+    }
+
+    fn byte(&self, index: usize) -> usize {
+        self.offsets
+            .get(index)
+            .or_else(|| self.offsets.last())
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The text of the value at `path`.
+    fn text<'a>(spans: &ValueSpans, content: &'a str, path: &str) -> &'a str {
+        let range = spans
+            .get(path)
+            .unwrap_or_else(|| panic!("no span for {path}"));
+        &content[range.clone()]
+    }
+
+    #[test]
+    fn test_json_spans() {
+        let content = r#"{
+  "name": "café",
+  "server": {"port": 8080, "hosts": ["a", "b"]},
+  "debug": true
+}"#;
+        let spans = json(content);
+
+        assert_eq!(text(&spans, content, "name"), "\"café\"");
+        assert_eq!(text(&spans, content, "server.port"), "8080");
+        assert_eq!(text(&spans, content, "server.hosts[1]"), "\"b\"");
+        assert_eq!(text(&spans, content, "debug"), "true");
+        assert!(text(&spans, content, "server").starts_with('{'));
+    }
+
+    #[test]
+    fn test_json5_spans() {
+        let content =
+            "// settings\n{\n  name: 'it\\'s', /* inline */ port: 8080,\n  tags: [1, 2,],\n}\n";
+        let spans = json(content);
+
+        assert_eq!(text(&spans, content, "name"), "'it\\'s'");
+        assert_eq!(text(&spans, content, "port"), "8080");
+        assert_eq!(text(&spans, content, "tags[1]"), "2");
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_spans() {
+        let content = "name = \"café\"\n\n[server]\nport = 8080\n\n[[workers]]\nid = 1\n\n[[workers]]\nid = 2\n";
+        let spans = toml(content);
+
+        assert_eq!(text(&spans, content, "name"), "\"café\"");
+        assert_eq!(text(&spans, content, "server.port"), "8080");
+        assert_eq!(text(&spans, content, "workers[1].id"), "2");
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_spans() {
+        let content = "name: café\nserver:\n  port: 8080\n  hosts:\n    - a\n    - b\n? [complex, key]\n: ignored\nlast: 1\n";
+        let spans = yaml(content);
+
+        assert_eq!(text(&spans, content, "name"), "café");
+        assert_eq!(text(&spans, content, "server.port"), "8080");
+        assert_eq!(text(&spans, content, "server.hosts[1]"), "b");
+        assert_eq!(text(&spans, content, "last"), "1");
+        assert!(!spans.keys().any(|path| path.contains("complex")));
+    }
+}
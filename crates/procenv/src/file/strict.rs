@@ -0,0 +1,199 @@
+//! Strict mode: keys the configuration does not define.
+//!
+//! Once the configuration's keys are registered with
+//! [`ConfigBuilder::known_keys`](super::ConfigBuilder::known_keys), every key
+//! loaded from a file, and every variable with the
+//! [`env_prefix`](super::ConfigBuilder::env_prefix), is checked against them.
+//! Unknown ones are listed by
+//! [`OriginTracker::unknown_keys`](super::OriginTracker::unknown_keys), and
+//! with [`ConfigBuilder::strict`](super::ConfigBuilder::strict) they are
+//! errors, each suggesting the known key it was probably meant to be:
+//!
+//! ```text
+//! Error: unknown key `databse.port` in config.toml
+//!    ╭─[config.toml:4:8]
+//!  4 │ port = 5432
+//!    │        ──┬─
+//!    │          ╰── not a known key
+//!    ╰────
+//!   help: did you mean `database.port`?
+//! ```
+
+use std::collections::BTreeSet;
+
+use miette::{NamedSource, SourceSpan};
+
+use super::env_path::{EnvPathStyle, PathSegment};
+use super::error::FileError;
+use super::origin::OriginTracker;
+use super::utils::FileUtils;
+
+/// The paths a configuration defines.
+///
+/// Anything below a known key is accepted too, so a key can hold a map or
+/// a list of any shape.
+#[derive(Clone, Debug, Default)]
+pub struct KnownKeys {
+    keys: Vec<String>,
+}
+
+impl KnownKeys {
+    /// Add known dotted paths.
+    pub fn extend(&mut self, keys: impl IntoIterator<Item = String>) {
+        self.keys.extend(keys);
+    }
+
+    /// Whether no keys are known, which turns checking off.
+    pub const fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Whether `path` is a known key, is below one, or contains one.
+    fn accepts(&self, path: &str) -> bool {
+        self.keys
+            .iter()
+            .any(|key| key == path || is_below(path, key) || is_below(key, path))
+    }
+
+    /// The known keys and the objects containing them, for suggestions.
+    fn candidates(&self) -> BTreeSet<&str> {
+        let mut candidates = BTreeSet::new();
+
+        for key in &self.keys {
+            candidates.insert(key.as_str());
+            candidates.extend(key.match_indices('.').map(|(end, _)| &key[..end]));
+        }
+
+        candidates
+    }
+
+    /// Keys loaded from files that the configuration does not define.
+    ///
+    /// Unknown tables are reported by their entries, and unknown arrays as
+    /// a whole.
+    pub fn unknown_file_keys(&self, origins: &OriginTracker) -> Vec<FileError> {
+        let unknown: BTreeSet<&str> = origins
+            .origins
+            .keys()
+            .filter(|path| !self.accepts(path))
+            .map(|path| path.split('[').next().unwrap_or(path))
+            .collect();
+
+        let candidates = self.candidates();
+
+        unknown
+            .iter()
+            .copied()
+            .filter(|path| {
+                !unknown.iter().any(|other| {
+                    other
+                        .strip_prefix(path)
+                        .is_some_and(|rest| rest.starts_with('.'))
+                })
+            })
+            .filter_map(|path| {
+                let origin = origins.origins.get(path)?;
+
+                Some(FileError::UnknownKey {
+                    key: path.to_string(),
+                    file_path: origin.file_path.clone(),
+                    src: NamedSource::new(origin.file_path.clone(), origin.content.to_string()),
+                    span: origins
+                        .span_of(path)
+                        .map(|span| SourceSpan::from(span.range)),
+                    help: did_you_mean(path, candidates.iter().copied()),
+                })
+            })
+            .collect()
+    }
+
+    /// Variables with `prefix` that neither appear in `mapped` nor name a
+    /// known key.
+    pub fn unknown_env_vars(
+        &self,
+        prefix: &str,
+        style: &EnvPathStyle,
+        mapped: &[&str],
+    ) -> Vec<FileError> {
+        let mut candidates: BTreeSet<String> =
+            mapped.iter().map(|var| (*var).to_string()).collect();
+        candidates.extend(self.keys.iter().map(|key| style.var_name(prefix, key)));
+
+        let unknown: BTreeSet<String> = FileUtils::prefixed_env_vars(prefix, style)
+            .into_iter()
+            .filter(|(path, var, _)| {
+                !mapped.contains(&var.as_str()) && !self.accepts(&PathSegment::join(path))
+            })
+            .map(|(_, var, _)| var)
+            .collect();
+
+        unknown
+            .into_iter()
+            .map(|var| FileError::UnknownEnvVar {
+                help: did_you_mean(&var, candidates.iter().map(String::as_str)),
+                var,
+            })
+            .collect()
+    }
+}
+
+/// Whether `path` is strictly below `ancestor`.
+fn is_below(path: &str, ancestor: &str) -> bool {
+    path.strip_prefix(ancestor)
+        .is_some_and(|rest| rest.starts_with(['.', '[']))
+}
+
+/// Suggest the candidate closest to `name`, if it is close enough to be a
+/// likely typo.
+fn did_you_mean<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    candidates
+        .into_iter()
+        .map(|candidate| (strsim::damerau_levenshtein(name, candidate), candidate))
+        .filter(|(distance, candidate)| {
+            let len = name.chars().count().max(candidate.chars().count());
+            *distance <= (len / 3).max(1)
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| format!("did you mean `{candidate}`?"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn known(keys: &[&str]) -> KnownKeys {
+        let mut known = KnownKeys::default();
+        known.extend(keys.iter().map(|key| (*key).to_string()));
+        known
+    }
+
+    #[test]
+    fn test_accepts_known_keys_their_contents_and_parents() {
+        let known = known(&["name", "database.port", "plugins"]);
+
+        assert!(known.accepts("name"));
+        assert!(known.accepts("database"));
+        assert!(known.accepts("database.port"));
+        assert!(known.accepts("plugins[0].anything"));
+
+        assert!(!known.accepts("databse"));
+        assert!(!known.accepts("database.prot"));
+        assert!(!known.accepts("names"));
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        let known = known(&["name", "database.port", "database.host"]);
+        let candidates = known.candidates();
+
+        assert_eq!(
+            did_you_mean("databse.port", candidates.iter().copied()).as_deref(),
+            Some("did you mean `database.port`?")
+        );
+        assert_eq!(
+            did_you_mean("databse", candidates.iter().copied()).as_deref(),
+            Some("did you mean `database`?")
+        );
+        assert_eq!(did_you_mean("logging", candidates.iter().copied()), None);
+    }
+}
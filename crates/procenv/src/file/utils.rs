@@ -2,7 +2,7 @@
 //!
 //! This module provides the [`FileUtils`] struct with static methods for:
 //!
-//! - **Parsing** - Reading and parsing configuration files (JSON, TOML, YAML)
+//! - **Parsing** - Reading and parsing configuration files with a [`FormatParser`]
 //! - **Merging** - Deep merging JSON values with proper object recursion
 //! - **Coercion** - Converting string values to appropriate JSON types
 //! - **Error Location** - Finding field offsets for precise error reporting
@@ -44,7 +44,8 @@
 //! - `"3.14"` → `Number` (float, only if contains `.`)
 //! - Everything else → `String`
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use miette::{NamedSource, SourceSpan};
 use serde_json as SJSON;
@@ -55,9 +56,21 @@ use serde_saphyr as YAML;
 #[cfg(feature = "toml")]
 use toml as TOML;
 
+use super::env_path::{EnvPathStyle, PathConflict, PathSegment};
 use super::error::FileError;
 use super::format::FileFormat;
-use super::origin::ValueOrigin;
+use super::origin::{EnvOrigin, ValueOrigin};
+use super::parser::{FormatError, FormatParser, FormatRegistry, ValueSpans};
+
+/// Key naming files to merge beneath the file that contains it.
+const INCLUDE_KEY: &str = "_include";
+
+/// Key naming a single base file that the file containing it extends.
+const EXTENDS_KEY: &str = "_extends";
+
+/// A parsed file: its value, where each value was defined, its content, and
+/// the parser that read it.
+pub type ParsedFile = (SJSON::Value, ValueSpans, String, Arc<dyn FormatParser>);
 
 /// Utilities for file parsing and value manipulation.
 ///
@@ -183,6 +196,22 @@ impl FileUtils {
                 })
             }
 
+            #[cfg(feature = "json5")]
+            FileFormat::Json5 => {
+                // Keys may be double-quoted, single-quoted or bare
+                let patterns = vec![
+                    format!("\"{leaf_field}\":"),
+                    format!("\"{leaf_field}\" :"),
+                    format!("'{leaf_field}':"),
+                    format!("'{leaf_field}' :"),
+                    format!("{leaf_field}:"),
+                    format!("{leaf_field} :"),
+                ];
+                Self::find_value_with_patterns(search_content, base_offset, &patterns, |c: char| {
+                    !c.is_whitespace()
+                })
+            }
+
             #[cfg(feature = "toml")]
             FileFormat::Toml => {
                 let patterns = vec![format!("{leaf_field} ="), format!("{leaf_field}=")];
@@ -245,6 +274,24 @@ impl FileUtils {
                 None
             }
 
+            #[cfg(feature = "json5")]
+            FileFormat::Json5 => {
+                // Like JSON, but the parent key may be single-quoted or bare
+                let parent_key = parent_parts.last()?;
+                let patterns = [
+                    format!("\"{parent_key}\""),
+                    format!("'{parent_key}'"),
+                    format!("{parent_key}:"),
+                    format!("{parent_key} :"),
+                ];
+
+                patterns.iter().find_map(|pattern| {
+                    let key_end = content.find(pattern.as_str())? + pattern.len();
+                    let brace_pos = content[key_end..].find('{')?;
+                    Some(&content[key_end + brace_pos..])
+                })
+            }
+
             #[cfg(feature = "yaml")]
             FileFormat::Yaml => {
                 // For YAML, find parent key and return indented content after it
@@ -259,62 +306,83 @@ impl FileUtils {
         }
     }
 
-    pub(crate) fn json_parse_error(e: &SJSON::Error, content: &str, path: &Path) -> FileError {
-        let line = e.line();
-        let col = e.column();
-        let offset = Self::line_col_to_offset(content, line, col);
+    /// Map a parser's syntax error to a [`FileError`] for the file at `path`.
+    pub(crate) fn parse_error(
+        format: &'static str,
+        error: &FormatError,
+        content: &str,
+        path: &Path,
+    ) -> FileError {
+        let help = error
+            .help()
+            .unwrap_or("check the file for syntax errors")
+            .to_string();
+
+        match error.span() {
+            Some(span) => FileError::Parse {
+                format,
+                path: path.display().to_string(),
+                src: NamedSource::new(path.display().to_string(), content.to_string()),
+                span: if span.is_empty() {
+                    Self::offset_to_span(span.start, content)
+                } else {
+                    SourceSpan::new(span.start.into(), span.len())
+                },
+                message: error.message().to_string(),
+                help,
+            },
+            None => FileError::ParseNoSpan {
+                format,
+                message: error.message().to_string(),
+                help,
+            },
+        }
+    }
 
-        FileError::Parse {
-            format: "JSON",
-            path: path.display().to_string(),
-            src: NamedSource::new(path.display().to_string(), content.to_string()),
-            span: Self::offset_to_span(offset, content),
-            message: e.to_string(),
-            help: "check for missing commands, quotes, or brackets".to_string(),
+    pub(crate) fn json_parse_error(e: &SJSON::Error, content: &str) -> FormatError {
+        FormatError::new(e.to_string())
+            .with_line_column(content, e.line(), e.column())
+            .with_help("check for missing commands, quotes, or brackets")
+    }
+
+    #[cfg(feature = "json5")]
+    pub(crate) fn json5_parse_error(e: &json5::Error, content: &str) -> FormatError {
+        let json5::Error::Message { msg, location } = e;
+
+        // Syntax errors render a multi-line report; keep its summary line
+        let message = msg
+            .lines()
+            .rev()
+            .find_map(|line| line.trim_start().strip_prefix("= "))
+            .unwrap_or(msg);
+
+        let mut error = FormatError::new(message);
+        if let Some(location) = location {
+            error = error.with_line_column(content, location.line, location.column);
         }
+
+        error.with_help("check for missing commas, quotes, or brackets")
     }
 
     #[cfg(feature = "toml")]
-    pub(crate) fn toml_parse_error(e: &TOML::de::Error, content: &str, path: &Path) -> FileError {
-        e.span().map_or_else(
-            || FileError::ParseNoSpan {
-                format: "TOML",
-                message: e.to_string(),
-                help: "check for missing quotes, invalid values, or syntax errors".to_string(),
-            },
-            |span| FileError::Parse {
-                format: "TOML",
-                path: path.display().to_string(),
-                src: NamedSource::new(path.display().to_string(), content.to_string()),
-                span: SourceSpan::new(span.start.into(), span.end - span.start),
-                message: e.message().to_string(),
-                help: "check for missing quotes, invalid values, or syntax errors".to_string(),
-            },
-        )
+    pub(crate) fn toml_parse_error(e: &TOML::de::Error) -> FormatError {
+        e.span()
+            .map_or_else(
+                || FormatError::new(e.to_string()),
+                |span| FormatError::new(e.message()).with_span(span),
+            )
+            .with_help("check for missing quotes, invalid values, or syntax errors")
     }
 
     #[cfg(feature = "yaml")]
-    pub(crate) fn yaml_parse_error(e: &YAML::Error, content: &str, path: &Path) -> FileError {
+    pub(crate) fn yaml_parse_error(e: &YAML::Error, content: &str) -> FormatError {
         let msg = e.to_string();
+        let error = match Self::extract_yaml_location(&msg) {
+            Some((line, col)) => FormatError::new(msg).with_line_column(content, line, col),
+            None => FormatError::new(msg),
+        };
 
-        if let Some(loc) = Self::extract_yaml_location(&msg) {
-            let offset = Self::line_col_to_offset(content, loc.0, loc.1);
-
-            FileError::Parse {
-                format: "YAML",
-                path: path.display().to_string(),
-                src: NamedSource::new(path.display().to_string(), content.to_string()),
-                span: Self::offset_to_span(offset, content),
-                message: msg.clone(),
-                help: "check indentation and ensure proper YAML syntax".to_string(),
-            }
-        } else {
-            FileError::ParseNoSpan {
-                format: "YAML",
-                message: msg,
-                help: "check indentation and ensure proper YAML syntax".to_string(),
-            }
-        }
+        error.with_help("check indentation and ensure proper YAML syntax")
     }
 
     /// Try to extract line/column from YAML error message.
@@ -336,23 +404,70 @@ impl FileUtils {
     }
 
     /// Create a type mismatch error with source location.
+    ///
+    /// `file_path` is where the value at `path` is in its file; see
+    /// [`OriginTracker::path_in_file`](super::OriginTracker::path_in_file).
     pub(crate) fn type_mismatch_error(
         path: &str,
+        file_path: &str,
         message: &str,
         origin: &ValueOrigin,
     ) -> Option<FileError> {
-        let offset = Self::find_field_offset(&origin.content, path, origin.format)?;
+        // Prefer the span recorded at parse time over searching the text
+        let span = match origin.spans.get(file_path) {
+            Some(range) if !range.is_empty() => SourceSpan::from(range.clone()),
+            _ => Self::offset_to_span(
+                origin
+                    .format
+                    .find_field_offset(&origin.content, file_path)?,
+                &origin.content,
+            ),
+        };
 
         Some(FileError::TypeMismatch {
             path_str: path.into(),
             file_path: origin.file_path.clone(),
-            src: NamedSource::new(origin.file_path.clone(), origin.content.clone()),
-            span: Self::offset_to_span(offset, &origin.content),
+            src: NamedSource::new(origin.file_path.clone(), origin.content.to_string()),
+            span,
             message: message.to_string(),
             help: "check that the value matches the expected type".to_string(),
         })
     }
 
+    /// Create a type mismatch error for a value set by an environment variable.
+    pub(crate) fn env_type_mismatch_error(
+        path: &str,
+        message: &str,
+        origin: &EnvOrigin,
+    ) -> FileError {
+        // Serde quotes the offending value, so keep only what was expected
+        // when the value is a secret
+        let message = if origin.value.is_redacted() {
+            message
+                .rfind("expected")
+                .map_or("invalid value", |start| &message[start..])
+        } else {
+            message
+        };
+
+        let help = origin.dotenv_file.as_ref().map_or_else(
+            || format!("check the value of the {} environment variable", origin.var),
+            |file| format!("check the value of {} in {}", origin.var, file.display()),
+        );
+
+        FileError::EnvTypeMismatch {
+            path_str: path.into(),
+            var: origin.var.clone(),
+            value: origin.value.clone(),
+            dotenv_file: origin
+                .dotenv_file
+                .as_ref()
+                .map(|file| file.display().to_string()),
+            message: message.to_string(),
+            help,
+        }
+    }
+
     // ============================================================================
     // File Parsing
     // ============================================================================
@@ -391,14 +506,19 @@ impl FileUtils {
     /// let value = FileUtils::parse_file(Path::new("config.local.toml"), false)?;
     /// ```
     pub fn parse_file(path: &Path, required: bool) -> Result<Option<SJSON::Value>, FileError> {
-        Self::parse_file_with_content(path, required).map(|opt| opt.map(|(v, _, _)| v))
+        Self::parse_file_with_content(path, required, &FormatRegistry::default(), None)
+            .map(|opt| opt.map(|(value, ..)| value))
     }
 
     /// Parse a configuration file and return content for error reporting.
+    ///
+    /// `format` overrides the parser chosen from the extension.
     pub(crate) fn parse_file_with_content(
         path: &Path,
         required: bool,
-    ) -> Result<Option<(SJSON::Value, String, FileFormat)>, FileError> {
+        formats: &FormatRegistry,
+        format: Option<FileFormat>,
+    ) -> Result<Option<ParsedFile>, FileError> {
         let path_str = path.display().to_string();
 
         if !path.exists() {
@@ -413,31 +533,98 @@ impl FileUtils {
             source: e,
         })?;
 
-        let format = FileFormat::from_path(path).ok_or_else(|| FileError::UnknownFormat {
-            extension: path
-                .extension()
-                .and_then(|e| e.to_str())
-                .unwrap_or("unknown")
-                .to_string(),
-        })?;
+        let format = match (format, formats.for_path(path)) {
+            (Some(format), _) => Arc::new(format) as Arc<dyn FormatParser>,
+            (None, Some(parser)) => parser,
+            (None, None) if formats.sniffs() => {
+                let (format, value, spans) = Self::sniff_file(&content, path)?;
+                return Ok(Some((value, spans, content, Arc::new(format))));
+            }
+            (None, None) => {
+                return Err(FileError::UnknownFormat {
+                    extension: path
+                        .extension()
+                        .and_then(|e| e.to_str())
+                        .unwrap_or("unknown")
+                        .to_string(),
+                });
+            }
+        };
 
-        let value = match format {
-            FileFormat::Json => serde_json::from_str(&content)
-                .map_err(|e| Self::json_parse_error(&e, &content, path))?,
+        let (value, spans) = format
+            .parse_spanned(&content)
+            .map_err(|e| Self::parse_error(format.name(), &e, &content, path))?;
 
-            #[cfg(feature = "toml")]
-            FileFormat::Toml => {
-                let toml_value: toml::Value = toml::from_str(&content)
-                    .map_err(|e| Self::toml_parse_error(&e, &content, path))?;
-                Self::toml_to_json(toml_value)
+        Ok(Some((value, spans, content, format)))
+    }
+
+    /// Detect the format of a file from its content.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FileError::UndetectedFormat`] listing why each format was
+    /// rejected.
+    fn sniff_file(
+        content: &str,
+        path: &Path,
+    ) -> Result<(FileFormat, SJSON::Value, ValueSpans), FileError> {
+        FileFormat::detect(content).map_err(|attempts| {
+            let tried: Vec<&str> = attempts.iter().map(|(format, _)| format.name()).collect();
+
+            FileError::UndetectedFormat {
+                path: path.display().to_string(),
+                help: format!(
+                    "tried {}; set the format with `file_with_format` or give the file an extension",
+                    tried.join(", ")
+                ),
+                attempts: attempts
+                    .iter()
+                    .map(|(format, e)| Self::parse_error(format.name(), e, content, path))
+                    .collect(),
             }
+        })
+    }
 
-            #[cfg(feature = "yaml")]
-            FileFormat::Yaml => serde_saphyr::from_str(&content)
-                .map_err(|e| Self::yaml_parse_error(&e, &content, path))?,
+    /// Lists files in `dir` whose names match the glob `pattern`.
+    ///
+    /// Entries are returned sorted by file name. Subdirectories are skipped,
+    /// and a missing directory yields an empty list.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FileError::InvalidGlob`] if the pattern is malformed, or
+    /// [`FileError::ReadError`] if the directory exists but cannot be read.
+    pub fn list_dir_matching(dir: &Path, pattern: &str) -> Result<Vec<PathBuf>, FileError> {
+        let glob = glob::Pattern::new(pattern).map_err(|e| FileError::InvalidGlob {
+            pattern: pattern.to_string(),
+            message: e.to_string(),
+        })?;
+
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(FileError::ReadError {
+                    path: dir.display().to_string(),
+                    source: e,
+                });
+            }
         };
 
-        Ok(Some((value, content, format)))
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| glob.matches(name))
+            })
+            .collect();
+
+        paths.sort();
+
+        Ok(paths)
     }
 
     /// Parses a configuration string with an explicit format.
@@ -468,21 +655,111 @@ impl FileUtils {
     /// let value = FileUtils::parse_str(toml_content, FileFormat::Toml)?;
     /// ```
     pub fn parse_str(content: &str, format: FileFormat) -> Result<SJSON::Value, FileError> {
-        let dummy_path = Path::new("<string>");
-        match format {
-            FileFormat::Json => serde_json::from_str(content)
-                .map_err(|e| Self::json_parse_error(&e, content, dummy_path)),
+        format
+            .parse(content)
+            .map_err(|e| Self::parse_error(format.name(), &e, content, Path::new("<string>")))
+    }
 
-            #[cfg(feature = "toml")]
-            FileFormat::Toml => {
-                let toml_value: toml::Value = toml::from_str(content)
-                    .map_err(|e| Self::toml_parse_error(&e, content, dummy_path))?;
-                Ok(Self::toml_to_json(toml_value))
+    // ============================================================================
+    // Include Directives
+    // ============================================================================
+
+    /// Removes the `_extends` and `_include` directives from a parsed file
+    /// and resolves the files they name, in merge order.
+    ///
+    /// Paths are relative to the directory of `path`. `_extends` names a
+    /// single file; `_include` names a file or a list of files, where the
+    /// last component of each may be a glob (e.g. `"db/*.toml"`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FileError::InvalidInclude`] if a directive is not a path or
+    /// list of paths, or [`FileError::InvalidGlob`] for a malformed glob.
+    pub(crate) fn take_includes(
+        value: &mut SJSON::Value,
+        path: &Path,
+        content: &str,
+        format: &dyn FormatParser,
+    ) -> Result<Vec<PathBuf>, FileError> {
+        let SJSON::Value::Object(map) = value else {
+            return Ok(Vec::new());
+        };
+
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        let mut includes = Vec::new();
+
+        if let Some(extends) = map.remove(EXTENDS_KEY) {
+            let SJSON::Value::String(parent) = extends else {
+                return Err(Self::invalid_include(
+                    EXTENDS_KEY,
+                    "expected a path",
+                    path,
+                    content,
+                    format,
+                ));
+            };
+
+            includes.push(base.join(parent));
+        }
+
+        if let Some(include) = map.remove(INCLUDE_KEY) {
+            let entries = match include {
+                SJSON::Value::Array(entries) => entries,
+                entry => vec![entry],
+            };
+
+            for entry in entries {
+                let SJSON::Value::String(entry) = entry else {
+                    return Err(Self::invalid_include(
+                        INCLUDE_KEY,
+                        "expected a path or a list of paths",
+                        path,
+                        content,
+                        format,
+                    ));
+                };
+
+                includes.extend(Self::resolve_include(base, &entry)?);
             }
+        }
 
-            #[cfg(feature = "yaml")]
-            FileFormat::Yaml => serde_saphyr::from_str(content)
-                .map_err(|e| Self::yaml_parse_error(&e, content, dummy_path)),
+        Ok(includes)
+    }
+
+    /// Resolve an `_include` entry against `base`, expanding a glob in its
+    /// last component.
+    fn resolve_include(base: &Path, entry: &str) -> Result<Vec<PathBuf>, FileError> {
+        let path = base.join(entry);
+
+        let pattern = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .filter(|name| name.contains(['*', '?', '[']));
+
+        match (pattern, path.parent()) {
+            (Some(pattern), Some(dir)) => Self::list_dir_matching(dir, pattern),
+            _ => Ok(vec![path]),
+        }
+    }
+
+    /// Build an error for a malformed include directive.
+    fn invalid_include(
+        directive: &'static str,
+        message: &str,
+        path: &Path,
+        content: &str,
+        format: &dyn FormatParser,
+    ) -> FileError {
+        let path_str = path.display().to_string();
+
+        FileError::InvalidInclude {
+            directive,
+            path: path_str.clone(),
+            src: NamedSource::new(path_str, content.to_string()),
+            span: format
+                .find_field_offset(content, directive)
+                .map(|offset| Self::offset_to_span(offset, content)),
+            message: message.to_string(),
         }
     }
 
@@ -492,7 +769,7 @@ impl FileUtils {
 
     /// Convert a TOML Value to a JSON Value.
     #[cfg(feature = "toml")]
-    fn toml_to_json(toml: TOML::Value) -> SJSON::Value {
+    pub(crate) fn toml_to_json(toml: TOML::Value) -> SJSON::Value {
         match toml {
             TOML::Value::String(s) => SJSON::Value::String(s),
 
@@ -581,7 +858,8 @@ impl FileUtils {
     /// 4. String (fallback)
     ///
     /// This is used when converting environment variables to JSON for
-    /// merging with config file values.
+    /// merging with config file values, for paths without a registered
+    /// [`ValueType`](super::ValueType).
     ///
     /// # Example
     ///
@@ -618,44 +896,154 @@ impl FileUtils {
     }
 
     /// Convert environment variables to a nested JSON Value.
-    #[must_use]
-    pub fn env_to_value(prefix: &str, separator: &str) -> SJSON::Value {
+    ///
+    /// Names are mapped to paths with lowercased keys, numeric segments
+    /// included;
+    /// see [`ConfigBuilder::env_separator()`](super::ConfigBuilder::env_separator).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FileError::EnvConflict`] if a variable's path runs into a
+    /// value set by another variable, such as `APP_DB=x` and `APP_DB_HOST=y`.
+    pub fn env_to_value(prefix: &str, separator: &str) -> Result<SJSON::Value, FileError> {
         let mut root = serde_json::Map::new();
+        let mut set = Vec::new();
 
-        for (key, value) in std::env::vars() {
-            if let Some(stripped) = key.strip_prefix(prefix) {
-                let lowered = stripped.to_lowercase();
-                let parts: Vec<&str> = lowered.split(separator).collect();
-                let typed_value = Self::coerce_value(&value);
-                Self::insert_nested(&mut root, &parts, typed_value);
-            }
+        for (path, var, value) in Self::prefixed_env_vars(prefix, &EnvPathStyle::new(separator)) {
+            Self::insert_nested(&mut root, &path, Self::coerce_value(&value))
+                .map_err(|conflict| Self::env_conflict_error(&path, var.clone(), conflict, &set))?;
+            set.push((PathSegment::join(&path), var, value));
         }
 
-        SJSON::Value::Object(root)
+        Ok(SJSON::Value::Object(root))
+    }
+
+    /// Environment variables starting with `prefix`, as their key path, name
+    /// and value.
+    ///
+    /// Variables are sorted by path, so a list's elements come in order and
+    /// an object comes before its keys.
+    pub(crate) fn prefixed_env_vars(
+        prefix: &str,
+        style: &EnvPathStyle,
+    ) -> Vec<(Vec<PathSegment>, String, String)> {
+        let mut vars: Vec<_> = crate::env::vars()
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let path = style.parse(key.strip_prefix(prefix)?);
+                Some((path, key, value))
+            })
+            .collect();
+
+        vars.sort();
+        vars
+    }
+
+    /// Create an error for a variable whose path conflicts with an existing
+    /// value, naming the variable in `set` that set it, if any.
+    ///
+    /// `set` holds the paths already set, with the variables and values that
+    /// set them.
+    pub(crate) fn env_conflict_error(
+        path: &[PathSegment],
+        var: String,
+        conflict: PathConflict,
+        set: &[(String, String, String)],
+    ) -> FileError {
+        let help = match &conflict {
+            PathConflict::Shape { path, .. } => set
+                .iter()
+                .find(|(set_path, ..)| {
+                    set_path
+                        .strip_prefix(path.as_str())
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+                })
+                .map(|(set_path, other, _)| {
+                    format!("{other} sets `{set_path}`; remove one of the two variables")
+                }),
+
+            PathConflict::Gap { path, len, .. } => Some(format!(
+                "set the elements of `{path}` in order, starting from index {len}"
+            )),
+        };
+
+        FileError::EnvConflict {
+            path_str: PathSegment::join(path),
+            var,
+            conflict,
+            help,
+        }
     }
 
     /// Insert a value into a nested map structure.
     ///
-    /// Used for building nested JSON objects from flat key paths like "database.host".
+    /// Used for building nested JSON objects from key paths like
+    /// `database.host` or `servers[1].host`. Missing objects and lists are
+    /// created; an index may address an existing element or append one.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PathConflict`] if the path runs into a value that is not
+    /// the object or list it needs, or skips past the end of a list.
     pub fn insert_nested(
         map: &mut SJSON::Map<String, SJSON::Value>,
-        parts: &[&str],
+        path: &[PathSegment],
         value: SJSON::Value,
-    ) {
-        if parts.is_empty() {
-            return;
-        }
+    ) -> Result<(), PathConflict> {
+        fn insert(
+            target: &mut SJSON::Value,
+            path: &[PathSegment],
+            depth: usize,
+            value: SJSON::Value,
+        ) -> Result<(), PathConflict> {
+            let Some(segment) = path.get(depth) else {
+                *target = value;
+                return Ok(());
+            };
+
+            // A missing value becomes the collection the segment needs
+            if target.is_null() {
+                *target = match segment {
+                    PathSegment::Key(_) => SJSON::Value::Object(SJSON::Map::new()),
+                    PathSegment::Index(_) => SJSON::Value::Array(Vec::new()),
+                };
+            }
 
-        if parts.len() == 1 {
-            map.insert(parts[0].to_string(), value);
-        } else {
-            let entry = map
-                .entry(parts[0].to_string())
-                .or_insert_with(|| SJSON::Value::Object(SJSON::Map::new()));
+            match (segment, target) {
+                (PathSegment::Key(key), SJSON::Value::Object(map)) => {
+                    let child = map.entry(key.clone()).or_insert(SJSON::Value::Null);
+                    insert(child, path, depth + 1, value)
+                }
+
+                (PathSegment::Index(index), SJSON::Value::Array(items)) => {
+                    if *index > items.len() {
+                        return Err(PathConflict::Gap {
+                            path: PathSegment::join(&path[..depth]),
+                            len: items.len(),
+                            index: *index,
+                        });
+                    }
+                    if *index == items.len() {
+                        items.push(SJSON::Value::Null);
+                    }
+                    insert(&mut items[*index], path, depth + 1, value)
+                }
 
-            if let SJSON::Value::Object(nested) = entry {
-                Self::insert_nested(nested, &parts[1..], value);
+                (segment, found) => Err(PathConflict::shape(&path[..depth], found, segment)),
             }
         }
+
+        if path.is_empty() {
+            return Ok(());
+        }
+
+        let mut root = SJSON::Value::Object(std::mem::take(map));
+        let result = insert(&mut root, path, 0, value);
+
+        if let SJSON::Value::Object(root) = root {
+            *map = root;
+        }
+
+        result
     }
 }
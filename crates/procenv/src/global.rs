@@ -0,0 +1,207 @@
+//! Process-wide configuration.
+//!
+//! [`Global`] replaces the usual `static CONFIG: OnceLock<Config>` wrapper:
+//! the configuration is loaded on first access, a load failure is reported
+//! with the rendered [`Error`] diagnostic, and a [`ConfigHandle`] can take
+//! over later so that reloads become visible to every caller.
+//!
+//! [`ConfigHandle`]: crate::ConfigHandle
+
+use std::sync::{Arc, OnceLock};
+
+use crate::Error;
+
+/// What to do when a [`Global`] fails to load its configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailurePolicy {
+    /// Panic with the rendered diagnostic.
+    ///
+    /// Suited to tests and to globals first accessed after startup, where
+    /// unwinding can still be caught.
+    #[default]
+    Panic,
+
+    /// Print the rendered diagnostic to stderr and exit with status 1.
+    ///
+    /// Suited to binaries, where a configuration error should end the
+    /// process without a panic message and backtrace.
+    Exit,
+}
+
+/// A lazily loaded, process-wide configuration.
+///
+/// The loader runs on the first [`get`](Self::get); later calls return the
+/// same value. If loading fails, the [`FailurePolicy`] decides whether to
+/// panic or exit. There is no way to continue without a configuration, so
+/// use the loader directly where the error should be handled.
+///
+/// # Hot Reload
+///
+/// With the `watch` feature, [`watch`](Self::watch) hands the global over
+/// to a [`ConfigHandle`]. From then on `get` returns the handle's current
+/// configuration, so reloads are picked up by all call sites without
+/// changing them. Each `get` returns a snapshot: hold on to the returned
+/// `Arc` to see one consistent configuration across several reads.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use procenv::{FailurePolicy, Global};
+///
+/// static CONFIG: Global<Config> = Global::with_policy(Config::from_env, FailurePolicy::Exit);
+///
+/// fn main() {
+///     println!("listening on port {}", CONFIG.get().port);
+/// }
+/// ```
+///
+/// The derive generates the same wrapper as `Config::global()` when the
+/// struct is marked `#[env_config(global)]`.
+///
+/// [`ConfigHandle`]: crate::ConfigHandle
+pub struct Global<T> {
+    /// Loads the configuration on first access.
+    init: fn() -> Result<T, Error>,
+
+    /// What to do if `init` fails.
+    policy: FailurePolicy,
+
+    /// The configuration loaded by `init`.
+    value: OnceLock<Arc<T>>,
+
+    /// The reloadable configuration, once a handle has taken over.
+    #[cfg(feature = "watch")]
+    watched: parking_lot::RwLock<Option<Arc<crate::WatchedConfig<T>>>>,
+}
+
+impl<T> Global<T> {
+    /// Create a global loaded by `init`, panicking if loading fails.
+    #[must_use]
+    pub const fn new(init: fn() -> Result<T, Error>) -> Self {
+        Self::with_policy(init, FailurePolicy::Panic)
+    }
+
+    /// Create a global loaded by `init`, failing according to `policy`.
+    #[must_use]
+    pub const fn with_policy(init: fn() -> Result<T, Error>, policy: FailurePolicy) -> Self {
+        Self {
+            init,
+            policy,
+            value: OnceLock::new(),
+            #[cfg(feature = "watch")]
+            watched: parking_lot::const_rwlock(None),
+        }
+    }
+
+    /// Get the current configuration, loading it on first access.
+    ///
+    /// # Panics
+    ///
+    /// Panics if loading fails and the policy is [`FailurePolicy::Panic`].
+    /// With [`FailurePolicy::Exit`] the process exits instead.
+    pub fn get(&self) -> Arc<T> {
+        #[cfg(feature = "watch")]
+        if let Some(watched) = &*self.watched.read() {
+            return watched.get();
+        }
+
+        self.value
+            .get_or_init(|| match (self.init)() {
+                Ok(config) => Arc::new(config),
+                Err(error) => self.fail(error),
+            })
+            .clone()
+    }
+
+    /// Returns `true` once the configuration has been loaded or a handle
+    /// has taken over.
+    pub fn is_loaded(&self) -> bool {
+        #[cfg(feature = "watch")]
+        if self.watched.read().is_some() {
+            return true;
+        }
+
+        self.value.get().is_some()
+    }
+
+    /// Report a load failure according to the policy.
+    fn fail(&self, error: Error) -> ! {
+        let report = miette::Report::new(error);
+
+        match self.policy {
+            FailurePolicy::Panic => panic!("failed to load configuration: {report:?}"),
+            FailurePolicy::Exit => {
+                eprintln!("{report:?}");
+                std::process::exit(1)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "watch")]
+impl<T: Clone + Send + Sync + 'static> Global<T> {
+    /// Serve the configuration from `handle` from now on.
+    ///
+    /// Later calls to [`get`](Self::get) return the handle's current
+    /// configuration, including after reloads. The loader is not run if it
+    /// hasn't been already. Calling this again replaces the earlier handle.
+    ///
+    /// The global keeps serving the last configuration if the handle is
+    /// stopped.
+    pub fn watch(&self, handle: &crate::ConfigHandle<T>) {
+        *self.watched.write() = Some(handle.watched().clone());
+    }
+}
+
+impl<T> std::fmt::Debug for Global<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Global")
+            .field("policy", &self.policy)
+            .field("loaded", &self.is_loaded())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static LOADS: AtomicU32 = AtomicU32::new(0);
+
+    #[allow(clippy::unnecessary_wraps, reason = "Global loaders return a Result")]
+    fn load_port() -> Result<u16, Error> {
+        LOADS.fetch_add(1, Ordering::SeqCst);
+        Ok(8080)
+    }
+
+    fn load_missing() -> Result<u16, Error> {
+        Err(Error::missing("PORT"))
+    }
+
+    #[test]
+    fn test_global_loads_once() {
+        static PORT: Global<u16> = Global::new(load_port);
+        assert!(!PORT.is_loaded());
+
+        let first = PORT.get();
+        let second = PORT.get();
+
+        assert_eq!(*first, 8080);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(PORT.is_loaded());
+        assert_eq!(LOADS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_global_panics_with_diagnostic() {
+        static PORT: Global<u16> = Global::with_policy(load_missing, FailurePolicy::Panic);
+
+        let panic = std::panic::catch_unwind(|| PORT.get()).unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+
+        assert!(message.contains("failed to load configuration"));
+        assert!(message.contains("PORT"));
+        assert!(!PORT.is_loaded());
+    }
+}
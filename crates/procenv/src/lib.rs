@@ -86,6 +86,7 @@
 //! | `no_prefix` | Skip struct-level prefix for this field |
 //! | `flatten` | Embed nested config struct |
 //! | `format = "json"` | Parse value as JSON/TOML/YAML |
+//! | `merge = "append"` | Combine config file layers (append/replace/union/patch) |
 //!
 //! ## Struct Attributes
 //!
@@ -95,8 +96,10 @@
 //!     prefix = "APP_",                           // Prefix all env vars
 //!     dotenv,                                    // Load .env file
 //!     file_optional = "config.toml",             // Optional config file
+//!     strict,                                    // Reject unknown keys
 //!     profile_env = "APP_ENV",                   // Profile selection var
-//!     profiles = ["dev", "staging", "prod"]      // Valid profiles
+//!     profiles = ["dev", "staging", "prod"],     // Valid profiles
+//!     global = "exit"                            // Generate global()
 //! )]
 //! struct Config {
 //!     // ...
@@ -116,6 +119,10 @@
 //! | `from_args()` | Load from CLI arguments + env |
 //! | `from_env_validated()` | Load + validate (requires `validator` feature) |
 //! | `env_example()` | Generate `.env.example` template |
+//! | `dotenv_files()` | List configured `.env` files (for hot reload) |
+//! | `watch()` | Hot reload the configured files (requires `watch` feature) |
+//! | `global()` | Process-wide config, loaded on first access (with `global`) |
+//! | `watch_global()` | Hot reload the process-wide config (with `global` and `watch`) |
 //! | `keys()` | List all field names |
 //! | `get_str(&self, key)` | Get field value as string |
 //! | `has_key(key)` | Check if field exists |
@@ -128,9 +135,12 @@
 //! | `secrecy` | [`SecretString`] support for sensitive fields | No |
 //! | `clap` | CLI argument integration with [`clap`] | No |
 //! | `file` | Base config file support (JSON) | No |
+//! | `json5` | JSON5 / JSONC file parsing (implies `file`) | No |
 //! | `toml` | TOML file parsing (implies `file`) | No |
 //! | `yaml` | YAML file parsing (implies `file`) | No |
-//! | `file-all` | All file formats (toml + yaml + json) | No |
+//! | `ini` | INI file parsing (implies `file`) | No |
+//! | `properties` | Java `.properties` file parsing (implies `file`) | No |
+//! | `file-all` | All file formats (toml + yaml + json + json5 + ini + properties) | No |
 //! | `validator` | Validation via [`validator`] crate | No |
 //! | `provider` | Custom provider extensibility | No |
 //! | `watch` | Hot reload with file watching | No |
@@ -227,10 +237,6 @@ pub use toml;
 #[cfg(feature = "yaml")]
 pub use serde_saphyr as yaml;
 
-/// Re-export dotenvy when the dotenv feature is enabled.
-#[cfg(feature = "dotenv")]
-pub use dotenvy;
-
 // ============================================================================
 // Core Modules
 // ============================================================================
@@ -257,9 +263,12 @@ pub use error::{Error, MaybeRedacted};
 /// display configuration errors with colors, help text, and error codes.
 pub type Result<T> = miette::Result<T>;
 
+// Environment lookup, shared by generated code and providers
+pub mod env;
+
 // Source attribution types
 mod source;
-pub use source::{ConfigSources, Source, ValueSource};
+pub use source::{ConfigSources, Source, ValueSource, ValueSpan};
 
 // Validation support (feature-gated)
 #[cfg(feature = "validator")]
@@ -272,6 +281,15 @@ pub use validation::{ValidationFieldError, validation_errors_to_procenv};
 #[cfg(feature = "validator")]
 pub use validator::Validate;
 
+// ============================================================================
+// Dotenv Support
+// ============================================================================
+
+#[cfg(feature = "dotenv")]
+pub mod dotenv;
+#[cfg(feature = "dotenv")]
+pub use dotenv::DotenvError;
+
 // ============================================================================
 // File Configuration Support
 // ============================================================================
@@ -303,6 +321,13 @@ pub use provider::{
 
 pub use loader::ConfigLoader;
 
+// ============================================================================
+// Global Configuration
+// ============================================================================
+
+mod global;
+pub use global::{FailurePolicy, Global};
+
 // ============================================================================
 // Hot Reload Support (Phase E)
 // ============================================================================
@@ -312,6 +337,6 @@ pub mod watch;
 
 #[cfg(feature = "watch")]
 pub use watch::{
-    ChangeTrigger, ChangedField, ConfigChange, ConfigHandle, WatchBuilder, WatchCommand,
-    WatchError, WatchedConfig,
+    ChangeTrigger, ChangedField, ConfigChange, ConfigHandle, DeletePolicy, ReloadStats,
+    WatchBackend, WatchBuilder, WatchCommand, WatchError, WatchRegistry, WatchedConfig,
 };
@@ -80,9 +80,10 @@ impl ConfigLoader {
     ///
     /// # Errors
     ///
-    /// Returns an error if the `.env` file cannot be read.
+    /// Returns an error if the `.env` file cannot be read or parsed.
     #[cfg(feature = "dotenv")]
-    pub fn with_dotenv(self) -> Result<Self, std::io::Error> {
+    #[allow(clippy::result_large_err)]
+    pub fn with_dotenv(self) -> Result<Self, crate::DotenvError> {
         let provider = crate::provider::DotenvProvider::new()?;
         Ok(self.with_provider(Box::new(provider)))
     }
@@ -91,16 +92,36 @@ impl ConfigLoader {
     ///
     /// # Errors
     ///
-    /// Returns an error if the specified file cannot be read.
+    /// Returns an error if the specified file cannot be read or parsed.
     #[cfg(feature = "dotenv")]
+    #[allow(clippy::result_large_err)]
     pub fn with_dotenv_path(
         self,
         path: impl Into<std::path::PathBuf>,
-    ) -> Result<Self, std::io::Error> {
+    ) -> Result<Self, crate::DotenvError> {
         let provider = crate::provider::DotenvProvider::from_path(path)?;
         Ok(self.with_provider(Box::new(provider)))
     }
 
+    /// Adds a dotenv provider layering several files.
+    ///
+    /// The first file to define a key wins; see
+    /// [`DotenvProvider::layered`](crate::DotenvProvider::layered).
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of all files that are missing (if required),
+    /// unreadable or malformed.
+    #[cfg(feature = "dotenv")]
+    #[allow(clippy::result_large_err)]
+    pub fn with_dotenv_files(
+        self,
+        files: impl IntoIterator<Item = crate::dotenv::DotenvFile>,
+    ) -> Result<Self, crate::DotenvError> {
+        let provider = crate::provider::DotenvProvider::layered(files)?;
+        Ok(self.with_provider(Box::new(provider)))
+    }
+
     /// Adds a file provider from a required file.
     ///
     /// # Errors
@@ -70,4 +70,8 @@ impl<P: AsyncProvider> Provider for BlockingAdapter<P> {
     fn priority(&self) -> u32 {
         self.provider.priority()
     }
+
+    fn revision(&self) -> Option<String> {
+        self.runtime.block_on(self.provider.revision())
+    }
 }
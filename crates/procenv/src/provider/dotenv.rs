@@ -2,17 +2,22 @@
 
 use super::{Provider, ProviderResult, ProviderSource, ProviderValue, priority};
 use crate::Source;
+use crate::dotenv::{self, DotenvError, DotenvFile, LayeredValue};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Provider that loads configuration from `.env` files.
 ///
-/// This provider reads a `.env` file once at construction time and
+/// This provider reads its `.env` files once at construction time and
 /// caches the values. It does NOT modify the process environment.
 ///
+/// The files are parsed with the full [dotenv syntax](crate::dotenv), and a
+/// malformed line fails construction with a diagnostic pointing at it.
+///
 /// # Example
 ///
 /// ```rust,ignore
+/// use procenv::dotenv::DotenvFile;
 /// use procenv::provider::DotenvProvider;
 ///
 /// // Load from default .env
@@ -23,11 +28,16 @@ use std::path::PathBuf;
 ///
 /// // Optional loading (returns empty provider if file missing)
 /// let provider = DotenvProvider::from_path_optional(".env.local");
+///
+/// // Several files; `.env.local` wins over `.env`
+/// let provider = DotenvProvider::layered([
+///     DotenvFile::optional(".env.local"),
+///     DotenvFile::required(".env"),
+/// ])?;
 /// ```
 #[derive(Debug, Clone)]
 pub struct DotenvProvider {
-    values: HashMap<String, String>,
-    path: Option<PathBuf>,
+    values: HashMap<String, LayeredValue>,
     prefix: Option<String>,
 }
 
@@ -40,7 +50,8 @@ impl DotenvProvider {
     /// # Errors
     ///
     /// Returns an error if the `.env` file exists but cannot be read or parsed.
-    pub fn new() -> Result<Self, std::io::Error> {
+    #[allow(clippy::result_large_err)]
+    pub fn new() -> Result<Self, DotenvError> {
         Self::from_path_optional(".env")
     }
 
@@ -50,32 +61,42 @@ impl DotenvProvider {
     ///
     /// # Errors
     ///
-    /// Returns an error if the file doesn't exist or cannot be read.
-    pub fn from_path(path: impl Into<PathBuf>) -> Result<Self, std::io::Error> {
-        let path = path.into();
-        let values = Self::parse_dotenv_file(&path)?;
-        Ok(Self {
-            values,
-            path: Some(path),
-            prefix: None,
-        })
+    /// Returns an error if the file doesn't exist, cannot be read, or has
+    /// a malformed line.
+    #[allow(clippy::result_large_err)]
+    pub fn from_path(path: impl Into<PathBuf>) -> Result<Self, DotenvError> {
+        Self::layered([DotenvFile::required(path)])
     }
 
     /// Creates a provider from a path, returning empty if file doesn't exist.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file exists but cannot be read.
-    pub fn from_path_optional(path: impl Into<PathBuf>) -> Result<Self, std::io::Error> {
-        let path = path.into();
-        if !path.exists() {
-            return Ok(Self {
-                values: HashMap::new(),
-                path: None,
-                prefix: None,
-            });
-        }
-        Self::from_path(path)
+    /// Returns an error if the file exists but cannot be read or parsed.
+    #[allow(clippy::result_large_err)]
+    pub fn from_path_optional(path: impl Into<PathBuf>) -> Result<Self, DotenvError> {
+        Self::layered([DotenvFile::optional(path)])
+    }
+
+    /// Creates a provider from several files, in precedence order.
+    ///
+    /// A key defined in several files takes its value from the first of
+    /// them, matching `#[env_config(dotenv = [...])]`, and is attributed to
+    /// that file as [`Source::DotenvFile`]. Later files may reference
+    /// variables from earlier ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns every error at once: missing required files, unreadable
+    /// files and malformed lines. See [`dotenv::read_layered`].
+    #[allow(clippy::result_large_err)]
+    pub fn layered(files: impl IntoIterator<Item = DotenvFile>) -> Result<Self, DotenvError> {
+        let files: Vec<DotenvFile> = files.into_iter().collect();
+
+        Ok(Self {
+            values: dotenv::read_layered(&files)?,
+            prefix: None,
+        })
     }
 
     /// Creates a provider with a key prefix filter.
@@ -88,43 +109,6 @@ impl DotenvProvider {
         self
     }
 
-    /// Parse a dotenv file into a `HashMap`.
-    fn parse_dotenv_file(path: &PathBuf) -> Result<HashMap<String, String>, std::io::Error> {
-        let content = std::fs::read_to_string(path)?;
-        Ok(Self::parse_dotenv_content(&content))
-    }
-
-    /// Parse dotenv content (for testing).
-    fn parse_dotenv_content(content: &str) -> HashMap<String, String> {
-        let mut values = HashMap::new();
-
-        for line in content.lines() {
-            let line = line.trim();
-
-            // Skip empty lines and comments
-            if line.is_empty() || line.starts_with('#') {
-                continue;
-            }
-
-            // Parse KEY=VALUE
-            if let Some(eq_pos) = line.find('=') {
-                let key = line[..eq_pos].trim();
-                let mut value = line[eq_pos + 1..].trim();
-
-                // Strip surrounding quotes
-                if (value.starts_with('"') && value.ends_with('"'))
-                    || (value.starts_with('\'') && value.ends_with('\''))
-                {
-                    value = &value[1..value.len() - 1];
-                }
-
-                values.insert(key.to_string(), value.to_string());
-            }
-        }
-
-        values
-    }
-
     /// Returns the full key with prefix handling.
     fn lookup_key(&self, key: &str) -> String {
         self.prefix
@@ -137,7 +121,6 @@ impl Default for DotenvProvider {
     fn default() -> Self {
         Self::new().unwrap_or_else(|_| Self {
             values: HashMap::new(),
-            path: None,
             prefix: None,
         })
     }
@@ -153,10 +136,10 @@ impl Provider for DotenvProvider {
 
         self.values.get(&lookup).map_or_else(
             || Ok(None),
-            |value| {
+            |layered| {
                 Ok(Some(ProviderValue {
-                    value: value.clone(),
-                    source: ProviderSource::BuiltIn(Source::DotenvFile(self.path.clone())),
+                    value: layered.value.clone(),
+                    source: ProviderSource::BuiltIn(Source::DotenvFile(Some(layered.path.clone()))),
                     secret: false,
                 }))
             },
@@ -173,23 +156,72 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_dotenv_content() {
-        let content = r#"
+    fn test_from_path_parses_full_syntax() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        std::fs::write(
+            &path,
+            r#"
 # Comment
-DATABASE_URL=postgres://localhost/db
-PORT=8080
+export DATABASE_URL=postgres://localhost/db # inline comment
 QUOTED="hello world"
 SINGLE='single quoted'
-"#;
-        let values = DotenvProvider::parse_dotenv_content(content);
+PEM="-----BEGIN KEY-----
+abc
+-----END KEY-----"
+"#,
+        )
+        .unwrap();
+
+        let provider = DotenvProvider::from_path(&path).unwrap();
+        let value = |key: &str| provider.get(key).unwrap().map(|v| v.value);
+
+        assert_eq!(
+            value("DATABASE_URL").as_deref(),
+            Some("postgres://localhost/db")
+        );
+        assert_eq!(value("QUOTED").as_deref(), Some("hello world"));
+        assert_eq!(value("SINGLE").as_deref(), Some("single quoted"));
+        assert_eq!(
+            value("PEM").as_deref(),
+            Some("-----BEGIN KEY-----\nabc\n-----END KEY-----")
+        );
+    }
+
+    #[test]
+    fn test_from_path_rejects_malformed_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        std::fs::write(&path, "PORT=8080\nNOT A LINE\n").unwrap();
+
+        let error = DotenvProvider::from_path(&path).unwrap_err();
+        assert!(matches!(error, DotenvError::Syntax { line: 2, .. }));
+    }
+
+    #[test]
+    fn test_layered_attributes_winning_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join(".env");
+        let local = dir.path().join(".env.local");
+        std::fs::write(&base, "PORT=8080\nHOST=localhost\n").unwrap();
+        std::fs::write(&local, "PORT=9090\n").unwrap();
+
+        let provider =
+            DotenvProvider::layered([DotenvFile::optional(&local), DotenvFile::required(&base)])
+                .unwrap();
+
+        let port = provider.get("PORT").unwrap().unwrap();
+        assert_eq!(port.value, "9090");
+        assert_eq!(
+            port.source,
+            ProviderSource::BuiltIn(Source::DotenvFile(Some(local)))
+        );
 
+        let host = provider.get("HOST").unwrap().unwrap();
         assert_eq!(
-            values.get("DATABASE_URL"),
-            Some(&"postgres://localhost/db".to_string())
+            host.source,
+            ProviderSource::BuiltIn(Source::DotenvFile(Some(base)))
         );
-        assert_eq!(values.get("PORT"), Some(&"8080".to_string()));
-        assert_eq!(values.get("QUOTED"), Some(&"hello world".to_string()));
-        assert_eq!(values.get("SINGLE"), Some(&"single quoted".to_string()));
     }
 
     #[test]
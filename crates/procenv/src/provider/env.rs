@@ -59,7 +59,7 @@ impl Provider for EnvProvider {
     fn get(&self, key: &str) -> ProviderResult<ProviderValue> {
         let full_key = self.full_key(key);
 
-        match std::env::var(&full_key) {
+        match crate::env::var(&full_key) {
             Ok(value) => Ok(Some(ProviderValue {
                 value,
                 source: ProviderSource::BuiltIn(Source::Environment),
@@ -2,11 +2,12 @@
 
 use super::{Provider, ProviderError, ProviderResult, ProviderSource, ProviderValue, priority};
 use crate::Source;
-use crate::file::{ConfigBuilder, FileFormat, FileUtils, OriginTracker};
+use crate::file::{ConfigBuilder, FileFormat, FileUtils, FormatParser, Merge, OriginTracker};
 use serde_json::Value;
 use std::path::{Path, PathBuf};
 
-/// Provider that loads configuration from files (TOML, JSON, YAML).
+/// Provider that loads configuration from files (TOML, JSON, YAML, or any
+/// registered [`FormatParser`]).
 ///
 /// This provider wraps the existing `ConfigBuilder` functionality to
 /// provide file-based configuration as a provider in the chain.
@@ -163,6 +164,38 @@ impl FileProviderBuilder {
         self
     }
 
+    /// Adds a required file parsed as `format`.
+    ///
+    /// See [`ConfigBuilder::file_with_format`].
+    pub fn file_with_format(mut self, path: impl AsRef<Path>, format: FileFormat) -> Self {
+        self.inner = self.inner.file_with_format(path, format);
+        self
+    }
+
+    /// Detects the format of files with an unknown extension from their content.
+    ///
+    /// See [`ConfigBuilder::sniff_formats`].
+    pub fn sniff_formats(mut self, enabled: bool) -> Self {
+        self.inner = self.inner.sniff_formats(enabled);
+        self
+    }
+
+    /// Sets how values at `path` are merged across files.
+    ///
+    /// See [`ConfigBuilder::merge_strategy`].
+    pub fn merge_strategy(mut self, path: impl Into<String>, strategy: Merge) -> Self {
+        self.inner = self.inner.merge_strategy(path, strategy);
+        self
+    }
+
+    /// Registers a parser for additional file extensions.
+    ///
+    /// See [`ConfigBuilder::format`].
+    pub fn format(mut self, parser: impl FormatParser + 'static) -> Self {
+        self.inner = self.inner.format(parser);
+        self
+    }
+
     /// Builds the file provider.
     ///
     /// # Errors
@@ -453,6 +453,48 @@ pub trait Provider: Send + Sync {
     fn fallthrough(&self) -> bool {
         true
     }
+
+    /// Returns an opaque token that changes whenever the provider's values do.
+    ///
+    /// Hot reload polls this to detect changes (e.g. a Consul index, an etcd
+    /// revision or a content hash). The default, `None`, means the provider
+    /// cannot detect changes; a `None` while polling is treated as unknown
+    /// and never triggers a reload.
+    fn revision(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Shares a provider between a [`ConfigLoader`](crate::ConfigLoader) and a
+/// watcher polling its [`revision`](Provider::revision).
+impl<P: Provider + ?Sized> Provider for std::sync::Arc<P> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn get(&self, key: &str) -> ProviderResult<ProviderValue> {
+        (**self).get(key)
+    }
+
+    fn get_many(&self, keys: &[&str]) -> HashMap<String, ProviderResult<ProviderValue>> {
+        (**self).get_many(keys)
+    }
+
+    fn is_available(&self) -> bool {
+        (**self).is_available()
+    }
+
+    fn priority(&self) -> u32 {
+        (**self).priority()
+    }
+
+    fn fallthrough(&self) -> bool {
+        (**self).fallthrough()
+    }
+
+    fn revision(&self) -> Option<String> {
+        (**self).revision()
+    }
 }
 
 // ============================================================================
@@ -528,6 +570,15 @@ pub trait AsyncProvider: Send + Sync {
     fn priority(&self) -> u32 {
         priority::CUSTOM
     }
+
+    /// Returns an opaque token that changes whenever the provider's values do.
+    ///
+    /// See [`Provider::revision`]; [`BlockingAdapter`] forwards it so async
+    /// providers can be watched too.
+    #[allow(clippy::elidable_lifetime_names)]
+    fn revision<'a>(&'a self) -> BoxFuture<'a, Option<String>> {
+        Box::pin(async { None })
+    }
 }
 
 // ============================================================================
@@ -600,6 +651,18 @@ mod tests {
         assert_eq!(custom.to_string(), "vault (secret/app/db)");
     }
 
+    #[test]
+    fn test_provider_revision() {
+        let provider = TestProvider::new();
+        assert_eq!(provider.revision(), None);
+
+        // Shared providers forward everything
+        let shared = std::sync::Arc::new(provider);
+        assert_eq!(Provider::name(&shared), "test");
+        assert_eq!(shared.get("key1").unwrap().unwrap().value, "value1");
+        assert_eq!(Provider::revision(&shared), None);
+    }
+
     #[test]
     fn test_provider_value_builder() {
         let value = ProviderValue::new("test", ProviderSource::environment()).with_secret(true);
@@ -4,6 +4,7 @@
 //! enabling debugging and auditing of configuration loading.
 
 use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
 use std::path::PathBuf;
 
 /// Indicates where a configuration value originated from.
@@ -40,6 +41,11 @@ use std::path::PathBuf;
 /// }
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "kind", content = "value", rename_all = "snake_case")
+)]
 #[non_exhaustive]
 pub enum Source {
     /// Value was provided via a CLI argument (e.g., `--port 8080`).
@@ -61,7 +67,8 @@ pub enum Source {
 
     /// Value was loaded from a configuration file (TOML, JSON, or YAML).
     ///
-    /// The optional [`PathBuf`] contains the path to the file.
+    /// The optional [`PathBuf`] contains the path to the file; where in it
+    /// the value was defined is [`ValueSource::span()`].
     /// This source is used when `#[env_config(file = "...")]` is configured.
     ConfigFile(Option<PathBuf>),
 
@@ -115,6 +122,60 @@ impl Display for Source {
     }
 }
 
+/// Where a value was defined in a configuration file.
+///
+/// Recorded when the file is parsed, and displayed as `line:column`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let (config, sources) = Config::from_config_with_sources()?;
+///
+/// if let Some(port) = sources.get("port")
+///     && let (Source::ConfigFile(Some(path)), Some(span)) = (&port.source, port.span())
+/// {
+///     println!("port set at {}:{span}", path.display());  // config.toml:12:7
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ValueSpan {
+    /// The 1-based line of the value.
+    pub line: usize,
+
+    /// The 1-based column of the value, in characters.
+    pub column: usize,
+
+    /// The byte range of the value in the file.
+    pub range: Range<usize>,
+}
+
+impl ValueSpan {
+    /// Locate the byte `range` within `content`.
+    #[must_use]
+    pub fn new(content: &str, range: Range<usize>) -> Self {
+        let mut start = range.start.min(content.len());
+        while !content.is_char_boundary(start) {
+            start -= 1;
+        }
+
+        let before = &content[..start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            range,
+        }
+    }
+}
+
+impl Display for ValueSpan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
 /// Source information for a single configuration value.
 ///
 /// This struct pairs an environment variable name with its [`Source`],
@@ -128,12 +189,17 @@ impl Display for Source {
 /// println!("{}", source);  // "DATABASE_URL: Environment variable"
 /// ```
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ValueSource {
     /// The environment variable name (e.g., `"DATABASE_URL"`).
     pub var_name: String,
 
     /// Where the value originated from.
     pub source: Source,
+
+    /// Where the value was defined in its [`Source::ConfigFile`].
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    span: Option<ValueSpan>,
 }
 
 impl ValueSource {
@@ -147,13 +213,38 @@ impl ValueSource {
         Self {
             var_name: var_name.into(),
             source,
+            span: None,
+        }
+    }
+
+    /// Sets where the value was defined in its config file.
+    #[must_use]
+    pub const fn with_span(mut self, span: Option<ValueSpan>) -> Self {
+        self.span = span;
+        self
+    }
+
+    /// Where the value was defined in its [`Source::ConfigFile`], when the
+    /// file's format records locations.
+    #[must_use]
+    pub const fn span(&self) -> Option<&ValueSpan> {
+        self.span.as_ref()
+    }
+
+    /// The source, with the span after a config file's path.
+    fn located_source(&self) -> String {
+        match (&self.source, &self.span) {
+            (Source::ConfigFile(Some(path)), Some(span)) => {
+                format!("Config file ({}:{span})", path.display())
+            }
+            (source, _) => source.to_string(),
         }
     }
 }
 
 impl Display for ValueSource {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {}", self.var_name, self.source)
+        write!(f, "{}: {}", self.var_name, self.located_source())
     }
 }
 
@@ -301,7 +392,7 @@ impl Display for ConfigSources {
                 f,
                 "  {:<width$}  <- {} [{}]",
                 field_name,
-                source.source,
+                source.located_source(),
                 source.var_name,
                 width = max_len,
             )?;
@@ -311,6 +402,20 @@ impl Display for ConfigSources {
     }
 }
 
+/// Serializes as a map from field name to [`ValueSource`], in load order.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ConfigSources {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+        for (field_name, source) in &self.entries {
+            map.serialize_entry(field_name, source)?;
+        }
+        map.end()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -327,6 +432,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_config_file_display_includes_location() {
+        let content = "name = \"app\"\n\n[server]\nport = 8080\n";
+        let span = ValueSpan::new(content, 30..34);
+        assert_eq!((span.line, span.column), (4, 8));
+
+        let source = ValueSource::new("PORT", Source::ConfigFile(Some("config.toml".into())));
+        assert_eq!(source.to_string(), "PORT: Config file (config.toml)");
+        assert_eq!(
+            source.with_span(Some(span)).to_string(),
+            "PORT: Config file (config.toml:4:8)"
+        );
+    }
+
     #[test]
     fn test_source_equality() {
         assert_eq!(Source::Environment, Source::Environment);
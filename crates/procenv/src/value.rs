@@ -110,9 +110,9 @@ pub enum ConfigValue {
     /// A boolean value.
     Boolean(bool),
     /// A list of values.
-    List(Vec<ConfigValue>),
+    List(Vec<Self>),
     /// A map of string keys to values.
-    Map(HashMap<String, ConfigValue>),
+    Map(HashMap<String, Self>),
     /// No value (missing optional).
     None,
 }
@@ -0,0 +1,129 @@
+//! Audit log for applied and rejected reloads.
+//!
+//! Each reload is appended to the log as one JSON object per line:
+//!
+//! ```text
+//! {"event":"applied","trigger":{"kind":"file_modified","value":"config.toml"},"timestamp":"2025-01-01T12:00:00.000Z",...}
+//! {"event":"rejected","timestamp":"2025-01-01T12:00:05.000Z","message":"...","errors":["..."]}
+//! ```
+//!
+//! Field values come from the masked [`ConfigChange::field_changes`], and
+//! error messages never contain secret values, so the log is safe to ship
+//! to a log store.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::Serialize;
+
+use super::types::{ConfigChange, WatchError, rfc3339};
+
+/// One line of the audit log.
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case", bound = "")]
+enum Record<'a, T> {
+    /// A reload that replaced the configuration.
+    Applied(&'a ConfigChange<T>),
+
+    /// A reload that failed; the previous configuration was kept.
+    Rejected {
+        timestamp: String,
+        message: &'a str,
+        errors: Vec<String>,
+    },
+}
+
+/// Appends JSON lines for reloads to a file.
+pub struct AuditLog {
+    /// Path of the log file.
+    path: PathBuf,
+    /// The log file, opened for appending.
+    file: File,
+}
+
+impl AuditLog {
+    /// Open `path` for appending, creating it if needed.
+    pub fn open(path: &Path) -> Result<Self, WatchError> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| WatchError::path_error(path, format!("failed to open audit log: {e}")))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+        })
+    }
+
+    /// Record an applied reload.
+    pub fn record_change<T>(&mut self, change: &ConfigChange<T>) -> Result<(), WatchError> {
+        self.append(&Record::Applied(change))
+    }
+
+    /// Record a rejected reload. Other errors are not reloads and are skipped.
+    pub fn record_error(&mut self, error: &WatchError) -> Result<(), WatchError> {
+        let WatchError::ReloadFailed { message, errors } = error else {
+            return Ok(());
+        };
+
+        self.append::<()>(&Record::Rejected {
+            timestamp: rfc3339(SystemTime::now()),
+            message,
+            errors: errors.iter().map(ToString::to_string).collect(),
+        })
+    }
+
+    /// Append a record as a single line.
+    fn append<T>(&mut self, record: &Record<'_, T>) -> Result<(), WatchError> {
+        let mut line = serde_json::to_string(record).map_err(|e| {
+            WatchError::path_error(&self.path, format!("failed to serialize audit record: {e}"))
+        })?;
+        line.push('\n');
+
+        // A single write keeps concurrent appenders from interleaving lines
+        self.file.write_all(line.as_bytes()).map_err(|e| {
+            WatchError::path_error(&self.path, format!("failed to write audit log: {e}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConfigSources;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_audit_log_appends_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let mut log = AuditLog::open(&path).unwrap();
+
+        let change = ConfigChange::new(
+            None,
+            Arc::new(8080_u16),
+            Vec::new(),
+            super::super::ChangeTrigger::ManualReload,
+            ConfigSources::new(),
+        );
+        log.record_change(&change).unwrap();
+        log.record_error(&WatchError::reload_failed("invalid port", Vec::new()))
+            .unwrap();
+        log.record_error(&WatchError::Stopped).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["event"], "applied");
+        assert_eq!(lines[0]["trigger"]["kind"], "manual_reload");
+        assert_eq!(lines[1]["event"], "rejected");
+        assert_eq!(lines[1]["message"], "invalid port");
+    }
+}
@@ -7,9 +7,14 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
+use super::audit::AuditLog;
+use super::env::{DotenvTracker, EnvPollConfig};
 use super::handle::ConfigHandle;
-use super::types::{ConfigChange, WatchError};
-use super::watcher::{ConfigWatcher, WatcherConfig};
+use super::provider::ProviderPoller;
+use super::registry::WatchRegistry;
+use super::types::{ConfigChange, DeletePolicy, FieldDiff, WatchBackend, WatchError};
+use super::watcher::{ConfigWatcher, ReloadPolicy, WatcherConfig};
+use crate::provider::Provider;
 use crate::{ConfigSources, Error};
 
 /// Callback type for configuration changes.
@@ -18,12 +23,34 @@ pub type ChangeCallback<T> = Box<dyn Fn(ConfigChange<T>) + Send + Sync + 'static
 /// Callback type for reload errors.
 pub type ErrorCallback = Box<dyn Fn(WatchError) + Send + Sync + 'static>;
 
+/// Watcher configuration, field accessors and callbacks split from a builder.
+type BuildParts<T> = (WatcherConfig, Option<FieldDiff<T>>, Callbacks<T>);
+
+/// Callbacks and sinks run on the handle's callback thread.
+pub struct Callbacks<T> {
+    /// Callback for configuration changes.
+    pub on_change: Option<ChangeCallback<T>>,
+    /// Callback for errors.
+    pub on_error: Option<ErrorCallback>,
+    /// Audit log for applied and rejected reloads.
+    pub audit: Option<AuditLog>,
+}
+
+impl<T> Callbacks<T> {
+    /// Returns `true` if nothing needs to run on a callback thread.
+    pub const fn is_empty(&self) -> bool {
+        self.on_change.is_none() && self.on_error.is_none() && self.audit.is_none()
+    }
+}
+
 /// Builder for configuring hot reload behavior.
 ///
 /// `WatchBuilder` provides a fluent API for setting up file watching with
 /// customizable options including:
 /// - Files to watch
-/// - Debounce duration
+/// - Dotenv files and environment polling
+/// - Debounce duration, rate limiting and failure backoff
+/// - Native or polling backend
 /// - Change callbacks
 /// - Error callbacks
 ///
@@ -46,14 +73,44 @@ pub struct WatchBuilder<T: Clone + Send + Sync + 'static> {
     /// Files to watch.
     files: Vec<PathBuf>,
 
+    /// Directories to watch, with a glob pattern for file names.
+    dirs: Vec<(PathBuf, String)>,
+
+    /// Dotenv files to watch, in load order.
+    dotenv_files: Vec<PathBuf>,
+
+    /// Process environment polling, if enabled.
+    env_poll: Option<EnvPollConfig>,
+
+    /// Unix signals that trigger a reload.
+    signals: Vec<i32>,
+
+    /// Providers whose revisions are polled, with their intervals.
+    providers: Vec<(Arc<dyn Provider>, Duration)>,
+
     /// Debounce duration (default: 100ms).
     debounce: Duration,
 
+    /// How file changes are detected (default: auto).
+    backend: WatchBackend,
+
+    /// Rate limiting and failure backoff (default: none).
+    policy: ReloadPolicy,
+
+    /// What to do when a watched file is deleted (default: reload).
+    delete_policy: DeletePolicy,
+
     /// Callback for configuration changes.
     on_change: Option<ChangeCallback<T>>,
 
     /// Callback for errors.
     on_error: Option<ErrorCallback>,
+
+    /// Field accessors for reporting changed fields.
+    diff: Option<FieldDiff<T>>,
+
+    /// Path of the audit log, if enabled.
+    audit_log: Option<PathBuf>,
 }
 
 impl<T: Clone + Send + Sync + 'static> WatchBuilder<T> {
@@ -62,14 +119,25 @@ impl<T: Clone + Send + Sync + 'static> WatchBuilder<T> {
     /// Default settings:
     /// - No files watched
     /// - 100ms debounce
+    /// - Native backend, falling back to polling
     /// - No callbacks
     #[must_use]
     pub fn new() -> Self {
         Self {
             files: Vec::new(),
+            dirs: Vec::new(),
+            dotenv_files: Vec::new(),
+            env_poll: None,
+            signals: Vec::new(),
+            providers: Vec::new(),
             debounce: Duration::from_millis(100),
+            backend: WatchBackend::Auto,
+            policy: ReloadPolicy::default(),
+            delete_policy: DeletePolicy::Reload,
             on_change: None,
             on_error: None,
+            diff: None,
+            audit_log: None,
         }
     }
 
@@ -110,6 +178,206 @@ impl<T: Clone + Send + Sync + 'static> WatchBuilder<T> {
         self
     }
 
+    /// Watch a directory of configuration fragments.
+    ///
+    /// Any file directly inside `path` whose name matches the glob `pattern`
+    /// triggers a reload when it is created, modified, removed or renamed.
+    /// Pair this with [`ConfigBuilder::dir`](crate::ConfigBuilder::dir) in
+    /// the reload function so new fragments are picked up.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WatchBuilder::new()
+    ///     .watch_file("/etc/myapp/config.toml")
+    ///     .watch_dir("/etc/myapp/conf.d", "*.toml")
+    /// ```
+    #[must_use]
+    pub fn watch_dir(mut self, path: impl AsRef<Path>, pattern: impl Into<String>) -> Self {
+        self.dirs
+            .push((path.as_ref().to_path_buf(), pattern.into()));
+        self
+    }
+
+    /// Watch a dotenv file for changes.
+    ///
+    /// When the file changes, the watcher re-reads it and reloads with the
+    /// new values in effect, since dotenv loading never overrides variables
+    /// that are already set. The reload is reported as
+    /// [`ChangeTrigger::EnvVarChanged`] (or [`ChangeTrigger::EnvVarsChanged`])
+    /// with the names of the changed variables; edits that change no
+    /// effective value do not reload.
+    ///
+    /// Variables that were set in the real environment before the watcher
+    /// started keep precedence over the file, matching the initial load.
+    ///
+    /// Files are layered in the order they are added: the first file to
+    /// define a variable wins, as with `#[env_config(dotenv = [...])]`.
+    /// Relative paths are looked up in the current directory and its
+    /// parents, matching that loading as well.
+    ///
+    /// # Environment
+    ///
+    /// The process environment is not modified. The values are visible to
+    /// the reload function through [`procenv::env::var`](crate::env::var),
+    /// which the derive uses; a reload function that reads variables with
+    /// [`std::env::var`] sees the environment as of the initial load.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WatchBuilder::new()
+    ///     .watch_file("config.toml")
+    ///     .watch_dotenv(".env")
+    /// ```
+    ///
+    /// [`ChangeTrigger::EnvVarChanged`]: super::ChangeTrigger::EnvVarChanged
+    /// [`ChangeTrigger::EnvVarsChanged`]: super::ChangeTrigger::EnvVarsChanged
+    #[must_use]
+    pub fn watch_dotenv(mut self, path: impl AsRef<Path>) -> Self {
+        self.dotenv_files.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Watch multiple dotenv files, in load order.
+    ///
+    /// Pairs with the derive-generated `dotenv_files()` method to watch the
+    /// files configured via `#[env_config(dotenv = ...)]`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WatchBuilder::new()
+    ///     .watch_dotenv_files(Config::dotenv_files())
+    /// ```
+    #[must_use]
+    pub fn watch_dotenv_files<P: AsRef<Path>>(
+        mut self,
+        paths: impl IntoIterator<Item = P>,
+    ) -> Self {
+        self.dotenv_files
+            .extend(paths.into_iter().map(|p| p.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Poll the process environment for changes.
+    ///
+    /// Every `interval`, the environment is compared against the previous
+    /// snapshot, and any difference triggers a reload reported as
+    /// [`ChangeTrigger::EnvVarChanged`]. This covers in-process
+    /// [`std::env::set_var`] calls, which no file watcher can see.
+    ///
+    /// By default the whole environment is compared; use
+    /// [`poll_env_vars`](Self::poll_env_vars) to restrict the set.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WatchBuilder::new()
+    ///     .poll_env(Duration::from_secs(1))
+    /// ```
+    ///
+    /// [`ChangeTrigger::EnvVarChanged`]: super::ChangeTrigger::EnvVarChanged
+    #[must_use]
+    pub fn poll_env(mut self, interval: Duration) -> Self {
+        self.env_poll = Some(EnvPollConfig {
+            interval,
+            vars: None,
+        });
+        self
+    }
+
+    /// Poll only the given environment variables for changes.
+    ///
+    /// Like [`poll_env`](Self::poll_env), but ignores changes to any
+    /// variable not in `vars`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WatchBuilder::new()
+    ///     .poll_env_vars(["PORT", "HOST"], Duration::from_secs(1))
+    /// ```
+    #[must_use]
+    pub fn poll_env_vars<S: Into<String>>(
+        mut self,
+        vars: impl IntoIterator<Item = S>,
+        interval: Duration,
+    ) -> Self {
+        self.env_poll = Some(EnvPollConfig {
+            interval,
+            vars: Some(vars.into_iter().map(Into::into).collect()),
+        });
+        self
+    }
+
+    /// Reload the configuration when the process receives `signal`.
+    ///
+    /// The signal is funneled into the watcher's command channel, like
+    /// [`ConfigHandle::reload`], and the reload is reported as
+    /// [`ChangeTrigger::Signal`]. This works without any watched files, for
+    /// daemons that only reload on `SIGHUP`.
+    ///
+    /// A process-wide handler is installed when the watcher starts and is
+    /// never removed: after all watchers for the signal have stopped, the
+    /// signal is ignored instead of terminating the process.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use procenv::watch::SIGHUP;
+    ///
+    /// WatchBuilder::new()
+    ///     .reload_on_signal(SIGHUP)
+    ///     .build_sync(|| Config::from_config_with_sources())?;
+    /// ```
+    ///
+    /// [`ChangeTrigger::Signal`]: super::ChangeTrigger::Signal
+    #[cfg(unix)]
+    #[must_use]
+    pub fn reload_on_signal(mut self, signal: i32) -> Self {
+        self.signals.push(signal);
+        self
+    }
+
+    /// Poll a custom provider for changes.
+    ///
+    /// Every `interval`, the watcher compares the provider's
+    /// [`revision`](Provider::revision) with the previous one and reloads
+    /// when it changed, reported as [`ChangeTrigger::ProviderChanged`].
+    /// Providers that return `None` are never reloaded this way.
+    ///
+    /// Share the provider with the loader in the reload function via `Arc`,
+    /// which implements [`Provider`]. Async providers can be watched through
+    /// a [`BlockingAdapter`](crate::BlockingAdapter), which forwards their
+    /// revision.
+    ///
+    /// Revisions are read on a thread of their own, so a slow or hung
+    /// provider only delays its own changes, not file, environment or signal
+    /// reloads.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let consul = Arc::new(ConsulProvider::new("app/config"));
+    /// let loader_consul = consul.clone();
+    ///
+    /// WatchBuilder::new()
+    ///     .watch_provider(consul, Duration::from_secs(5))
+    ///     .build_sync(move || {
+    ///         let mut loader = ConfigLoader::new().with_provider(Box::new(loader_consul.clone()));
+    ///         let port = loader.get_parsed::<u16>("PORT")?.unwrap_or(8080);
+    ///         Ok((Config { port }, loader.into_sources()))
+    ///     })?;
+    /// ```
+    ///
+    /// [`ChangeTrigger::ProviderChanged`]: super::ChangeTrigger::ProviderChanged
+    #[must_use]
+    pub fn watch_provider(mut self, provider: Arc<dyn Provider>, interval: Duration) -> Self {
+        self.providers.push((provider, interval));
+        self
+    }
+
     /// Set the debounce duration.
     ///
     /// File system events are often emitted multiple times for a single save
@@ -132,6 +400,88 @@ impl<T: Clone + Send + Sync + 'static> WatchBuilder<T> {
         self
     }
 
+    /// Set the minimum interval between automatic reloads.
+    ///
+    /// Tools that rewrite a file in a loop can outpace the debounce. Events
+    /// arriving sooner than `interval` after the previous reload are held
+    /// back and coalesced into a single reload once the interval elapsed.
+    /// Manual and signal reloads are not delayed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WatchBuilder::new()
+    ///     .min_interval(Duration::from_secs(5))
+    /// ```
+    #[must_use]
+    pub const fn min_interval(mut self, interval: Duration) -> Self {
+        self.policy.min_interval = interval;
+        self
+    }
+
+    /// Back off exponentially after consecutive reload failures.
+    ///
+    /// After `n` consecutive [`WatchError::ReloadFailed`] errors, the next
+    /// automatic reload waits `initial * 2^(n-1)`, capped at `max`, so a
+    /// broken file that keeps changing does not cause an error storm. The
+    /// delay resets after a successful reload.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WatchBuilder::new()
+    ///     .backoff(Duration::from_millis(500), Duration::from_secs(30))
+    /// ```
+    #[must_use]
+    pub const fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.policy.backoff = Some((initial, max));
+        self
+    }
+
+    /// Set what happens when a watched file is deleted.
+    ///
+    /// By default the configuration is reloaded without the file
+    /// ([`DeletePolicy::Reload`]), so optional files fall back to defaults.
+    /// Whatever the policy, a recreated file is picked up again.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Tolerate tools that delete and rewrite the file
+    /// WatchBuilder::new()
+    ///     .watch_file("config.toml")
+    ///     .on_delete(DeletePolicy::WaitForRecreate { timeout: Duration::from_secs(5) })
+    /// ```
+    #[must_use]
+    pub const fn on_delete(mut self, policy: DeletePolicy) -> Self {
+        self.delete_policy = policy;
+        self
+    }
+
+    /// Set how file changes are detected.
+    ///
+    /// Native notifications never arrive on NFS, FUSE mounts and some
+    /// container overlay filesystems, so hot reload silently does nothing
+    /// there. [`WatchBackend::Poll`] scans the watched paths instead,
+    /// comparing modification times and content hashes.
+    ///
+    /// The default, [`WatchBackend::Auto`], only falls back to polling when
+    /// the native watcher fails to start. Use [`ConfigHandle::backend`] to
+    /// see which backend is in use.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WatchBuilder::new()
+    ///     .watch_file("/mnt/nfs/config.toml")
+    ///     .backend(WatchBackend::Poll { interval: Duration::from_secs(2) })
+    /// ```
+    #[must_use]
+    pub const fn backend(mut self, backend: WatchBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
     /// Register a callback for configuration changes.
     ///
     /// The callback is invoked after each successful reload with a
@@ -187,6 +537,60 @@ impl<T: Clone + Send + Sync + 'static> WatchBuilder<T> {
         self
     }
 
+    /// Report changed fields using the given field accessors.
+    ///
+    /// Each reload compares the value of every key in the old and new
+    /// configuration, and lists the differences in
+    /// [`ConfigChange::field_changes`] and
+    /// [`ConfigChange::changed_fields`]. The derive-generated `watch()` and
+    /// `watch_with()` set this up automatically.
+    ///
+    /// Values should be masked for secret fields, as the derive-generated
+    /// `get_str()` does; masked fields are then never reported as changed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WatchBuilder::new()
+    ///     .watch_file("config.toml")
+    ///     .diff_fields(Config::keys(), Config::get_str)
+    /// ```
+    #[must_use]
+    pub fn diff_fields(
+        mut self,
+        keys: &'static [&'static str],
+        get: fn(&T, &str) -> Option<String>,
+    ) -> Self {
+        self.diff = Some(FieldDiff { keys, get });
+        self
+    }
+
+    /// Append every applied and rejected reload to a JSON lines file.
+    ///
+    /// Each line is a JSON object with an `event` of `"applied"` or
+    /// `"rejected"`. Applied reloads include the serialized
+    /// [`ConfigChange`]: trigger, timestamp, changed files, changed fields
+    /// and sources. Rejected reloads include the error messages. Secret
+    /// values are never written, since field values are masked and errors
+    /// never contain secrets.
+    ///
+    /// Records are written on the callback thread, before the
+    /// [`on_change`](Self::on_change) and [`on_error`](Self::on_error)
+    /// callbacks run. Write failures are reported to `on_error`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// WatchBuilder::new()
+    ///     .watch_file("config.toml")
+    ///     .audit_log("/var/log/myapp/config-audit.jsonl")
+    /// ```
+    #[must_use]
+    pub fn audit_log(mut self, path: impl AsRef<Path>) -> Self {
+        self.audit_log = Some(path.as_ref().to_path_buf());
+        self
+    }
+
     /// Build and start the file watcher (synchronous).
     ///
     /// This spawns a background thread to watch for file changes. The returned
@@ -204,7 +608,11 @@ impl<T: Clone + Send + Sync + 'static> WatchBuilder<T> {
     /// # Errors
     ///
     /// Returns [`WatchError`] if:
-    /// - No files were specified to watch
+    /// - No files, directories, dotenv files, environment polling, signals
+    ///   or providers were specified
+    /// - A signal handler could not be installed
+    /// - A directory glob pattern is invalid
+    /// - The audit log could not be opened
     /// - Failed to initialize the file watcher
     /// - Initial configuration load failed
     ///
@@ -223,26 +631,130 @@ impl<T: Clone + Send + Sync + 'static> WatchBuilder<T> {
     where
         F: Fn() -> Result<(T, ConfigSources), Error> + Send + Sync + 'static,
     {
-        if self.files.is_empty() {
+        let (watcher_config, diff, callbacks) = self.into_parts()?;
+        let (initial_config, initial_sources) = initial_load(&reload_fn)?;
+
+        let watcher = ConfigWatcher::start(
+            initial_config,
+            initial_sources,
+            watcher_config,
+            reload_fn,
+            diff,
+        )?;
+
+        Ok(ConfigHandle::new(watcher, callbacks))
+    }
+
+    /// Build the watcher on the shared thread of a [`WatchRegistry`].
+    ///
+    /// Like [`build_sync`](Self::build_sync), but instead of spawning a
+    /// thread and notify instance for this configuration, it is served by
+    /// the registry. The registry's backend is used instead of
+    /// [`backend`](Self::backend).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WatchError::Stopped`] if the registry was stopped, and
+    /// otherwise the same errors as [`build_sync`](Self::build_sync).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let registry = WatchRegistry::new()?;
+    ///
+    /// let handle = WatchBuilder::new()
+    ///     .watch_file("config.toml")
+    ///     .build_in(&registry, || Config::from_config_with_sources())?;
+    /// ```
+    pub fn build_in<F>(
+        self,
+        registry: &WatchRegistry,
+        reload_fn: F,
+    ) -> Result<ConfigHandle<T>, WatchError>
+    where
+        F: Fn() -> Result<(T, ConfigSources), Error> + Send + Sync + 'static,
+    {
+        let (watcher_config, diff, callbacks) = self.into_parts()?;
+        let (initial_config, initial_sources) = initial_load(&reload_fn)?;
+
+        let watcher = ConfigWatcher::start_in(
+            registry,
+            initial_config,
+            initial_sources,
+            watcher_config,
+            reload_fn,
+            diff,
+        )?;
+
+        Ok(ConfigHandle::new(watcher, callbacks))
+    }
+
+    /// Validate the settings and split them into the watcher configuration,
+    /// field accessors and callbacks.
+    ///
+    /// Dotenv files are tracked from here on, so this must run before the
+    /// initial load applies them.
+    fn into_parts(self) -> Result<BuildParts<T>, WatchError> {
+        if self.files.is_empty()
+            && self.dirs.is_empty()
+            && self.dotenv_files.is_empty()
+            && self.env_poll.is_none()
+            && self.signals.is_empty()
+            && self.providers.is_empty()
+        {
             return Err(WatchError::init_failed("no files specified to watch", None));
         }
 
-        // Perform initial load
-        let (initial_config, initial_sources) = reload_fn()
-            .map_err(|e| WatchError::reload_failed("initial configuration load failed", vec![e]))?;
+        let dirs = self
+            .dirs
+            .into_iter()
+            .map(|(dir, pattern)| match glob::Pattern::new(&pattern) {
+                Ok(pattern) => Ok((dir, pattern)),
+                Err(e) => Err(WatchError::path_error(
+                    dir,
+                    format!("invalid glob pattern `{pattern}`: {e}"),
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Start tracking dotenv files before the initial load applies them
+        let dotenv = (!self.dotenv_files.is_empty()).then(|| DotenvTracker::new(self.dotenv_files));
+
+        // Read provider revisions before the initial load, so a change in
+        // between is not missed
+        let providers = ProviderPoller::new(self.providers);
 
         let watcher_config = WatcherConfig {
             debounce: self.debounce,
             paths: self.files,
+            dirs,
+            dotenv,
+            env_poll: self.env_poll,
+            backend: self.backend,
+            signals: self.signals,
+            policy: self.policy,
+            delete_policy: self.delete_policy,
+            providers,
         };
 
-        let watcher =
-            ConfigWatcher::start(initial_config, initial_sources, &watcher_config, reload_fn)?;
+        let callbacks = Callbacks {
+            on_change: self.on_change,
+            on_error: self.on_error,
+            audit: self.audit_log.as_deref().map(AuditLog::open).transpose()?,
+        };
 
-        Ok(ConfigHandle::new(watcher, self.on_change, self.on_error))
+        Ok((watcher_config, self.diff, callbacks))
     }
 }
 
+/// Perform the initial load.
+fn initial_load<T, F>(reload_fn: &F) -> Result<(T, ConfigSources), WatchError>
+where
+    F: Fn() -> Result<(T, ConfigSources), Error>,
+{
+    reload_fn().map_err(|e| WatchError::reload_failed("initial configuration load failed", vec![e]))
+}
+
 impl<T: Clone + Send + Sync + 'static> Default for WatchBuilder<T> {
     fn default() -> Self {
         Self::new()
@@ -262,9 +774,17 @@ mod tests {
     fn test_builder_defaults() {
         let builder: WatchBuilder<TestConfig> = WatchBuilder::new();
         assert!(builder.files.is_empty());
+        assert!(builder.dotenv_files.is_empty());
+        assert!(builder.env_poll.is_none());
         assert_eq!(builder.debounce, Duration::from_millis(100));
+        assert_eq!(builder.backend, WatchBackend::Auto);
+        assert_eq!(builder.policy.min_interval, Duration::ZERO);
+        assert!(builder.policy.backoff.is_none());
+        assert_eq!(builder.delete_policy, DeletePolicy::Reload);
         assert!(builder.on_change.is_none());
         assert!(builder.on_error.is_none());
+        assert!(builder.diff.is_none());
+        assert!(builder.audit_log.is_none());
     }
 
     #[test]
@@ -272,10 +792,19 @@ mod tests {
         let builder: WatchBuilder<TestConfig> = WatchBuilder::new()
             .watch_file("config.toml")
             .watch_file("local.toml")
-            .debounce(Duration::from_millis(200));
+            .debounce(Duration::from_millis(200))
+            .backend(WatchBackend::Native)
+            .min_interval(Duration::from_secs(1))
+            .backoff(Duration::from_millis(100), Duration::from_secs(5));
 
         assert_eq!(builder.files.len(), 2);
         assert_eq!(builder.debounce, Duration::from_millis(200));
+        assert_eq!(builder.backend, WatchBackend::Native);
+        assert_eq!(builder.policy.min_interval, Duration::from_secs(1));
+        assert_eq!(
+            builder.policy.backoff,
+            Some((Duration::from_millis(100), Duration::from_secs(5)))
+        );
     }
 
     #[test]
@@ -286,6 +815,48 @@ mod tests {
         assert_eq!(builder.files.len(), 3);
     }
 
+    #[test]
+    fn test_watch_dir() {
+        let builder: WatchBuilder<TestConfig> = WatchBuilder::new().watch_dir("conf.d", "*.toml");
+
+        assert_eq!(
+            builder.dirs,
+            vec![(PathBuf::from("conf.d"), "*.toml".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_invalid_dir_pattern_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let result: Result<ConfigHandle<TestConfig>, _> = WatchBuilder::new()
+            .watch_dir(dir.path(), "[*.toml")
+            .build_sync(|| Ok((TestConfig::default(), ConfigSources::default())));
+
+        assert!(result.unwrap_err().to_string().contains("invalid glob"));
+    }
+
+    #[test]
+    fn test_watch_dotenv_and_poll_env() {
+        let builder: WatchBuilder<TestConfig> = WatchBuilder::new()
+            .watch_dotenv(".env")
+            .watch_dotenv_files([".env.local"])
+            .poll_env_vars(["PORT"], Duration::from_secs(1));
+
+        assert_eq!(builder.dotenv_files.len(), 2);
+        let poll = builder.env_poll.unwrap();
+        assert_eq!(poll.interval, Duration::from_secs(1));
+        assert_eq!(poll.vars, Some(vec!["PORT".to_string()]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_reload_on_signal() {
+        let builder: WatchBuilder<TestConfig> =
+            WatchBuilder::new().reload_on_signal(crate::watch::SIGHUP);
+
+        assert_eq!(builder.signals, [crate::watch::SIGHUP]);
+    }
+
     #[test]
     fn test_build_without_files_fails() {
         let result: Result<ConfigHandle<TestConfig>, _> = WatchBuilder::new()
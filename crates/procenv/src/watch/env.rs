@@ -0,0 +1,306 @@
+//! Environment change detection for hot reload.
+//!
+//! This module contains the two sources of [`ChangeTrigger::EnvVarChanged`]:
+//!
+//! - [`DotenvTracker`] - Re-reads watched `.env` files and keeps their
+//!   values for the next reload
+//! - [`EnvPoller`] - Periodically diffs the process environment to catch
+//!   in-process `set_var` calls
+//!
+//! [`ChangeTrigger::EnvVarChanged`]: super::ChangeTrigger::EnvVarChanged
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Tracks the values contributed by a set of dotenv files.
+///
+/// Dotenv loading never overrides variables that are already set, so re-running
+/// the derive's dotenv loading after an edit has no effect. Instead, the
+/// tracker parses the files itself, and the watcher runs the reload function
+/// with the tracked values laid over the process environment (see
+/// [`crate::env`]). The process environment itself is never modified.
+///
+/// Only variables *owned* by the dotenv files are overlaid: a variable is owned
+/// if it was unset (or already held the dotenv value) when tracking started,
+/// or if it was first introduced by an edit. Real environment variables keep
+/// precedence over dotenv files, matching the initial load.
+#[derive(Debug)]
+pub struct DotenvTracker {
+    /// Dotenv files in load order (earlier files take precedence).
+    files: Vec<PathBuf>,
+    /// Effective values after layering all files.
+    values: HashMap<String, String>,
+    /// Variables the tracker provides values for.
+    owned: HashSet<String>,
+}
+
+impl DotenvTracker {
+    /// Start tracking the given dotenv files.
+    ///
+    /// Relative paths are looked up in the current directory and its
+    /// parents, as the derive's dotenv loading does; a file that isn't found
+    /// is tracked at its given path, in case it is created later.
+    ///
+    /// Must be called before the initial configuration load, so variables
+    /// that the load itself sets from dotenv are recognized as owned.
+    pub fn new(files: Vec<PathBuf>) -> Self {
+        let files: Vec<PathBuf> = files
+            .into_iter()
+            .map(|file| crate::dotenv::find(&file).unwrap_or(file))
+            .collect();
+        let values = read_layered(&files, &HashSet::new());
+        let owned = values
+            .iter()
+            .filter(|(key, value)| {
+                std::env::var_os(key).is_none_or(|current| current == value.as_str())
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        Self {
+            files,
+            values,
+            owned,
+        }
+    }
+
+    /// The dotenv files being tracked.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Re-read the dotenv files.
+    ///
+    /// Returns the sorted names of variables whose effective value changed.
+    /// Variables shadowed by the real environment are not reported.
+    pub fn refresh(&mut self) -> Vec<String> {
+        let new_values = read_layered(&self.files, &self.owned);
+
+        let keys: BTreeSet<&String> = self.values.keys().chain(new_values.keys()).collect();
+        let mut changed = Vec::new();
+
+        for key in keys {
+            let old = self.values.get(key);
+            let new = new_values.get(key);
+            if old == new {
+                continue;
+            }
+
+            let owned =
+                self.owned.contains(key) || (old.is_none() && std::env::var_os(key).is_none());
+            if !owned {
+                continue;
+            }
+
+            self.owned.insert(key.clone());
+            changed.push(key.clone());
+        }
+
+        self.values = new_values;
+        changed
+    }
+
+    /// The owned variables as an overlay for [`crate::env`]: their current
+    /// values, or `None` for variables removed from the files.
+    pub fn overlay(&self) -> HashMap<String, Option<String>> {
+        self.owned
+            .iter()
+            .map(|key| (key.clone(), self.values.get(key).cloned()))
+            .collect()
+    }
+}
+
+/// Read dotenv files with load precedence (the first file to define a
+/// key wins). Missing or unreadable files contribute no values, and
+/// malformed lines are skipped.
+///
+/// `${VAR}` references to `owned` variables resolve to the values read from
+/// the files, never to the process environment: the initial load wrote the
+/// old values there, so `B=${A}` would otherwise keep A's value from before
+/// an edit.
+fn read_layered(files: &[PathBuf], owned: &HashSet<String>) -> HashMap<String, String> {
+    let mut values: HashMap<String, String> = HashMap::new();
+
+    for file in files {
+        let lookup = |name: &str| {
+            if owned.contains(name) {
+                // Unknown names fall back to earlier entries of the same file
+                values.get(name).cloned()
+            } else {
+                std::env::var(name)
+                    .ok()
+                    .or_else(|| values.get(name).cloned())
+            }
+        };
+        let Some(entries) = crate::dotenv::read_lenient(file, &lookup) else {
+            continue;
+        };
+
+        let file_values: HashMap<String, String> = entries
+            .into_iter()
+            .map(|entry| (entry.key, entry.value))
+            .collect();
+        for (key, value) in file_values {
+            values.entry(key).or_insert(value);
+        }
+    }
+
+    values
+}
+
+/// Configuration for process environment polling.
+#[derive(Debug, Clone)]
+pub struct EnvPollConfig {
+    /// How often to compare the environment.
+    pub interval: Duration,
+    /// Variables to compare, or `None` for the whole environment.
+    pub vars: Option<Vec<String>>,
+}
+
+/// Detects changes to the process environment by periodic snapshots.
+#[derive(Debug)]
+pub struct EnvPoller {
+    /// Variables to compare, or `None` for the whole environment.
+    vars: Option<Vec<String>>,
+    /// Last observed values.
+    snapshot: HashMap<OsString, OsString>,
+}
+
+impl EnvPoller {
+    /// Create a poller with an initial snapshot of the environment.
+    pub fn new(vars: Option<Vec<String>>) -> Self {
+        let mut poller = Self {
+            vars,
+            snapshot: HashMap::new(),
+        };
+        poller.snapshot = poller.capture();
+        poller
+    }
+
+    /// Compare the environment against the last snapshot.
+    ///
+    /// Returns the sorted names of variables that were added, removed or
+    /// changed since the previous call.
+    pub fn poll(&mut self) -> Vec<String> {
+        let current = self.capture();
+
+        let changed: BTreeSet<String> = current
+            .iter()
+            .filter(|(key, value)| self.snapshot.get(*key) != Some(*value))
+            .map(|(key, _)| key)
+            .chain(
+                self.snapshot
+                    .keys()
+                    .filter(|key| !current.contains_key(*key)),
+            )
+            .map(|key| key.to_string_lossy().into_owned())
+            .collect();
+
+        self.snapshot = current;
+        changed.into_iter().collect()
+    }
+
+    fn capture(&self) -> HashMap<OsString, OsString> {
+        self.vars.as_ref().map_or_else(
+            || std::env::vars_os().collect(),
+            |vars| {
+                vars.iter()
+                    .filter_map(|var| std::env::var_os(var).map(|value| (var.into(), value)))
+                    .collect()
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_dotenv_tracker_reports_changed_vars() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(".env");
+        fs::write(
+            &path,
+            "PROCENV_TRACKER_A=1\nPROCENV_TRACKER_B=2\nPROCENV_TRACKER_C=3\n",
+        )
+        .unwrap();
+
+        let mut tracker = DotenvTracker::new(vec![path.clone()]);
+
+        fs::write(
+            &path,
+            "PROCENV_TRACKER_A=1\nPROCENV_TRACKER_B=20\nPROCENV_TRACKER_D=4\n",
+        )
+        .unwrap();
+
+        let changed = tracker.refresh();
+        assert_eq!(
+            changed,
+            [
+                "PROCENV_TRACKER_B",
+                "PROCENV_TRACKER_C",
+                "PROCENV_TRACKER_D"
+            ]
+        );
+
+        let overlay = tracker.overlay();
+        assert_eq!(overlay["PROCENV_TRACKER_B"].as_deref(), Some("20"));
+        assert_eq!(overlay["PROCENV_TRACKER_C"], None);
+        assert_eq!(overlay["PROCENV_TRACKER_D"].as_deref(), Some("4"));
+
+        // The process environment is left alone
+        assert!(std::env::var("PROCENV_TRACKER_B").is_err());
+        assert!(std::env::var("PROCENV_TRACKER_D").is_err());
+
+        // Nothing changed since the last refresh
+        assert!(tracker.refresh().is_empty());
+    }
+
+    #[test]
+    fn test_dotenv_tracker_respects_real_env() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(".env");
+        fs::write(&path, "PROCENV_TRACKER_REAL=from_file\n").unwrap();
+
+        unsafe { std::env::set_var("PROCENV_TRACKER_REAL", "from_env") };
+
+        let mut tracker = DotenvTracker::new(vec![path.clone()]);
+        fs::write(&path, "PROCENV_TRACKER_REAL=edited\n").unwrap();
+
+        assert!(tracker.refresh().is_empty());
+        assert!(!tracker.overlay().contains_key("PROCENV_TRACKER_REAL"));
+
+        unsafe { std::env::remove_var("PROCENV_TRACKER_REAL") };
+    }
+
+    #[test]
+    fn test_dotenv_tracker_first_file_wins() {
+        let dir = tempdir().unwrap();
+        let base = dir.path().join(".env");
+        let local = dir.path().join(".env.local");
+        fs::write(&base, "PROCENV_TRACKER_LAYER=base\n").unwrap();
+        fs::write(&local, "PROCENV_TRACKER_LAYER=local\n").unwrap();
+
+        let tracker = DotenvTracker::new(vec![base, local]);
+        assert_eq!(tracker.values["PROCENV_TRACKER_LAYER"], "base");
+    }
+
+    #[test]
+    fn test_env_poller_detects_changes() {
+        let var = "PROCENV_POLLER_TEST".to_string();
+        let mut poller = EnvPoller::new(Some(vec![var.clone()]));
+        assert!(poller.poll().is_empty());
+
+        unsafe { std::env::set_var(&var, "1") };
+        assert_eq!(poller.poll(), [var.clone()]);
+        assert!(poller.poll().is_empty());
+
+        unsafe { std::env::remove_var(&var) };
+        assert_eq!(poller.poll(), [var]);
+    }
+}
@@ -9,8 +9,8 @@ use std::thread::{self, JoinHandle};
 use crossbeam_channel::Receiver;
 
 use super::WatchedConfig;
-use super::builder::{ChangeCallback, ErrorCallback};
-use super::types::{ConfigChange, WatchError};
+use super::builder::Callbacks;
+use super::types::{ConfigChange, ReloadStats, WatchBackend, WatchError};
 use super::watcher::{ConfigWatcher, WatchCommand};
 use crate::ConfigSources;
 
@@ -55,15 +55,13 @@ pub struct ConfigHandle<T: Clone + Send + Sync + 'static> {
 
 impl<T: Clone + Send + Sync + 'static> ConfigHandle<T> {
     /// Create a new config handle.
-    pub(crate) fn new(
-        watcher: ConfigWatcher<T>,
-        on_change: Option<ChangeCallback<T>>,
-        on_error: Option<ErrorCallback>,
-    ) -> Self {
+    pub(crate) fn new(watcher: ConfigWatcher<T>, callbacks: Callbacks<T>) -> Self {
         let watcher = Arc::new(watcher);
 
         // Spawn callback processor thread if callbacks are registered
-        let callback_thread = if on_change.is_some() || on_error.is_some() {
+        let callback_thread = if callbacks.is_empty() {
+            None
+        } else {
             let change_rx = watcher.change_receiver().clone();
             let error_rx = watcher.error_receiver().clone();
             let watcher_clone = watcher.clone();
@@ -71,19 +69,11 @@ impl<T: Clone + Send + Sync + 'static> ConfigHandle<T> {
             let handle = thread::Builder::new()
                 .name("procenv-callbacks".to_string())
                 .spawn(move || {
-                    callback_loop(
-                        &change_rx,
-                        &error_rx,
-                        on_change.as_ref(),
-                        on_error.as_ref(),
-                        &watcher_clone,
-                    );
+                    callback_loop(&change_rx, &error_rx, callbacks, &watcher_clone);
                 })
                 .ok();
 
             handle.map(Arc::new)
-        } else {
-            None
         };
 
         Self {
@@ -216,6 +206,41 @@ impl<T: Clone + Send + Sync + 'static> ConfigHandle<T> {
         self.watcher.is_running()
     }
 
+    /// Get a snapshot of the reload statistics.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let stats = handle.stats();
+    /// println!("{} of {} reloads failed", stats.failures, stats.attempts);
+    /// ```
+    #[must_use]
+    pub fn stats(&self) -> ReloadStats {
+        self.watcher.stats()
+    }
+
+    /// Get the backend detecting file changes.
+    ///
+    /// Returns [`WatchBackend::Native`] or [`WatchBackend::Poll`], never
+    /// [`WatchBackend::Auto`], so a fallback to polling can be detected.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if let WatchBackend::Poll { interval } = handle.backend() {
+    ///     eprintln!("native file events unavailable, polling every {interval:?}");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn backend(&self) -> WatchBackend {
+        self.watcher.backend()
+    }
+
+    /// Get the container holding the current configuration.
+    pub(crate) fn watched(&self) -> &Arc<WatchedConfig<T>> {
+        self.watcher.config()
+    }
+
     /// Get a clone of the command sender for advanced use cases.
     ///
     /// This allows sending commands to the watcher from other contexts.
@@ -240,16 +265,18 @@ impl<T: Clone + Send + Sync + 'static> std::fmt::Debug for ConfigHandle<T> {
         f.debug_struct("ConfigHandle")
             .field("epoch", &self.epoch())
             .field("running", &self.is_running())
+            .field("backend", &self.backend())
             .finish()
     }
 }
 
 /// Callback processing loop.
+///
+/// Audit records are written before the callbacks run.
 fn callback_loop<T: Clone + Send + Sync + 'static>(
     change_rx: &Receiver<ConfigChange<T>>,
     error_rx: &Receiver<WatchError>,
-    on_change: Option<&ChangeCallback<T>>,
-    on_error: Option<&ErrorCallback>,
+    mut callbacks: Callbacks<T>,
     watcher: &Arc<ConfigWatcher<T>>,
 ) {
     use crossbeam_channel::select;
@@ -257,17 +284,29 @@ fn callback_loop<T: Clone + Send + Sync + 'static>(
     while watcher.is_running() {
         select! {
             recv(change_rx) -> change => {
-                if let Ok(change) = change
-                    && let Some(cb) = on_change
-                {
-                    cb(change);
+                if let Ok(change) = change {
+                    if let Some(audit) = &mut callbacks.audit
+                        && let Err(e) = audit.record_change(&change)
+                        && let Some(cb) = &callbacks.on_error
+                    {
+                        cb(e);
+                    }
+                    if let Some(cb) = &callbacks.on_change {
+                        cb(change);
+                    }
                 }
             }
             recv(error_rx) -> error => {
-                if let Ok(error) = error
-                    && let Some(cb) = on_error
-                {
-                    cb(error);
+                if let Ok(error) = error {
+                    if let Some(audit) = &mut callbacks.audit
+                        && let Err(e) = audit.record_error(&error)
+                        && let Some(cb) = &callbacks.on_error
+                    {
+                        cb(e);
+                    }
+                    if let Some(cb) = &callbacks.on_error {
+                        cb(error);
+                    }
                 }
             }
             default(std::time::Duration::from_millis(100)) => {
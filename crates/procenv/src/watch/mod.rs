@@ -7,10 +7,23 @@
 //! # Features
 //!
 //! - **File watching** - Monitor config files for changes using the `notify` crate
-//! - **Debouncing** - Configurable delay to handle rapid file saves
+//! - **Directory watching** - Reload when `conf.d`-style fragments are added or removed
+//! - **Environment watching** - Re-read `.env` files and poll the process environment
+//! - **Polling fallback** - Poll files where native events never arrive (NFS, FUSE)
+//! - **Symlink swaps** - Follow Kubernetes `..data` swaps to the new target
+//! - **Signals** - Reload on `SIGHUP`, even without watched files
+//! - **Providers** - Poll custom providers for revision changes
+//! - **Debouncing** - Configurable delay to handle rapid file saves, coalescing
+//!   all files changed in the window into one reload
+//! - **Rate limiting** - Minimum reload interval and backoff after failures
 //! - **Error resilience** - Keep last valid config on reload errors
+//! - **Delete policies** - Keep, reload, fail or wait when a file is deleted
 //! - **Callbacks** - Register handlers for changes and errors
+//! - **Audit log** - Append every applied and rejected reload as JSON lines,
+//!   with secrets masked
 //! - **Thread-safe** - Concurrent access to configuration
+//! - **Shared watcher** - Serve several config structs from one thread with
+//!   [`WatchRegistry`]
 //!
 //! # Quick Start
 //!
@@ -104,9 +117,15 @@
 //! }
 //! ```
 
+mod audit;
 mod builder;
 mod container;
+mod env;
 mod handle;
+mod provider;
+mod registry;
+#[cfg(unix)]
+mod signal;
 mod types;
 mod watcher;
 
@@ -114,9 +133,14 @@ mod watcher;
 pub use builder::WatchBuilder;
 pub use container::WatchedConfig;
 pub use handle::ConfigHandle;
-pub use types::{ChangeTrigger, ChangedField, ConfigChange, WatchError};
+pub use registry::WatchRegistry;
+pub use types::{
+    ChangeTrigger, ChangedField, ConfigChange, DeletePolicy, ReloadStats, WatchBackend, WatchError,
+};
 
 // Re-export for convenience
+#[cfg(unix)]
+pub use signal::{SIGHUP, SIGUSR1, SIGUSR2};
 pub use watcher::WatchCommand;
 
 #[cfg(test)]
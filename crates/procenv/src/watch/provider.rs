@@ -0,0 +1,200 @@
+//! Change detection for custom providers.
+//!
+//! Providers have no file to watch, so the [`ProviderPoller`] polls their
+//! [`Provider::revision`] instead and reports changes as
+//! [`ChangeTrigger::ProviderChanged`]. Polling runs on a thread of its own,
+//! since a revision may come from a remote store.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender, bounded, never, unbounded};
+
+use super::types::{ChangeTrigger, WatchError};
+use crate::provider::Provider;
+
+/// A provider whose revision is polled.
+struct WatchedProvider {
+    provider: Arc<dyn Provider>,
+    /// How often to poll the revision.
+    interval: Duration,
+    /// Last known revision, if any.
+    revision: Option<String>,
+    /// When to poll next.
+    next_poll: Instant,
+}
+
+/// Polls provider revisions on their intervals.
+#[derive(Default)]
+pub struct ProviderPoller {
+    providers: Vec<WatchedProvider>,
+}
+
+impl ProviderPoller {
+    /// Start polling the given providers.
+    ///
+    /// Reads the current revisions, so this should run before the initial
+    /// load: a change in between then causes an extra reload rather than a
+    /// missed one.
+    pub fn new(providers: Vec<(Arc<dyn Provider>, Duration)>) -> Self {
+        let now = Instant::now();
+
+        Self {
+            providers: providers
+                .into_iter()
+                .map(|(provider, interval)| WatchedProvider {
+                    revision: provider.revision(),
+                    provider,
+                    interval,
+                    next_poll: now + interval,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if no providers are polled.
+    pub const fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+    }
+
+    /// When the next provider is due, if any.
+    pub fn next_poll(&self) -> Option<Instant> {
+        self.providers.iter().map(|p| p.next_poll).min()
+    }
+
+    /// Poll the providers that are due, returning a trigger for each
+    /// provider whose revision changed.
+    ///
+    /// An unknown (`None`) revision keeps the last known one.
+    pub fn poll(&mut self) -> Vec<ChangeTrigger> {
+        let now = Instant::now();
+        let mut triggers = Vec::new();
+
+        for watched in &mut self.providers {
+            if watched.next_poll > now {
+                continue;
+            }
+            watched.next_poll = now + watched.interval;
+
+            let Some(revision) = watched.provider.revision() else {
+                continue;
+            };
+            if watched.revision.as_ref() != Some(&revision) {
+                if watched.revision.is_some() {
+                    triggers.push(ChangeTrigger::ProviderChanged {
+                        provider: watched.provider.name().to_string(),
+                    });
+                }
+                watched.revision = Some(revision);
+            }
+        }
+
+        triggers
+    }
+
+    /// Poll the providers on a thread of their own, so a slow or hung
+    /// [`Provider::revision`] delays only their own changes, not the file,
+    /// environment and signal reloads of the watcher thread.
+    ///
+    /// The thread stops once the returned [`ProviderChanges`] is dropped,
+    /// after the revision it is reading, if any.
+    pub fn spawn(mut self) -> Result<ProviderChanges, WatchError> {
+        if self.is_empty() {
+            return Ok(ProviderChanges {
+                triggers: never(),
+                _stop: None,
+            });
+        }
+
+        let (trigger_tx, trigger_rx) = unbounded();
+        let (stop_tx, stop_rx) = bounded::<()>(0);
+
+        thread::Builder::new()
+            .name("procenv-providers".to_string())
+            .spawn(move || {
+                while let Some(due) = self.next_poll() {
+                    let timeout = due.saturating_duration_since(Instant::now());
+                    if stop_rx.recv_timeout(timeout) != Err(RecvTimeoutError::Timeout) {
+                        return;
+                    }
+
+                    for trigger in self.poll() {
+                        if trigger_tx.send(trigger).is_err() {
+                            return;
+                        }
+                    }
+                }
+            })
+            .map_err(|e| {
+                WatchError::init_failed(format!("failed to spawn provider thread: {e}"), None)
+            })?;
+
+        Ok(ProviderChanges {
+            triggers: trigger_rx,
+            _stop: Some(stop_tx),
+        })
+    }
+}
+
+/// Changes detected by a [`ProviderPoller`] thread.
+pub struct ProviderChanges {
+    /// Triggers for changed revisions.
+    pub triggers: Receiver<ChangeTrigger>,
+    /// Stops the thread when dropped.
+    _stop: Option<Sender<()>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{ProviderResult, ProviderValue};
+    use parking_lot::Mutex;
+
+    struct Revisioned(Mutex<Option<&'static str>>);
+
+    impl Provider for Revisioned {
+        fn name(&self) -> &'static str {
+            "revisioned"
+        }
+
+        fn get(&self, _key: &str) -> ProviderResult<ProviderValue> {
+            Ok(None)
+        }
+
+        fn revision(&self) -> Option<String> {
+            self.0.lock().map(ToString::to_string)
+        }
+    }
+
+    #[test]
+    fn test_poll_reports_changed_revisions() {
+        let provider = Arc::new(Revisioned(Mutex::new(Some("1"))));
+        let mut poller = ProviderPoller::new(vec![(provider.clone(), Duration::ZERO)]);
+        assert!(poller.poll().is_empty());
+
+        *provider.0.lock() = Some("2");
+        assert_eq!(
+            poller.poll(),
+            [ChangeTrigger::ProviderChanged {
+                provider: "revisioned".to_string()
+            }]
+        );
+
+        // Unknown revisions are ignored, and the last known one is kept
+        *provider.0.lock() = None;
+        assert!(poller.poll().is_empty());
+        *provider.0.lock() = Some("2");
+        assert!(poller.poll().is_empty());
+    }
+
+    #[test]
+    fn test_poll_waits_for_interval() {
+        let provider = Arc::new(Revisioned(Mutex::new(Some("1"))));
+        let mut poller = ProviderPoller::new(vec![(provider.clone(), Duration::from_secs(60))]);
+
+        *provider.0.lock() = Some("2");
+        assert!(poller.poll().is_empty());
+        assert!(poller.next_poll().unwrap() > Instant::now());
+    }
+}
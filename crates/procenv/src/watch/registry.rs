@@ -0,0 +1,226 @@
+//! Shared watcher for multiple configurations.
+//!
+//! The [`WatchRegistry`] owns a single notify watcher and thread that serve
+//! any number of [`ConfigHandle`]s, possibly of different types.
+//!
+//! [`ConfigHandle`]: super::ConfigHandle
+
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use crossbeam_channel::{Sender, bounded};
+use notify::Event;
+use parking_lot::Mutex;
+
+use super::types::{WatchBackend, WatchError};
+use super::watcher::{Control, Entry, SharedWatcher, spawn_loop, start_backend};
+
+/// A single watcher thread shared by several configurations.
+///
+/// Each [`WatchBuilder::build_sync`] call spawns its own watcher thread and
+/// notify instance. Binaries that load several config structs can instead
+/// register them all with one registry via [`WatchBuilder::build_in`]:
+/// file events are dispatched to every handle whose files they match.
+///
+/// # Ordering
+///
+/// Configurations are served in registration order. When one event (e.g. a
+/// shared `.env` file) triggers several reloads, they run one after another
+/// in that order.
+///
+/// # Shutdown
+///
+/// [`stop`](Self::stop) stops all registered handles at once. Dropping the
+/// registry stops them too, so keep it alive as long as its handles are in
+/// use. Stopping a single handle only unregisters that handle.
+///
+/// Callbacks still run on a thread per handle, so a slow callback does not
+/// delay reloads of the other configurations.
+///
+/// # Example
+///
+/// ```ignore
+/// let registry = WatchRegistry::new()?;
+///
+/// let server = WatchBuilder::new()
+///     .watch_file("server.toml")
+///     .build_in(&registry, || ServerConfig::from_config_with_sources())?;
+///
+/// let database = WatchBuilder::new()
+///     .watch_file("database.toml")
+///     .build_in(&registry, || DatabaseConfig::from_config_with_sources())?;
+///
+/// // ... later, stop watching everything
+/// registry.stop();
+/// ```
+///
+/// [`WatchBuilder::build_sync`]: super::WatchBuilder::build_sync
+/// [`WatchBuilder::build_in`]: super::WatchBuilder::build_in
+pub struct WatchRegistry {
+    /// The notify watcher shared by all entries.
+    watcher: Arc<Mutex<SharedWatcher>>,
+
+    /// Sender for entries and shutdown.
+    control_tx: Sender<Control>,
+
+    /// Backend in use (never [`WatchBackend::Auto`]).
+    backend: WatchBackend,
+
+    /// Watcher thread handle.
+    thread_handle: Option<JoinHandle<()>>,
+}
+
+impl WatchRegistry {
+    /// Create a registry using the native backend, falling back to polling.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WatchError::InitFailed`] if no file watcher could be
+    /// created or the thread could not be spawned.
+    pub fn new() -> Result<Self, WatchError> {
+        Self::with_backend(WatchBackend::Auto)
+    }
+
+    /// Create a registry using the given backend.
+    ///
+    /// The backend applies to every registered configuration; the backend
+    /// set on their builders is ignored. [`WatchBackend::Auto`] only falls
+    /// back to polling if the native watcher cannot be created.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WatchError::InitFailed`] if the file watcher could not be
+    /// created or the thread could not be spawned.
+    pub fn with_backend(backend: WatchBackend) -> Result<Self, WatchError> {
+        let (notify_tx, notify_rx) = bounded::<notify::Result<Event>>(100);
+        let (watcher, backend, ()) = start_backend(backend, &notify_tx, &|_| Ok(()))?;
+        let watcher = Arc::new(Mutex::new(watcher));
+
+        let (control_tx, control_rx) = bounded::<Control>(16);
+        let thread_handle = spawn_loop(
+            "procenv-registry",
+            Vec::new(),
+            notify_rx,
+            Some(control_rx),
+            watcher.clone(),
+        )?;
+
+        Ok(Self {
+            watcher,
+            control_tx,
+            backend,
+            thread_handle: Some(thread_handle),
+        })
+    }
+
+    /// Get the backend detecting file changes.
+    ///
+    /// Returns [`WatchBackend::Native`] or [`WatchBackend::Poll`], never
+    /// [`WatchBackend::Auto`].
+    #[must_use]
+    pub const fn backend(&self) -> WatchBackend {
+        self.backend
+    }
+
+    /// Stop the registry and all registered handles.
+    ///
+    /// The handles keep their last configuration, and registering further
+    /// configurations fails with [`WatchError::Stopped`].
+    pub fn stop(&self) {
+        let _ = self.control_tx.send(Control::Stop);
+    }
+
+    /// Check if the registry thread is still running.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.thread_handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// Get the shared notify watcher.
+    pub(crate) const fn watcher(&self) -> &Arc<Mutex<SharedWatcher>> {
+        &self.watcher
+    }
+
+    /// Hand an entry to the registry thread.
+    pub(crate) fn add(&self, entry: Box<dyn Entry>) -> Result<(), WatchError> {
+        self.control_tx
+            .send(Control::Add(entry))
+            .map_err(|_| WatchError::Stopped)
+    }
+}
+
+impl Drop for WatchRegistry {
+    fn drop(&mut self) {
+        self.stop();
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl std::fmt::Debug for WatchRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WatchRegistry")
+            .field("running", &self.is_running())
+            .field("backend", &self.backend)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConfigSources;
+    use crate::watch::WatchBuilder;
+    use std::time::Duration;
+
+    #[test]
+    fn test_register_after_stop_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = WatchRegistry::new().unwrap();
+        assert!(registry.is_running());
+        assert_ne!(registry.backend(), WatchBackend::Auto);
+
+        registry.stop();
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!registry.is_running());
+
+        let result = WatchBuilder::<u16>::new()
+            .watch_file(dir.path().join("config.toml"))
+            .build_in(&registry, || Ok((8080, ConfigSources::default())));
+        assert!(matches!(result, Err(WatchError::Stopped)));
+    }
+
+    #[test]
+    fn test_stopped_handle_drops_its_watches() {
+        let dir = tempfile::tempdir().unwrap();
+        let shared = dir.path().join("shared.toml");
+        let own = dir.path().join("own.toml");
+        std::fs::write(&shared, "port = 8080").unwrap();
+        std::fs::write(&own, "port = 8080").unwrap();
+
+        let registry = WatchRegistry::new().unwrap();
+        let first = WatchBuilder::<u16>::new()
+            .watch_file(&shared)
+            .build_in(&registry, || Ok((8080, ConfigSources::default())))
+            .unwrap();
+        let second = WatchBuilder::<u16>::new()
+            .watch_files([&shared, &own])
+            .build_in(&registry, || Ok((8080, ConfigSources::default())))
+            .unwrap();
+        assert_eq!(registry.watcher().lock().count(&shared), 2);
+
+        // The other handle keeps its watch on the shared file
+        first.stop();
+        std::thread::sleep(Duration::from_millis(300));
+        assert_eq!(registry.watcher().lock().count(&shared), 1);
+        assert_eq!(registry.watcher().lock().count(&own), 1);
+
+        second.stop();
+        std::thread::sleep(Duration::from_millis(300));
+        assert_eq!(registry.watcher().lock().count(&shared), 0);
+        assert_eq!(registry.watcher().lock().count(&own), 0);
+    }
+}
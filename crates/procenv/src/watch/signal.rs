@@ -0,0 +1,251 @@
+//! Unix signal integration for hot reload.
+//!
+//! Signal handlers may only do async-signal-safe work, so the handler writes
+//! the signal number to a self-pipe. A dispatcher thread reads the pipe and
+//! forwards [`WatchCommand::Signal`] to every watcher registered for that
+//! signal, through the same channel used by manual reloads.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::fd::FromRawFd;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::thread;
+
+use crossbeam_channel::{Sender, TrySendError};
+use parking_lot::Mutex;
+
+use super::types::WatchError;
+use super::watcher::WatchCommand;
+
+/// Hangup, the traditional "reload your configuration" signal.
+pub const SIGHUP: i32 = libc::SIGHUP;
+
+/// User-defined signal 1.
+pub const SIGUSR1: i32 = libc::SIGUSR1;
+
+/// User-defined signal 2.
+pub const SIGUSR2: i32 = libc::SIGUSR2;
+
+/// Write end of the self-pipe, or -1 before the dispatcher is started.
+static PIPE_WRITE: AtomicI32 = AtomicI32::new(-1);
+
+/// Command senders registered per signal.
+static SUBSCRIBERS: Mutex<Vec<(i32, Sender<WatchCommand>)>> = Mutex::new(Vec::new());
+
+/// Forward `signal` to `sender` as [`WatchCommand::Signal`].
+///
+/// The first registration for a signal installs a process-wide handler,
+/// which stays installed for the life of the process. Once every watcher
+/// registered for the signal has stopped, the signal is ignored instead of
+/// taking its default action.
+pub fn register(signal: i32, sender: Sender<WatchCommand>) -> Result<(), WatchError> {
+    let mut subscribers = SUBSCRIBERS.lock();
+
+    start_dispatcher()?;
+    if !subscribers.iter().any(|(s, _)| *s == signal) {
+        install_handler(signal)?;
+    }
+
+    subscribers.push((signal, sender));
+    drop(subscribers);
+    Ok(())
+}
+
+/// Create the self-pipe and spawn the dispatcher thread, once.
+///
+/// Called with the subscriber lock held, which serializes initialization.
+fn start_dispatcher() -> Result<(), WatchError> {
+    if PIPE_WRITE.load(Ordering::Acquire) >= 0 {
+        return Ok(());
+    }
+
+    let mut fds = [0; 2];
+    // SAFETY: `fds` has room for the two descriptors `pipe` writes.
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(WatchError::init_failed(
+            format!(
+                "failed to create signal pipe: {}",
+                io::Error::last_os_error()
+            ),
+            None,
+        ));
+    }
+    let [read_fd, write_fd] = fds;
+
+    // SAFETY: both descriptors were just created by `pipe` and are owned here.
+    // A full pipe must never block the signal handler.
+    unsafe {
+        libc::fcntl(read_fd, libc::F_SETFD, libc::FD_CLOEXEC);
+        libc::fcntl(write_fd, libc::F_SETFD, libc::FD_CLOEXEC);
+        libc::fcntl(write_fd, libc::F_SETFL, libc::O_NONBLOCK);
+    }
+
+    // SAFETY: the read end is owned exclusively by the dispatcher thread.
+    let pipe = unsafe { File::from_raw_fd(read_fd) };
+    thread::Builder::new()
+        .name("procenv-signals".to_string())
+        .spawn(move || dispatch_loop(pipe))
+        .map_err(|e| {
+            WatchError::init_failed(format!("failed to spawn signal thread: {e}"), None)
+        })?;
+
+    PIPE_WRITE.store(write_fd, Ordering::Release);
+    Ok(())
+}
+
+/// Install [`handle_signal`] for `signal`.
+fn install_handler(signal: i32) -> Result<(), WatchError> {
+    // SAFETY: a zeroed `sigaction` is a valid empty action, and the handler
+    // only performs async-signal-safe operations.
+    let result = unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = handle_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESTART;
+        libc::sigemptyset(&raw mut action.sa_mask);
+        libc::sigaction(signal, &raw const action, std::ptr::null_mut())
+    };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(WatchError::init_failed(
+            format!(
+                "failed to install handler for signal {signal}: {}",
+                io::Error::last_os_error()
+            ),
+            None,
+        ))
+    }
+}
+
+/// Signal handler writing the signal number to the self-pipe.
+///
+/// `errno` is saved on entry and restored before returning, so a failed
+/// `write` cannot clobber the value seen by the interrupted code.
+extern "C" fn handle_signal(signal: libc::c_int) {
+    let fd = PIPE_WRITE.load(Ordering::Relaxed);
+    if fd < 0 {
+        return;
+    }
+
+    // Signal numbers are small positive integers
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let byte = signal as u8;
+
+    // SAFETY: `errno` is thread-local, so its location is valid for the
+    // current thread. `write` is async-signal-safe and the write end stays
+    // open for the life of the process. If the pipe is full, a reload is
+    // already pending and the signal can be dropped.
+    unsafe {
+        let errno = errno_location();
+        let saved = *errno;
+        libc::write(fd, (&raw const byte).cast(), 1);
+        *errno = saved;
+    }
+}
+
+/// Location of the calling thread's `errno`.
+#[cfg(any(target_os = "linux", target_os = "emscripten", target_os = "redox"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    // SAFETY: always returns a valid pointer for the current thread.
+    unsafe { libc::__errno_location() }
+}
+
+/// Location of the calling thread's `errno`.
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "tvos",
+    target_os = "watchos",
+    target_os = "freebsd",
+    target_os = "dragonfly"
+))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    // SAFETY: always returns a valid pointer for the current thread.
+    unsafe { libc::__error() }
+}
+
+/// Location of the calling thread's `errno`.
+#[cfg(any(target_os = "android", target_os = "netbsd", target_os = "openbsd"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    // SAFETY: always returns a valid pointer for the current thread.
+    unsafe { libc::__errno() }
+}
+
+/// Location of the calling thread's `errno`.
+#[cfg(any(target_os = "solaris", target_os = "illumos"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    // SAFETY: always returns a valid pointer for the current thread.
+    unsafe { libc::___errno() }
+}
+
+/// Read signal numbers from the pipe and forward them to subscribers.
+fn dispatch_loop(mut pipe: File) {
+    let mut buf = [0u8; 64];
+
+    loop {
+        match pipe.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                for &signal in &buf[..n] {
+                    dispatch(i32::from(signal));
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(_) => break,
+        }
+    }
+}
+
+/// Send a signal command to all subscribers, dropping stopped watchers.
+fn dispatch(signal: i32) {
+    SUBSCRIBERS.lock().retain(|(s, tx)| {
+        *s != signal
+            || !matches!(
+                tx.try_send(WatchCommand::Signal(signal)),
+                Err(TrySendError::Disconnected(_))
+            )
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_signal_forwarded_as_command() {
+        let (tx, rx) = crossbeam_channel::bounded(16);
+        register(SIGUSR2, tx).unwrap();
+
+        // SAFETY: a handler for SIGUSR2 was installed above.
+        unsafe { libc::raise(SIGUSR2) };
+
+        let command = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(matches!(command, WatchCommand::Signal(SIGUSR2)));
+    }
+
+    #[test]
+    fn test_handler_preserves_errno() {
+        let (tx, rx) = crossbeam_channel::bounded(16);
+        register(SIGUSR1, tx).unwrap();
+
+        // SAFETY: the handler runs synchronously on this thread, and a
+        // handler for SIGUSR1 was installed above.
+        let errno = unsafe {
+            *errno_location() = libc::EINTR;
+            libc::raise(SIGUSR1);
+            *errno_location()
+        };
+        assert_eq!(errno, libc::EINTR);
+
+        let command = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(matches!(command, WatchCommand::Signal(SIGUSR1)));
+    }
+
+    #[test]
+    fn test_invalid_signal_fails() {
+        let (tx, _rx) = crossbeam_channel::bounded(16);
+        assert!(register(libc::SIGKILL, tx).is_err());
+    }
+}
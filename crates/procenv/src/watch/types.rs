@@ -4,10 +4,14 @@
 //! - [`WatchError`] - Errors specific to file watching and reloading
 //! - [`ConfigChange`] - Represents a configuration change event
 //! - [`ChangeTrigger`] - What caused the configuration to reload
+//!
+//! With the `serde` feature, [`ConfigChange`] serializes to JSON for audit
+//! logs. The configurations themselves are never serialized, only field
+//! values as reported by `get_str()`, which masks secrets.
 
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use miette::Diagnostic;
 use thiserror::Error;
@@ -138,6 +142,10 @@ impl WatchError {
 /// what changed, including the old and new configurations, which fields
 /// changed, and what triggered the reload.
 ///
+/// With the `serde` feature it serializes to an object with the trigger,
+/// timestamp, changed files, changed fields (with masked values) and
+/// sources; `old` and `new` are skipped so secrets never leak.
+///
 /// # Example
 ///
 /// ```ignore
@@ -160,12 +168,29 @@ pub struct ConfigChange<T> {
     /// Names of fields that changed (empty for initial load).
     pub changed_fields: Vec<String>,
 
+    /// Old and new values of the changed fields, with secrets masked.
+    ///
+    /// Only filled in for types with field accessors, such as those
+    /// watched via the derive's `watch()`; see
+    /// [`WatchBuilder::diff_fields`](super::WatchBuilder::diff_fields).
+    pub field_changes: Vec<ChangedField>,
+
     /// What triggered this configuration change.
+    ///
+    /// When several events arrive within one debounce window they are
+    /// coalesced into a single reload; this is the most recent file trigger,
+    /// or the combined env var change if only the environment changed.
     pub trigger: ChangeTrigger,
 
+    /// All files that changed since the previous reload, in event order.
+    pub changed_files: Vec<PathBuf>,
+
     /// When the change was detected.
     pub timestamp: Instant,
 
+    /// Wall-clock time of the change, for logs.
+    pub time: SystemTime,
+
     /// Source attribution for the new configuration.
     pub sources: ConfigSources,
 }
@@ -183,12 +208,32 @@ impl<T> ConfigChange<T> {
             old,
             new,
             changed_fields,
+            field_changes: Vec::new(),
+            changed_files: trigger.file_path().cloned().into_iter().collect(),
             trigger,
             timestamp: Instant::now(),
+            time: SystemTime::now(),
             sources,
         }
     }
 
+    /// Set the files that changed since the previous reload.
+    #[must_use]
+    pub fn with_changed_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.changed_files = files;
+        self
+    }
+
+    /// Set the changed fields with their old and new values.
+    ///
+    /// Also replaces [`changed_fields`](Self::changed_fields) with their names.
+    #[must_use]
+    pub fn with_field_changes(mut self, fields: Vec<ChangedField>) -> Self {
+        self.changed_fields = fields.iter().map(|field| field.name.clone()).collect();
+        self.field_changes = fields;
+        self
+    }
+
     /// Create a change event for initial configuration load.
     pub fn initial(config: Arc<T>, sources: ConfigSources) -> Self {
         Self::new(None, config, Vec::new(), ChangeTrigger::Initial, sources)
@@ -213,8 +258,59 @@ impl<T> ConfigChange<T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for ConfigChange<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("ConfigChange", 5)?;
+        state.serialize_field("trigger", &self.trigger)?;
+        state.serialize_field("timestamp", &rfc3339(self.time))?;
+        state.serialize_field("changed_files", &self.changed_files)?;
+        state.serialize_field("changed_fields", &self.field_changes)?;
+        state.serialize_field("sources", &self.sources)?;
+        state.end()
+    }
+}
+
+/// Format a time as an RFC 3339 UTC timestamp with milliseconds.
+pub fn rfc3339(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let (days, secs) = (
+        since_epoch.as_secs() / 86_400,
+        since_epoch.as_secs() % 86_400,
+    );
+
+    // Civil date from days since 1970-01-01, in 400-year eras starting March 1st
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
 /// What triggered a configuration reload.
+///
+/// With the `serde` feature it serializes as `{"kind": "file_modified",
+/// "value": "config.toml"}`, omitting `value` for variants without data.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "kind", content = "value", rename_all = "snake_case")
+)]
 #[non_exhaustive]
 pub enum ChangeTrigger {
     /// A watched file was modified.
@@ -225,21 +321,53 @@ pub enum ChangeTrigger {
 
     /// A watched file was deleted.
     ///
-    /// Note: The configuration is not reloaded on deletion; this trigger
-    /// is informational only.
+    /// Only reported with [`DeletePolicy::Reload`]; the other policies do
+    /// not reload on deletion.
     FileDeleted(PathBuf),
 
-    /// An environment variable changed (detected via polling).
+    /// An environment variable changed.
+    ///
+    /// Emitted when a watched dotenv file changes the effective value of a
+    /// variable, or when environment polling detects a change.
     EnvVarChanged(String),
 
+    /// Several environment variables changed in the same reload.
+    ///
+    /// Like [`EnvVarChanged`](Self::EnvVarChanged), for changes to more than
+    /// one variable. The names are sorted and deduplicated.
+    EnvVarsChanged(Vec<String>),
+
     /// Configuration was manually reloaded via `reload()`.
     ManualReload,
 
+    /// A Unix signal registered with `reload_on_signal()` was received.
+    ///
+    /// Holds the signal number (e.g. `SIGHUP`).
+    Signal(i32),
+
+    /// The revision of a watched provider changed.
+    ///
+    /// Emitted when polling [`Provider::revision`](crate::Provider::revision)
+    /// returns a new value.
+    ProviderChanged {
+        /// Name of the provider.
+        provider: String,
+    },
+
     /// Initial configuration load.
     Initial,
 }
 
 impl ChangeTrigger {
+    /// The trigger for a change to the given sorted variable names.
+    pub(crate) fn env(mut vars: Vec<String>) -> Self {
+        if vars.len() == 1 {
+            Self::EnvVarChanged(vars.remove(0))
+        } else {
+            Self::EnvVarsChanged(vars)
+        }
+    }
+
     /// Returns the file path if this trigger is file-related.
     #[must_use]
     pub const fn file_path(&self) -> Option<&PathBuf> {
@@ -250,13 +378,21 @@ impl ChangeTrigger {
         }
     }
 
-    /// Returns the env var name if this trigger is env-related.
+    /// Returns the (first) changed env var name if this trigger is env-related.
     #[must_use]
     pub fn env_var(&self) -> Option<&str> {
+        self.env_vars().first().map(String::as_str)
+    }
+
+    /// Returns all changed env var names (empty for non-env triggers).
+    #[must_use]
+    pub fn env_vars(&self) -> &[String] {
         match self {
-            Self::EnvVarChanged(var) => Some(var),
+            Self::EnvVarChanged(var) => std::slice::from_ref(var),
 
-            _ => None,
+            Self::EnvVarsChanged(vars) => vars,
+
+            _ => &[],
         }
     }
 
@@ -281,17 +417,131 @@ impl std::fmt::Display for ChangeTrigger {
 
             Self::EnvVarChanged(var) => write!(f, "env var changed: {var}"),
 
+            Self::EnvVarsChanged(vars) => write!(f, "env vars changed: {}", vars.join(", ")),
+
             Self::ManualReload => write!(f, "manual reload"),
 
+            Self::Signal(signal) => write!(f, "signal {signal}"),
+
+            Self::ProviderChanged { provider } => write!(f, "provider changed: {provider}"),
+
             Self::Initial => write!(f, "initial load"),
         }
     }
 }
 
+/// What the watcher does when a watched file is deleted.
+///
+/// A deleted file is always watched for recreation; once it reappears, it
+/// is reloaded like a modification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum DeletePolicy {
+    /// Keep the last configuration until the file is recreated.
+    ///
+    /// Other changes in the same debounce window are still reloaded, without
+    /// the deleted file.
+    KeepLast,
+
+    /// Reload without the file, reported as [`ChangeTrigger::FileDeleted`].
+    ///
+    /// Optional files fall back to the remaining layers and defaults, while a
+    /// missing required file fails the reload and keeps the last config.
+    #[default]
+    Reload,
+
+    /// Report [`WatchError::FileDeleted`] and stop the watcher.
+    Fail,
+
+    /// Keep the last configuration, expecting the file to be recreated.
+    ///
+    /// If the file is still missing after `timeout`,
+    /// [`WatchError::FileDeleted`] is reported; the watcher keeps running and
+    /// reloads once the file reappears.
+    WaitForRecreate {
+        /// How long to wait for the file to reappear.
+        timeout: Duration,
+    },
+}
+
+/// Reload statistics for a watcher.
+///
+/// Only reloads after the initial load are counted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ReloadStats {
+    /// Number of reloads attempted.
+    pub attempts: u64,
+
+    /// Number of reloads that failed (the previous configuration was kept).
+    pub failures: u64,
+
+    /// Number of failures since the last successful reload.
+    pub consecutive_failures: u32,
+
+    /// When the last successful reload finished.
+    pub last_success: Option<Instant>,
+
+    /// When the last failed reload finished.
+    pub last_failure: Option<Instant>,
+}
+
+impl ReloadStats {
+    /// Record a successful reload.
+    pub(crate) fn record_success(&mut self) {
+        self.attempts += 1;
+        self.consecutive_failures = 0;
+        self.last_success = Some(Instant::now());
+    }
+
+    /// Record a failed reload.
+    pub(crate) fn record_failure(&mut self) {
+        self.attempts += 1;
+        self.failures += 1;
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        self.last_failure = Some(Instant::now());
+    }
+}
+
+/// How the watcher detects file changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum WatchBackend {
+    /// Use native notifications, falling back to polling every second if the
+    /// native watcher cannot be created or cannot watch a path.
+    #[default]
+    Auto,
+
+    /// Native OS notifications (inotify, `FSEvents`, `ReadDirectoryChangesW`).
+    Native,
+
+    /// Periodically compare modification times and content hashes.
+    ///
+    /// Use this on NFS, FUSE mounts and some container overlay filesystems,
+    /// where native events are never delivered.
+    Poll {
+        /// How often to scan the watched paths.
+        interval: Duration,
+    },
+}
+
+impl std::fmt::Display for WatchBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auto => write!(f, "auto"),
+
+            Self::Native => write!(f, "native"),
+
+            Self::Poll { interval } => write!(f, "poll ({interval:?})"),
+        }
+    }
+}
+
 /// Information about a changed field.
 ///
 /// Used for detailed change tracking when diffing configurations.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ChangedField {
     /// Name of the field that changed.
     pub name: String,
@@ -323,6 +573,38 @@ impl ChangedField {
     }
 }
 
+/// Field accessors used to diff two configurations.
+///
+/// Matches the derive-generated `keys()` and `get_str()`, which report
+/// secret fields as `<redacted>`. Secret fields therefore never show up as
+/// changed.
+pub struct FieldDiff<T> {
+    /// Names of the fields to compare.
+    pub keys: &'static [&'static str],
+    /// Returns a field value as a string, with secrets masked.
+    pub get: fn(&T, &str) -> Option<String>,
+}
+
+impl<T> FieldDiff<T> {
+    /// List the fields whose values differ, with the source of the new value.
+    pub fn diff(&self, old: &T, new: &T, sources: &ConfigSources) -> Vec<ChangedField> {
+        self.keys
+            .iter()
+            .filter_map(|&key| {
+                let old_value = (self.get)(old, key);
+                let new_value = (self.get)(new, key);
+
+                (old_value != new_value).then(|| {
+                    let source = sources
+                        .get(key)
+                        .map_or(Source::NotSet, |value| value.source.clone());
+                    ChangedField::new(key, old_value, new_value, source)
+                })
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -347,8 +629,19 @@ mod tests {
         let trigger = ChangeTrigger::EnvVarChanged("PORT".to_string());
         assert!(trigger.to_string().contains("PORT"));
 
+        let trigger = ChangeTrigger::EnvVarsChanged(vec!["HOST".to_string(), "PORT".to_string()]);
+        assert!(trigger.to_string().contains("HOST, PORT"));
+
         let trigger = ChangeTrigger::ManualReload;
         assert!(trigger.to_string().contains("manual"));
+
+        let trigger = ChangeTrigger::Signal(1);
+        assert_eq!(trigger.to_string(), "signal 1");
+
+        let trigger = ChangeTrigger::ProviderChanged {
+            provider: "consul".to_string(),
+        };
+        assert_eq!(trigger.to_string(), "provider changed: consul");
     }
 
     #[test]
@@ -362,5 +655,99 @@ mod tests {
         assert!(!trigger.is_file_trigger());
         assert!(trigger.file_path().is_none());
         assert_eq!(trigger.env_var(), Some("TEST"));
+        assert_eq!(trigger.env_vars(), ["TEST".to_string()]);
+
+        let trigger = ChangeTrigger::EnvVarsChanged(vec!["A".to_string(), "B".to_string()]);
+        assert_eq!(trigger.env_var(), Some("A"));
+        assert_eq!(trigger.env_vars(), ["A".to_string(), "B".to_string()]);
+    }
+
+    #[test]
+    fn test_field_diff_masks_secrets() {
+        struct Config {
+            port: u16,
+            api_key: &'static str,
+        }
+
+        fn get(config: &Config, key: &str) -> Option<String> {
+            match key {
+                "port" => Some(config.port.to_string()),
+                "api_key" => Some("<redacted>".to_string()),
+                _ => None,
+            }
+        }
+
+        let diff = FieldDiff {
+            keys: &["port", "api_key"],
+            get,
+        };
+        let old = Config {
+            port: 8080,
+            api_key: "old",
+        };
+        let new = Config {
+            port: 9090,
+            api_key: "new",
+        };
+
+        let mut sources = ConfigSources::new();
+        sources.add("port", crate::ValueSource::new("PORT", Source::Environment));
+
+        assert_eq!(
+            diff.diff(&old, &new, &sources),
+            [ChangedField::new(
+                "port",
+                Some("8080".to_string()),
+                Some("9090".to_string()),
+                Source::Environment,
+            )]
+        );
+    }
+
+    #[test]
+    fn test_rfc3339() {
+        assert_eq!(rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        assert_eq!(
+            rfc3339(UNIX_EPOCH + Duration::from_millis(951_827_696_789)),
+            "2000-02-29T12:34:56.789Z"
+        );
+        assert_eq!(
+            rfc3339(UNIX_EPOCH + Duration::from_secs(1_735_689_599)),
+            "2024-12-31T23:59:59.000Z"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_config_change_serializes_without_configs() {
+        let change = ConfigChange::new(
+            None,
+            Arc::new("secret value"),
+            Vec::new(),
+            ChangeTrigger::FileModified(PathBuf::from("config.toml")),
+            ConfigSources::new(),
+        )
+        .with_field_changes(vec![ChangedField::new(
+            "port",
+            Some("8080".to_string()),
+            Some("9090".to_string()),
+            Source::ConfigFile(Some(PathBuf::from("config.toml"))),
+        )]);
+
+        let json = serde_json::to_value(&change).unwrap();
+        assert_eq!(
+            json["trigger"],
+            serde_json::json!({"kind": "file_modified", "value": "config.toml"})
+        );
+        assert_eq!(json["changed_files"], serde_json::json!(["config.toml"]));
+        assert_eq!(json["changed_fields"][0]["new_value"], "9090");
+        assert_eq!(
+            json["changed_fields"][0]["source"],
+            serde_json::json!({"kind": "config_file", "value": "config.toml"})
+        );
+        assert!(!json.to_string().contains("secret value"));
+
+        let json = serde_json::to_value(ChangeTrigger::ManualReload).unwrap();
+        assert_eq!(json, serde_json::json!({"kind": "manual_reload"}));
     }
 }
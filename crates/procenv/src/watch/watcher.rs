@@ -3,26 +3,91 @@
 //! This module contains the [`ConfigWatcher`] which manages file system
 //! events using the `notify` crate and triggers configuration reloads.
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread::{self, JoinHandle};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use crossbeam_channel::{Receiver, Sender, bounded, select};
-use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use crossbeam_channel::{Receiver, Select, Sender, bounded, never, tick};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, PollWatcher, RecursiveMode, Watcher};
+use parking_lot::Mutex;
 
 use super::WatchedConfig;
-use super::types::{ChangeTrigger, ConfigChange, WatchError};
+use super::env::{DotenvTracker, EnvPollConfig, EnvPoller};
+use super::provider::{ProviderChanges, ProviderPoller};
+use super::registry::WatchRegistry;
+use super::types::{
+    ChangeTrigger, ConfigChange, DeletePolicy, FieldDiff, ReloadStats, WatchBackend, WatchError,
+};
 use crate::{ConfigSources, Error};
 
+/// Polling interval used when [`WatchBackend::Auto`] falls back to polling.
+const FALLBACK_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A native or polling file system watcher.
+pub type BoxedWatcher = Box<dyn Watcher + Send>;
+
+/// A notify watcher with the number of watches on each path.
+///
+/// The entries of a [`WatchRegistry`] share one watcher, so a path is only
+/// unwatched once no entry watches it anymore.
+pub struct SharedWatcher {
+    watcher: BoxedWatcher,
+    counts: HashMap<PathBuf, usize>,
+}
+
+impl SharedWatcher {
+    /// Count the watches of `watcher`, which watches nothing yet.
+    pub fn new(watcher: BoxedWatcher) -> Self {
+        Self {
+            watcher,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Watch `path`, or count another watch on it.
+    pub fn watch(&mut self, path: &Path) -> notify::Result<()> {
+        if let Some(count) = self.counts.get_mut(path) {
+            *count += 1;
+            return Ok(());
+        }
+
+        self.watcher.watch(path, RecursiveMode::NonRecursive)?;
+        self.counts.insert(path.to_path_buf(), 1);
+        Ok(())
+    }
+
+    /// How many watches are held on `path`.
+    #[cfg(test)]
+    pub fn count(&self, path: &Path) -> usize {
+        self.counts.get(path).copied().unwrap_or_default()
+    }
+
+    /// Drop a watch on `path`, unwatching it with the last one.
+    pub fn unwatch(&mut self, path: &Path) {
+        let Some(count) = self.counts.get_mut(path) else {
+            return;
+        };
+
+        *count -= 1;
+        if *count == 0 {
+            self.counts.remove(path);
+            let _ = self.watcher.unwatch(path);
+        }
+    }
+}
+
 /// Commands sent to the watcher thread.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub enum WatchCommand {
     /// Request a manual reload.
     Reload,
+    /// Reload in response to a Unix signal.
+    Signal(i32),
     /// Stop the watcher.
     Stop,
 }
@@ -35,14 +100,17 @@ pub struct WatcherState<T> {
     pub watched_paths: Vec<PathBuf>,
     /// Whether the watcher is running.
     pub running: AtomicBool,
+    /// Reload statistics.
+    pub stats: Mutex<ReloadStats>,
 }
 
 impl<T> WatcherState<T> {
-    pub const fn new(config: Arc<WatchedConfig<T>>, watched_paths: Vec<PathBuf>) -> Self {
+    pub fn new(config: Arc<WatchedConfig<T>>, watched_paths: Vec<PathBuf>) -> Self {
         Self {
             config,
             watched_paths,
             running: AtomicBool::new(true),
+            stats: Mutex::new(ReloadStats::default()),
         }
     }
 
@@ -53,6 +121,46 @@ impl<T> WatcherState<T> {
     pub fn stop(&self) {
         self.running.store(false, Ordering::Release);
     }
+
+    pub fn stats(&self) -> ReloadStats {
+        self.stats.lock().clone()
+    }
+}
+
+/// Limits on how often automatic reloads run.
+///
+/// Manual and signal reloads are not limited, but they do count towards
+/// the minimum interval and the failure backoff.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReloadPolicy {
+    /// Minimum time between the end of one reload and the next.
+    pub min_interval: Duration,
+    /// Initial and maximum delay after consecutive failures, if enabled.
+    pub backoff: Option<(Duration, Duration)>,
+}
+
+impl ReloadPolicy {
+    /// Earliest time the next automatic reload may run, if limited.
+    ///
+    /// After `n` consecutive failures the delay is `initial * 2^(n-1)`,
+    /// capped at the maximum.
+    pub fn next_allowed(
+        &self,
+        last_reload: Option<Instant>,
+        stats: &ReloadStats,
+    ) -> Option<Instant> {
+        let rate_limit = last_reload.map(|at| at + self.min_interval);
+
+        let backoff = match (self.backoff, stats.last_failure) {
+            (Some((initial, max)), Some(last_failure)) if stats.consecutive_failures > 0 => {
+                let exponent = (stats.consecutive_failures - 1).min(31);
+                Some(last_failure + initial.saturating_mul(1 << exponent).min(max))
+            }
+            _ => None,
+        };
+
+        rate_limit.max(backoff)
+    }
 }
 
 /// Result from a reload attempt.
@@ -71,6 +179,22 @@ pub struct WatcherConfig {
     pub debounce: Duration,
     /// Paths to watch.
     pub paths: Vec<PathBuf>,
+    /// Directories to watch, with a glob pattern for file names.
+    pub dirs: Vec<(PathBuf, glob::Pattern)>,
+    /// Tracker for watched dotenv files, created before the initial load.
+    pub dotenv: Option<DotenvTracker>,
+    /// Process environment polling, if enabled.
+    pub env_poll: Option<EnvPollConfig>,
+    /// How file changes are detected.
+    pub backend: WatchBackend,
+    /// Unix signals that trigger a reload.
+    pub signals: Vec<i32>,
+    /// Limits on automatic reloads.
+    pub policy: ReloadPolicy,
+    /// What to do when a watched file is deleted.
+    pub delete_policy: DeletePolicy,
+    /// Providers whose revisions are polled, created before the initial load.
+    pub providers: ProviderPoller,
 }
 
 impl Default for WatcherConfig {
@@ -78,8 +202,261 @@ impl Default for WatcherConfig {
         Self {
             debounce: Duration::from_millis(100),
             paths: Vec::new(),
+            dirs: Vec::new(),
+            dotenv: None,
+            env_poll: None,
+            backend: WatchBackend::Auto,
+            signals: Vec::new(),
+            policy: ReloadPolicy::default(),
+            delete_policy: DeletePolicy::default(),
+            providers: ProviderPoller::default(),
+        }
+    }
+}
+
+/// A watched directory whose matching entries trigger reloads.
+struct WatchedDir {
+    /// Directory paths (original, absolute and canonical).
+    paths: HashSet<PathBuf>,
+    /// Pattern for file names inside the directory.
+    pattern: glob::Pattern,
+}
+
+/// A watched file whose resolved target may change over time.
+struct WatchedFile {
+    /// Path as configured.
+    path: PathBuf,
+    /// Absolute form of the configured path.
+    absolute: Option<PathBuf>,
+    /// Canonical target at the last check, if the file exists.
+    canonical: Option<PathBuf>,
+}
+
+/// Paths whose events are relevant to the watcher.
+struct WatchTargets {
+    /// Individual files (original, absolute and canonical paths).
+    files: HashSet<PathBuf>,
+    /// Watched files with their last resolved targets.
+    resolved: Vec<WatchedFile>,
+    /// Directories of fragments.
+    dirs: Vec<WatchedDir>,
+    /// Paths watched on the notify watcher for these targets, once per watch.
+    watches: Vec<PathBuf>,
+}
+
+impl WatchTargets {
+    fn new(
+        paths: &[PathBuf],
+        dotenv_files: &[PathBuf],
+        dirs: Vec<(PathBuf, glob::Pattern)>,
+        watches: Vec<PathBuf>,
+    ) -> Self {
+        // Store both original paths AND canonical paths (if file exists)
+        // This allows matching newly created files that didn't exist at startup
+        let mut files = path_set(paths);
+        files.extend(path_set(dotenv_files));
+
+        let resolved = paths
+            .iter()
+            .chain(dotenv_files)
+            .map(|path| WatchedFile {
+                path: path.clone(),
+                absolute: std::path::absolute(path).ok(),
+                canonical: path.canonicalize().ok(),
+            })
+            .collect();
+
+        Self {
+            files,
+            resolved,
+            dirs: dirs
+                .into_iter()
+                .map(|(dir, pattern)| WatchedDir {
+                    paths: path_set(std::slice::from_ref(&dir)),
+                    pattern,
+                })
+                .collect(),
+            watches,
+        }
+    }
+
+    /// Watch `path` for these targets.
+    fn watch(&mut self, watcher: &mut SharedWatcher, path: &Path) -> notify::Result<()> {
+        watcher.watch(path)?;
+        self.watches.push(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Drop a watch on `path`, if these targets hold one.
+    fn unwatch(&mut self, watcher: &mut SharedWatcher, path: &Path) {
+        if let Some(index) = self.watches.iter().position(|watched| watched == path) {
+            self.watches.swap_remove(index);
+            watcher.unwatch(path);
+        }
+    }
+
+    /// Drop all watches held for these targets.
+    fn unwatch_all(&mut self, watcher: &mut SharedWatcher) {
+        for path in self.watches.drain(..) {
+            watcher.unwatch(&path);
+        }
+    }
+
+    /// Re-resolve canonical paths of all watched files.
+    ///
+    /// Kubernetes updates `ConfigMap` and `Secret` volumes by atomically swapping
+    /// the `..data` symlink, so the configured path never receives an event
+    /// of its own and its canonical path changes. When a file's target moved,
+    /// the new target is watched and matched from then on.
+    ///
+    /// Returns a trigger for the configured path if an existing file now
+    /// resolves to a different target. Plain creation and deletion are left
+    /// to the regular event triggers.
+    fn resolve(&mut self, watcher: &mut SharedWatcher) -> Option<ChangeTrigger> {
+        let mut trigger = None;
+
+        let mut resolved = std::mem::take(&mut self.resolved);
+
+        for file in &mut resolved {
+            let canonical = file.path.canonicalize().ok();
+            if canonical == file.canonical {
+                continue;
+            }
+
+            if let Some(old) = &file.canonical
+                && Some(old) != file.absolute.as_ref()
+                && *old != file.path
+            {
+                self.files.remove(old);
+                self.unwatch(watcher, old);
+            }
+
+            if let Some(new) = &canonical {
+                self.files.insert(new.clone());
+                let _ = self.watch(watcher, new);
+
+                if file.canonical.is_some() {
+                    trigger = Some(ChangeTrigger::FileModified(file.path.clone()));
+                }
+            }
+
+            file.canonical = canonical;
+        }
+
+        self.resolved = resolved;
+        trigger
+    }
+
+    /// Move watches down to directories created, or moved into place, on the
+    /// way to missing files.
+    ///
+    /// A file whose directory doesn't exist is watched through its nearest
+    /// existing ancestor, which only sees the next directory being created.
+    ///
+    /// Returns a trigger for a file that was created before its new
+    /// directory could be watched.
+    fn descend(
+        &mut self,
+        watcher: &mut SharedWatcher,
+        appeared: &[PathBuf],
+    ) -> Option<ChangeTrigger> {
+        let appeared: Vec<PathBuf> = appeared
+            .iter()
+            .filter(|path| path.is_dir())
+            .filter_map(|path| std::path::absolute(path).ok())
+            .collect();
+        let mut trigger = None;
+
+        for file in &self.resolved {
+            let Some(absolute) = &file.absolute else {
+                continue;
+            };
+            if file.canonical.is_some() || !appeared.iter().any(|dir| absolute.starts_with(dir)) {
+                continue;
+            }
+
+            if let Ok(target) = watch_path(watcher, &file.path) {
+                self.watches.push(target);
+            }
+            if file.path.exists() {
+                trigger = Some(ChangeTrigger::FileCreated(file.path.clone()));
+            }
+        }
+
+        trigger
+    }
+
+    /// Returns `true` if an event for `path` should trigger a reload.
+    fn matches(&self, path: &Path) -> bool {
+        // Check direct path match first (handles newly created files)
+        self.files.contains(path)
+            // Then try absolute match (handles deleted files and relative event paths)
+            || std::path::absolute(path).is_ok_and(|a| self.files.contains(&a))
+            // Then try canonical match (handles existing files with symlinks/relative paths)
+            || path.canonicalize().is_ok_and(|c| self.files.contains(&c))
+            || self.matches_dir(path)
+    }
+
+    /// Returns `true` if `path` is a matching entry of a watched directory.
+    ///
+    /// Only the parent directory is resolved, so removed and renamed-away
+    /// fragments still match.
+    fn matches_dir(&self, path: &Path) -> bool {
+        let (Some(parent), Some(name)) = (path.parent(), path.file_name().and_then(|n| n.to_str()))
+        else {
+            return false;
+        };
+
+        self.dirs.iter().any(|dir| {
+            dir.pattern.matches(name)
+                && (dir.paths.contains(parent)
+                    || parent.canonicalize().is_ok_and(|c| dir.paths.contains(&c)))
+        })
+    }
+}
+
+/// Environment change sources owned by the watcher thread.
+struct EnvSources {
+    /// Tracker for watched dotenv files.
+    dotenv: Option<DotenvTracker>,
+    /// Dotenv paths (original and canonical) for event classification.
+    dotenv_paths: HashSet<PathBuf>,
+    /// Process environment poller.
+    poller: Option<EnvPoller>,
+}
+
+impl EnvSources {
+    /// Returns `true` if the path belongs to a watched dotenv file.
+    fn is_dotenv_path(&self, path: &Path) -> bool {
+        self.dotenv_paths.contains(path)
+            || path
+                .canonicalize()
+                .is_ok_and(|c| self.dotenv_paths.contains(&c))
+    }
+
+    /// Re-read dotenv files, returning the names of changed variables.
+    fn refresh_dotenv(&mut self) -> Vec<String> {
+        self.dotenv
+            .as_mut()
+            .map(DotenvTracker::refresh)
+            .unwrap_or_default()
+    }
+
+    /// Run `reload_fn` with the tracked dotenv values in effect.
+    fn with_dotenv<R>(&self, reload_fn: impl FnOnce() -> R) -> R {
+        match &self.dotenv {
+            Some(tracker) => crate::env::with_overlay(tracker.overlay(), reload_fn),
+            None => reload_fn(),
         }
     }
+
+    /// Poll the process environment, returning the names of changed variables.
+    fn poll_env(&mut self) -> Vec<String> {
+        self.poller
+            .as_mut()
+            .map(EnvPoller::poll)
+            .unwrap_or_default()
+    }
 }
 
 /// Internal file watcher that manages notify events and reloads.
@@ -92,12 +469,14 @@ pub struct ConfigWatcher<T: Clone + Send + Sync + 'static> {
     change_rx: Receiver<ConfigChange<T>>,
     /// Receiver for errors.
     error_rx: Receiver<WatchError>,
-    /// Watcher thread handle.
+    /// Watcher thread handle, or `None` when served by a registry.
     thread_handle: Option<JoinHandle<()>>,
+    /// Backend in use (never [`WatchBackend::Auto`]).
+    backend: WatchBackend,
 }
 
 impl<T: Clone + Send + Sync + 'static> ConfigWatcher<T> {
-    /// Start the file watcher.
+    /// Start the file watcher on its own thread.
     ///
     /// # Arguments
     ///
@@ -105,12 +484,90 @@ impl<T: Clone + Send + Sync + 'static> ConfigWatcher<T> {
     /// * `initial_sources` - Source attribution for initial config
     /// * `config` - Watcher configuration
     /// * `reload_fn` - Function to reload the configuration
+    /// * `diff` - Field accessors to report changed fields, if available
     pub fn start<F>(
         initial_config: T,
         initial_sources: ConfigSources,
-        watcher_config: &WatcherConfig,
+        watcher_config: WatcherConfig,
         reload_fn: F,
+        diff: Option<FieldDiff<T>>,
     ) -> Result<Self, WatchError>
+    where
+        F: Fn() -> Result<(T, ConfigSources), Error> + Send + Sync + 'static,
+    {
+        let (notify_tx, notify_rx) = bounded::<notify::Result<Event>>(100);
+        let (watcher, backend, watched_paths) =
+            start_backend(watcher_config.backend, &notify_tx, &|watcher| {
+                watch_all(watcher, &watcher_config)
+            })?;
+
+        let (mut this, entry) = Self::create(
+            initial_config,
+            initial_sources,
+            watcher_config,
+            reload_fn,
+            diff,
+            backend,
+            watched_paths,
+        )?;
+
+        let thread_handle = spawn_loop(
+            "procenv-watcher",
+            vec![entry],
+            notify_rx,
+            None,
+            Arc::new(Mutex::new(watcher)),
+        )?;
+        this.thread_handle = Some(thread_handle);
+
+        Ok(this)
+    }
+
+    /// Start watching on the shared thread of a registry.
+    ///
+    /// The registry's notify watcher and backend are used; the backend in
+    /// `watcher_config` is ignored.
+    pub fn start_in<F>(
+        registry: &WatchRegistry,
+        initial_config: T,
+        initial_sources: ConfigSources,
+        watcher_config: WatcherConfig,
+        reload_fn: F,
+        diff: Option<FieldDiff<T>>,
+    ) -> Result<Self, WatchError>
+    where
+        F: Fn() -> Result<(T, ConfigSources), Error> + Send + Sync + 'static,
+    {
+        if !registry.is_running() {
+            return Err(WatchError::Stopped);
+        }
+
+        let watched_paths = watch_all(&mut registry.watcher().lock(), &watcher_config)?;
+
+        let (this, entry) = Self::create(
+            initial_config,
+            initial_sources,
+            watcher_config,
+            reload_fn,
+            diff,
+            registry.backend(),
+            watched_paths,
+        )?;
+        registry.add(entry)?;
+
+        Ok(this)
+    }
+
+    /// Create the watcher and the entry serving it, without a thread.
+    fn create<F>(
+        initial_config: T,
+        initial_sources: ConfigSources,
+        watcher_config: WatcherConfig,
+        reload_fn: F,
+        diff: Option<FieldDiff<T>>,
+        backend: WatchBackend,
+        watches: Vec<PathBuf>,
+    ) -> Result<(Self, Box<dyn Entry>), WatchError>
     where
         F: Fn() -> Result<(T, ConfigSources), Error> + Send + Sync + 'static,
     {
@@ -125,62 +582,80 @@ impl<T: Clone + Send + Sync + 'static> ConfigWatcher<T> {
         let (change_tx, change_rx) = bounded::<ConfigChange<T>>(16);
         let (error_tx, error_rx) = bounded::<WatchError>(16);
 
-        // Set up notify watcher
-        let (notify_tx, notify_rx) = bounded::<notify::Result<Event>>(100);
-        let mut watcher = create_notify_watcher(notify_tx)?;
-
-        // Watch all configured paths
-        for path in &watcher_config.paths {
-            watch_path(&mut watcher, path)?;
-        }
+        let dotenv_files = watcher_config
+            .dotenv
+            .as_ref()
+            .map_or(&[][..], DotenvTracker::files);
+        let targets = WatchTargets::new(
+            &watcher_config.paths,
+            dotenv_files,
+            watcher_config.dirs,
+            watches,
+        );
 
-        // Spawn watcher thread
-        let thread_state = state.clone();
-        let debounce = watcher_config.debounce;
+        // Snapshot the environment after the initial load, so values applied
+        // by the load itself are not reported as changes
+        let env_tick = watcher_config
+            .env_poll
+            .as_ref()
+            .map_or_else(never, |poll| tick(poll.interval));
+        let env_sources = EnvSources {
+            dotenv_paths: watcher_config
+                .dotenv
+                .as_ref()
+                .map(|tracker| path_set(tracker.files()))
+                .unwrap_or_default(),
+            dotenv: watcher_config.dotenv,
+            poller: watcher_config
+                .env_poll
+                .map(|poll| EnvPoller::new(poll.vars)),
+        };
 
-        // Store both original paths AND canonical paths (if file exists)
-        // This allows matching newly created files that didn't exist at startup
-        let watched_paths: HashSet<PathBuf> = watcher_config
-            .paths
-            .iter()
-            .flat_map(|p| {
-                let mut paths = vec![p.clone()];
-                // Also store canonical path if file exists
-                if let Ok(canonical) = p.canonicalize()
-                    && canonical != *p
-                {
-                    paths.push(canonical);
-                }
-                paths
-            })
-            .collect();
+        #[cfg(unix)]
+        for &signal in &watcher_config.signals {
+            super::signal::register(signal, command_tx.clone())?;
+        }
 
-        let thread_handle = thread::Builder::new()
-            .name("procenv-watcher".to_string())
-            .spawn(move || {
-                watcher_loop(
-                    thread_state,
-                    command_rx,
-                    notify_rx,
-                    change_tx,
-                    error_tx,
-                    reload_fn,
-                    debounce,
-                    watched_paths,
-                    watcher,
-                );
-            })
-            .map_err(|e| {
-                WatchError::init_failed(format!("failed to spawn watcher thread: {e}"), None)
-            })?;
+        let entry = WatchEntry {
+            state: state.clone(),
+            command_rx,
+            change_tx,
+            error_tx,
+            reload_fn,
+            diff,
+            debounce: watcher_config.debounce,
+            policy: watcher_config.policy,
+            delete_policy: watcher_config.delete_policy,
+            providers: watcher_config.providers.spawn()?,
+            targets,
+            env_sources,
+            env_tick,
+            pending: Vec::new(),
+            last_event: Instant::now(),
+            last_reload: None,
+            awaiting: AwaitingRecreate::default(),
+        };
 
-        Ok(Self {
+        let this = Self {
             state,
             command_tx,
             change_rx,
             error_rx,
-            thread_handle: Some(thread_handle),
-        })
+            thread_handle: None,
+            backend,
+        };
+
+        Ok((this, Box::new(entry)))
+    }
+
+    /// Get a snapshot of the reload statistics.
+    pub fn stats(&self) -> ReloadStats {
+        self.state.stats()
+    }
+
+    /// Get the backend in use.
+    pub const fn backend(&self) -> WatchBackend {
+        self.backend
     }
 
     /// Get a reference to the watched configuration.
@@ -234,117 +709,648 @@ impl<T: Clone + Send + Sync + 'static> Drop for ConfigWatcher<T> {
     }
 }
 
+/// Collect paths together with their absolute and canonical forms.
+fn path_set(paths: &[PathBuf]) -> HashSet<PathBuf> {
+    paths
+        .iter()
+        .flat_map(|p| {
+            let mut paths = vec![p.clone()];
+            // Absolute form matches events for files that don't exist yet
+            if let Ok(absolute) = std::path::absolute(p) {
+                paths.push(absolute);
+            }
+            // Also store canonical path if file exists
+            if let Ok(canonical) = p.canonicalize() {
+                paths.push(canonical);
+            }
+            paths
+        })
+        .collect()
+}
+
+/// Returns `true` if the path resolves through a symlink.
+fn is_symlinked(path: &Path) -> bool {
+    match (std::path::absolute(path), path.canonicalize()) {
+        (Ok(absolute), Ok(canonical)) => absolute != canonical,
+        _ => false,
+    }
+}
+
+/// Create a watcher for `backend` and watch all paths with it.
+///
+/// [`WatchBackend::Auto`] tries the native backend first and falls back to
+/// polling if it fails. Returns the watcher, the backend actually in use and
+/// what `watch_all` returned.
+pub fn start_backend<R>(
+    backend: WatchBackend,
+    tx: &Sender<notify::Result<Event>>,
+    watch_all: &dyn Fn(&mut SharedWatcher) -> Result<R, WatchError>,
+) -> Result<(SharedWatcher, WatchBackend, R), WatchError> {
+    if backend == WatchBackend::Auto {
+        return start_backend(WatchBackend::Native, tx, watch_all).or_else(|_| {
+            start_backend(
+                WatchBackend::Poll {
+                    interval: FALLBACK_POLL_INTERVAL,
+                },
+                tx,
+                watch_all,
+            )
+        });
+    }
+
+    let mut watcher = SharedWatcher::new(create_notify_watcher(backend, tx.clone())?);
+    let watched_paths = watch_all(&mut watcher)?;
+    Ok((watcher, backend, watched_paths))
+}
+
 /// Create a notify watcher with the given event sender.
 fn create_notify_watcher(
+    backend: WatchBackend,
     tx: Sender<notify::Result<Event>>,
-) -> Result<RecommendedWatcher, WatchError> {
-    notify::recommended_watcher(move |res| {
+) -> Result<BoxedWatcher, WatchError> {
+    let handler = move |res| {
         let _ = tx.send(res);
-    })
-    .map_err(|e| WatchError::init_failed(format!("failed to create file watcher: {e}"), Some(e)))
-}
-
-/// Watch a path with the notify watcher.
-fn watch_path(watcher: &mut RecommendedWatcher, path: &Path) -> Result<(), WatchError> {
-    // Watch the parent directory if the file doesn't exist yet
-    let watch_target = if path.exists() {
-        path.to_path_buf()
-    } else if let Some(parent) = path.parent() {
-        if parent.exists() {
-            parent.to_path_buf()
-        } else {
-            return Err(WatchError::path_error(
-                path,
-                "neither file nor parent directory exists",
-            ));
-        }
-    } else {
-        return Err(WatchError::path_error(path, "invalid path"));
     };
 
+    let watcher: notify::Result<BoxedWatcher> = match backend {
+        WatchBackend::Poll { interval } => {
+            // Content comparison catches edits within the mtime granularity
+            let config = notify::Config::default()
+                .with_poll_interval(interval)
+                .with_compare_contents(true);
+            PollWatcher::new(handler, config).map(|w| Box::new(w) as BoxedWatcher)
+        }
+        WatchBackend::Auto | WatchBackend::Native => {
+            notify::recommended_watcher(handler).map(|w| Box::new(w) as BoxedWatcher)
+        }
+    };
+
+    watcher.map_err(|e| {
+        WatchError::init_failed(format!("failed to create file watcher: {e}"), Some(e))
+    })
+}
+
+/// Watch all configured paths, including dotenv files and directories.
+///
+/// Returns the paths watched, once per watch. If one of them can't be
+/// watched, those already watched are unwatched again.
+fn watch_all(
+    watcher: &mut SharedWatcher,
+    config: &WatcherConfig,
+) -> Result<Vec<PathBuf>, WatchError> {
+    let dotenv_files = config.dotenv.as_ref().map_or(&[][..], DotenvTracker::files);
+    let mut watched_paths = Vec::new();
+
+    let result = (|| {
+        for path in config
+            .paths
+            .iter()
+            .chain(dotenv_files)
+            .chain(config.dirs.iter().map(|(dir, _)| dir))
+        {
+            watched_paths.push(watch_path(watcher, path)?);
+        }
+
+        // Symlinked files are swapped by replacing a link in their directory
+        // (e.g. Kubernetes `..data`), which only the directory sees
+        for path in config.paths.iter().chain(dotenv_files) {
+            if is_symlinked(path)
+                && let Some(parent) = path.parent()
+            {
+                watcher
+                    .watch(parent)
+                    .map_err(|e| WatchError::path_error(parent, format!("failed to watch: {e}")))?;
+                watched_paths.push(parent.to_path_buf());
+            }
+        }
+
+        Ok(())
+    })();
+
+    if let Err(e) = result {
+        for path in &watched_paths {
+            watcher.unwatch(path);
+        }
+        return Err(e);
+    }
+
+    Ok(watched_paths)
+}
+
+/// Watch a path with the notify watcher, returning the path watched.
+fn watch_path(watcher: &mut SharedWatcher, path: &Path) -> Result<PathBuf, WatchError> {
+    // Watch the nearest existing ancestor if the file doesn't exist yet,
+    // and follow directories as they are created (see `WatchTargets::descend`)
+    let watch_target = path
+        .ancestors()
+        // A bare file name like `.env` has an empty parent
+        .map(|ancestor| {
+            if ancestor.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                ancestor
+            }
+        })
+        .find(|ancestor| ancestor.exists())
+        .ok_or_else(|| WatchError::path_error(path, "no ancestor directory exists"))?
+        .to_path_buf();
+
     watcher
-        .watch(&watch_target, RecursiveMode::NonRecursive)
-        .map_err(|e| WatchError::path_error(path, format!("failed to watch: {e}")))
+        .watch(&watch_target)
+        .map_err(|e| WatchError::path_error(path, format!("failed to watch: {e}")))?;
+    Ok(watch_target)
 }
 
-/// Main watcher loop running in a separate thread.
-#[allow(clippy::too_many_arguments, clippy::needless_pass_by_value)]
-fn watcher_loop<T, F>(
+/// How long the watcher loop sleeps when no entry has a deadline.
+const IDLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Messages to a watcher thread about the entries it serves.
+pub enum Control {
+    /// Start serving another entry.
+    Add(Box<dyn Entry>),
+    /// Stop all entries and exit.
+    Stop,
+}
+
+/// A watched configuration served by a watcher thread.
+///
+/// Type-erased so a single thread can serve configurations of different
+/// types.
+pub trait Entry: Send {
+    /// Receiver for commands from the handle and signals.
+    fn commands(&self) -> &Receiver<WatchCommand>;
+
+    /// Receiver for environment polling ticks.
+    fn env_tick(&self) -> &Receiver<Instant>;
+
+    /// Receiver for provider revision changes.
+    fn provider_changes(&self) -> &Receiver<ChangeTrigger>;
+
+    /// Returns `true` until the entry was stopped.
+    fn is_running(&self) -> bool;
+
+    /// Stop the entry.
+    fn stop(&self);
+
+    /// Drop the watches the entry holds, once it is removed.
+    fn unwatch(&mut self, watcher: &mut SharedWatcher);
+
+    /// Handle a command.
+    fn on_command(&mut self, command: WatchCommand);
+
+    /// Collect the trigger for a file system event, if relevant.
+    fn on_event(&mut self, event: &Event, at: Instant, watcher: &mut SharedWatcher);
+
+    /// Poll the process environment.
+    fn on_env_tick(&mut self);
+
+    /// Collect a provider change, or `None` once its polling thread is gone.
+    fn on_provider_change(&mut self, trigger: Option<ChangeTrigger>);
+
+    /// When [`process`](Self::process) has work to do next, if ever.
+    fn next_wakeup(&self) -> Option<Instant>;
+
+    /// Report expired recreate timeouts and run the pending reload if due.
+    fn process(&mut self);
+}
+
+/// A configuration of type `T` with the state of its pending reload.
+struct WatchEntry<T, F> {
     state: Arc<WatcherState<T>>,
     command_rx: Receiver<WatchCommand>,
-    notify_rx: Receiver<notify::Result<Event>>,
     change_tx: Sender<ConfigChange<T>>,
     error_tx: Sender<WatchError>,
     reload_fn: F,
+    diff: Option<FieldDiff<T>>,
     debounce: Duration,
-    watched_paths: HashSet<PathBuf>,
-    _watcher: RecommendedWatcher, // Keep watcher alive
-) where
+    policy: ReloadPolicy,
+    delete_policy: DeletePolicy,
+    providers: ProviderChanges,
+    targets: WatchTargets,
+    env_sources: EnvSources,
+    env_tick: Receiver<Instant>,
+    /// Triggers collected since the last reload.
+    pending: Vec<ChangeTrigger>,
+    last_event: Instant,
+    last_reload: Option<Instant>,
+    awaiting: AwaitingRecreate,
+}
+
+impl<T, F> WatchEntry<T, F>
+where
+    F: Fn() -> Result<(T, ConfigSources), Error>,
+{
+    /// Perform a reload and send the result to the handle.
+    fn reload(&self, trigger: ChangeTrigger, changed_files: Vec<PathBuf>) {
+        match self.env_sources.with_dotenv(&self.reload_fn) {
+            Ok((new_config, new_sources)) => {
+                self.state.stats.lock().record_success();
+
+                let new_arc = Arc::new(new_config);
+                let (old_config, _old_sources) =
+                    self.state.config.swap(new_arc.clone(), new_sources.clone());
+
+                let field_changes = self.diff.as_ref().map_or_else(Vec::new, |diff| {
+                    diff.diff(&old_config, &new_arc, &new_sources)
+                });
+
+                let change =
+                    ConfigChange::new(Some(old_config), new_arc, Vec::new(), trigger, new_sources)
+                        .with_changed_files(changed_files)
+                        .with_field_changes(field_changes);
+
+                let _ = self.change_tx.send(change);
+            }
+            Err(e) => {
+                self.state.stats.lock().record_failure();
+
+                let watch_err = WatchError::reload_failed(e.to_string(), vec![e]);
+                let _ = self.error_tx.send(watch_err);
+            }
+        }
+    }
+
+    /// When the pending triggers may be reloaded, or `None` if nothing is pending.
+    fn reload_due(&self) -> Option<Instant> {
+        if self.pending.is_empty() {
+            return None;
+        }
+
+        let settled = self.last_event + self.debounce;
+        let allowed = self
+            .policy
+            .next_allowed(self.last_reload, &self.state.stats());
+        Some(allowed.map_or(settled, |allowed| allowed.max(settled)))
+    }
+}
+
+impl<T, F> Entry for WatchEntry<T, F>
+where
     T: Clone + Send + Sync + 'static,
     F: Fn() -> Result<(T, ConfigSources), Error> + Send + Sync + 'static,
 {
-    let mut pending_reload: Option<ChangeTrigger> = None;
-    let mut last_event = std::time::Instant::now();
-
-    while state.is_running() {
-        select! {
-            // Handle commands
-            recv(command_rx) -> cmd => {
-                match cmd {
-                    Ok(WatchCommand::Reload) => {
-                        do_reload(
-                            &state,
-                            &reload_fn,
-                            ChangeTrigger::ManualReload,
-                            &change_tx,
-                            &error_tx,
-                        );
-                    }
-                    Ok(WatchCommand::Stop) | Err(_) => {
-                        state.stop();
-                        break;
-                    }
-                }
+    fn commands(&self) -> &Receiver<WatchCommand> {
+        &self.command_rx
+    }
+
+    fn env_tick(&self) -> &Receiver<Instant> {
+        &self.env_tick
+    }
+
+    fn provider_changes(&self) -> &Receiver<ChangeTrigger> {
+        &self.providers.triggers
+    }
+
+    fn is_running(&self) -> bool {
+        self.state.is_running()
+    }
+
+    fn stop(&self) {
+        self.state.stop();
+    }
+
+    fn unwatch(&mut self, watcher: &mut SharedWatcher) {
+        self.targets.unwatch_all(watcher);
+    }
+
+    fn on_command(&mut self, command: WatchCommand) {
+        let trigger = match command {
+            WatchCommand::Reload => ChangeTrigger::ManualReload,
+            WatchCommand::Signal(signal) => ChangeTrigger::Signal(signal),
+            WatchCommand::Stop => {
+                self.state.stop();
+                return;
+            }
+        };
+
+        // Explicit reloads pick up pending dotenv edits too
+        self.env_sources.refresh_dotenv();
+        self.reload(trigger, Vec::new());
+        self.last_reload = Some(Instant::now());
+    }
+
+    fn on_event(&mut self, event: &Event, at: Instant, watcher: &mut SharedWatcher) {
+        // Symlinks are swapped by creating or renaming a link, and a swap
+        // outranks the raw event on the old target
+        let swapped = matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_))
+        );
+        // Directories on the way to a missing file appear the same way
+        let created = swapped
+            .then(|| self.targets.descend(watcher, &event.paths))
+            .flatten();
+        let trigger = swapped
+            .then(|| self.targets.resolve(watcher))
+            .flatten()
+            .or(created)
+            .or_else(|| process_notify_event(event, &self.targets));
+
+        if let Some(trigger) = trigger {
+            // Watch the parent so a recreated file is noticed
+            if let ChangeTrigger::FileDeleted(path) = &trigger
+                && let Ok(target) = watch_path(watcher, path)
+            {
+                self.targets.watches.push(target);
+            }
+
+            self.pending.push(trigger);
+            self.last_event = at;
+        }
+    }
+
+    fn on_env_tick(&mut self) {
+        let changed = self.env_sources.poll_env();
+        if !changed.is_empty() {
+            self.pending.push(ChangeTrigger::env(changed));
+        }
+    }
+
+    fn on_provider_change(&mut self, trigger: Option<ChangeTrigger>) {
+        match trigger {
+            Some(trigger) => {
+                self.pending.push(trigger);
+                self.last_event = Instant::now();
+            }
+            // A provider panicked; keep serving the other sources
+            None => self.providers.triggers = never(),
+        }
+    }
+
+    fn next_wakeup(&self) -> Option<Instant> {
+        self.reload_due()
+            .into_iter()
+            .chain(self.awaiting.next_deadline())
+            .min()
+    }
+
+    fn process(&mut self) {
+        for path in self.awaiting.expired() {
+            let _ = self.error_tx.send(WatchError::file_deleted(path));
+        }
+
+        if self.reload_due().is_none_or(|due| Instant::now() < due) {
+            return;
+        }
+
+        let mut triggers = std::mem::take(&mut self.pending);
+
+        if let Err(path) =
+            apply_delete_policy(self.delete_policy, &mut triggers, &mut self.awaiting)
+        {
+            let _ = self.error_tx.send(WatchError::file_deleted(path));
+            self.state.stop();
+            return;
+        }
+
+        // Windows without effective changes don't count against the interval
+        if let Some((trigger, files)) = coalesce(triggers, &mut self.env_sources) {
+            self.reload(trigger, files);
+            self.last_reload = Some(Instant::now());
+        }
+    }
+}
+
+/// What woke up the watcher loop.
+enum Wakeup {
+    /// A file system event.
+    Event(Event),
+    /// A control message, or `None` once all senders are gone.
+    Control(Option<Control>),
+    /// A command for the entry at the index, or `None` once its senders are gone.
+    Command(usize, Option<WatchCommand>),
+    /// An environment polling tick for the entry at the index.
+    EnvTick(usize),
+    /// A provider change for the entry at the index, or `None` once its
+    /// polling thread is gone.
+    ProviderChange(usize, Option<ChangeTrigger>),
+    /// A deadline passed, or nothing relevant arrived.
+    Timeout,
+}
+
+/// Spawn a thread running [`watcher_loop`].
+pub fn spawn_loop(
+    name: &str,
+    entries: Vec<Box<dyn Entry>>,
+    notify_rx: Receiver<notify::Result<Event>>,
+    control_rx: Option<Receiver<Control>>,
+    watcher: Arc<Mutex<SharedWatcher>>,
+) -> Result<JoinHandle<()>, WatchError> {
+    thread::Builder::new()
+        .name(name.to_string())
+        .spawn(move || watcher_loop(entries, &notify_rx, control_rx, &watcher))
+        .map_err(|e| WatchError::init_failed(format!("failed to spawn watcher thread: {e}"), None))
+}
+
+/// Main watcher loop running in a separate thread.
+///
+/// Each entry collects file and environment triggers until no event arrived
+/// for its debounce duration and its [`ReloadPolicy`] allows another reload,
+/// then coalesces them into a single reload. Manual and signal reloads run
+/// immediately.
+///
+/// Entries are always visited in the order they were added, so reloads of
+/// several entries triggered by the same event run in that order.
+///
+/// Without a control channel, the loop exits once all entries stopped.
+fn watcher_loop(
+    mut entries: Vec<Box<dyn Entry>>,
+    notify_rx: &Receiver<notify::Result<Event>>,
+    mut control_rx: Option<Receiver<Control>>,
+    watcher: &Mutex<SharedWatcher>,
+) {
+    loop {
+        entries.retain_mut(|entry| {
+            if entry.is_running() {
+                return true;
             }
+            entry.unwatch(&mut watcher.lock());
+            false
+        });
+        if entries.is_empty() && control_rx.is_none() {
+            break;
+        }
+
+        // Wake up when a pending reload or a recreate timeout is due, or
+        // check `running` regularly
+        let timeout = entries
+            .iter()
+            .filter_map(|entry| entry.next_wakeup())
+            .min()
+            .map_or(IDLE_INTERVAL, |due| {
+                due.saturating_duration_since(Instant::now())
+            });
 
-            // Handle file events
-            recv(notify_rx) -> event_result => {
-                if let Ok(Ok(event)) = event_result
-                    && let Some(trigger) = process_notify_event(&event, &watched_paths)
-                {
-                    pending_reload = Some(trigger);
-                    last_event = std::time::Instant::now();
+        match wait(&entries, notify_rx, control_rx.as_ref(), timeout) {
+            Wakeup::Event(event) => {
+                let at = Instant::now();
+                let mut watcher = watcher.lock();
+                for entry in &mut entries {
+                    entry.on_event(&event, at, &mut watcher);
                 }
             }
+            Wakeup::Control(Some(Control::Add(entry))) => entries.push(entry),
+            Wakeup::Control(Some(Control::Stop)) => {
+                for entry in &entries {
+                    entry.stop();
+                }
+                break;
+            }
+            Wakeup::Control(None) => control_rx = None,
+            Wakeup::Command(index, Some(command)) => entries[index].on_command(command),
+            Wakeup::Command(index, None) => entries[index].stop(),
+            Wakeup::EnvTick(index) => entries[index].on_env_tick(),
+            Wakeup::ProviderChange(index, trigger) => entries[index].on_provider_change(trigger),
+            Wakeup::Timeout => {}
+        }
+
+        for entry in &mut entries {
+            if entry.is_running() {
+                entry.process();
+            }
+        }
+    }
+}
+
+/// Wait for the next event, control message, command or tick.
+fn wait(
+    entries: &[Box<dyn Entry>],
+    notify_rx: &Receiver<notify::Result<Event>>,
+    control_rx: Option<&Receiver<Control>>,
+    timeout: Duration,
+) -> Wakeup {
+    let mut select = Select::new();
+    let notify = select.recv(notify_rx);
+    let control = control_rx.map(|rx| (select.recv(rx), rx));
+
+    // Each entry adds its command, tick and provider receivers, in that order
+    let first_entry = 1 + usize::from(control.is_some());
+    for entry in entries {
+        select.recv(entry.commands());
+        select.recv(entry.env_tick());
+        select.recv(entry.provider_changes());
+    }
+
+    let Ok(op) = select.select_timeout(timeout) else {
+        return Wakeup::Timeout;
+    };
+    let index = op.index();
 
-            // Debounce timeout - process pending reload
-            default(debounce) => {
-                if let Some(trigger) = pending_reload.take() {
-                    if last_event.elapsed() >= debounce {
-                        do_reload(&state, &reload_fn, trigger, &change_tx, &error_tx);
-                    } else {
-                        // Not enough time passed, re-queue
-                        pending_reload = Some(trigger);
-                    }
+    if index == notify {
+        return match op.recv(notify_rx) {
+            Ok(Ok(event)) => Wakeup::Event(event),
+            _ => Wakeup::Timeout,
+        };
+    }
+    if let Some((control, rx)) = control
+        && index == control
+    {
+        return Wakeup::Control(op.recv(rx).ok());
+    }
+
+    let entry = (index - first_entry) / 3;
+    match (index - first_entry) % 3 {
+        0 => Wakeup::Command(entry, op.recv(entries[entry].commands()).ok()),
+        1 => {
+            let _ = op.recv(entries[entry].env_tick());
+            Wakeup::EnvTick(entry)
+        }
+        _ => Wakeup::ProviderChange(entry, op.recv(entries[entry].provider_changes()).ok()),
+    }
+}
+
+/// Deleted files expected to be recreated, with their deadlines.
+#[derive(Default)]
+struct AwaitingRecreate {
+    files: Vec<(PathBuf, Instant)>,
+}
+
+impl AwaitingRecreate {
+    /// Start waiting for `path`, unless already waiting for it.
+    fn insert(&mut self, path: PathBuf, timeout: Duration) {
+        if !self.files.iter().any(|(p, _)| *p == path) {
+            self.files.push((path, Instant::now() + timeout));
+        }
+    }
+
+    /// The earliest deadline, if any.
+    fn next_deadline(&self) -> Option<Instant> {
+        self.files.iter().map(|(_, deadline)| *deadline).min()
+    }
+
+    /// Stop waiting for recreated files, and return those that timed out.
+    fn expired(&mut self) -> Vec<PathBuf> {
+        let now = Instant::now();
+        self.files.retain(|(path, _)| !path.exists());
+
+        let (expired, waiting) = std::mem::take(&mut self.files)
+            .into_iter()
+            .partition(|(_, deadline)| *deadline <= now);
+        self.files = waiting;
+
+        expired.into_iter().map(|(path, _)| path).collect()
+    }
+}
+
+/// Apply the delete policy to the triggers of one window.
+///
+/// Only files that are still missing count as deleted; a file deleted and
+/// recreated within the window is a plain modification. Returns the deleted
+/// path as an error if the watcher must stop.
+fn apply_delete_policy(
+    policy: DeletePolicy,
+    triggers: &mut Vec<ChangeTrigger>,
+    awaiting: &mut AwaitingRecreate,
+) -> Result<(), PathBuf> {
+    let deleted: Vec<PathBuf> = triggers
+        .iter()
+        .filter_map(|trigger| match trigger {
+            ChangeTrigger::FileDeleted(path) if !path.exists() => Some(path.clone()),
+            _ => None,
+        })
+        .collect();
+
+    if deleted.is_empty() {
+        return Ok(());
+    }
+
+    match policy {
+        DeletePolicy::Reload => {}
+
+        DeletePolicy::Fail => return Err(deleted[0].clone()),
+
+        DeletePolicy::KeepLast | DeletePolicy::WaitForRecreate { .. } => {
+            // Deletion also emits metadata events for the same path
+            triggers.retain(|trigger| {
+                trigger
+                    .file_path()
+                    .is_none_or(|path| !deleted.iter().any(|deleted| deleted == path))
+            });
+
+            if let DeletePolicy::WaitForRecreate { timeout } = policy {
+                for path in deleted {
+                    awaiting.insert(path, timeout);
                 }
             }
         }
     }
+
+    Ok(())
 }
 
 /// Process a notify event and return a trigger if relevant.
-fn process_notify_event(event: &Event, watched_paths: &HashSet<PathBuf>) -> Option<ChangeTrigger> {
+fn process_notify_event(event: &Event, targets: &WatchTargets) -> Option<ChangeTrigger> {
     // Check if any of the event paths are in our watched set
     for path in &event.paths {
-        // Check direct path match first (handles newly created files)
-        let is_watched = watched_paths.contains(path)
-            // Then try canonical match (handles existing files with symlinks/relative paths)
-            || path.canonicalize().is_ok_and(|c| watched_paths.contains(&c));
-
-        if is_watched {
+        if targets.matches(path) {
             return match event.kind {
                 EventKind::Create(_) => Some(ChangeTrigger::FileCreated(path.clone())),
                 EventKind::Modify(_) => Some(ChangeTrigger::FileModified(path.clone())),
+                // The old target of a replaced file or swapped symlink is
+                // removed while the watched path still resolves
+                EventKind::Remove(_) if path.exists() => {
+                    Some(ChangeTrigger::FileModified(path.clone()))
+                }
                 EventKind::Remove(_) => Some(ChangeTrigger::FileDeleted(path.clone())),
                 _ => None,
             };
@@ -353,38 +1359,46 @@ fn process_notify_event(event: &Event, watched_paths: &HashSet<PathBuf>) -> Opti
     None
 }
 
-/// Perform a reload and send results to channels.
-fn do_reload<T, F>(
-    state: &Arc<WatcherState<T>>,
-    reload_fn: &F,
-    trigger: ChangeTrigger,
-    change_tx: &Sender<ConfigChange<T>>,
-    error_tx: &Sender<WatchError>,
-) where
-    T: Clone + Send + Sync + 'static,
-    F: Fn() -> Result<(T, ConfigSources), Error>,
-{
-    match reload_fn() {
-        Ok((new_config, new_sources)) => {
-            let new_arc = Arc::new(new_config);
-            let (old_config, _old_sources) =
-                state.config.swap(new_arc.clone(), new_sources.clone());
-
-            let change = ConfigChange::new(
-                Some(old_config),
-                new_arc,
-                Vec::new(), // TODO: Implement field diffing
-                trigger,
-                new_sources,
-            );
+/// Coalesce the triggers of one window into a single trigger.
+///
+/// Dotenv files are re-read first, and their events are reported as the
+/// names of the variables that changed. The most recent trigger for any
+/// other file or for a provider wins; otherwise all changed variables are
+/// reported together.
+/// Returns `None` if nothing effectively changed (e.g. only a comment in a
+/// dotenv file was edited).
+///
+/// Also returns every file that changed, in event order.
+fn coalesce(
+    triggers: Vec<ChangeTrigger>,
+    env_sources: &mut EnvSources,
+) -> Option<(ChangeTrigger, Vec<PathBuf>)> {
+    let mut env_vars: BTreeSet<String> = env_sources.refresh_dotenv().into_iter().collect();
+    let mut files: Vec<PathBuf> = Vec::new();
+    let mut file_trigger = None;
 
-            let _ = change_tx.send(change);
-        }
-        Err(e) => {
-            let watch_err = WatchError::reload_failed(e.to_string(), vec![e]);
-            let _ = error_tx.send(watch_err);
+    for trigger in triggers {
+        env_vars.extend(trigger.env_vars().iter().cloned());
+
+        if let Some(path) = trigger.file_path() {
+            if !files.contains(path) {
+                files.push(path.clone());
+            }
+            if !env_sources.is_dotenv_path(path) {
+                file_trigger = Some(trigger);
+            }
+        } else if matches!(trigger, ChangeTrigger::ProviderChanged { .. }) {
+            file_trigger = Some(trigger);
         }
     }
+
+    let trigger = match file_trigger {
+        Some(trigger) => trigger,
+        None if !env_vars.is_empty() => ChangeTrigger::env(env_vars.into_iter().collect()),
+        None => return None,
+    };
+
+    Some((trigger, files))
 }
 
 #[cfg(test)]
@@ -403,6 +1417,141 @@ mod tests {
         let config = WatcherConfig::default();
         assert_eq!(config.debounce, Duration::from_millis(100));
         assert!(config.paths.is_empty());
+        assert!(config.dotenv.is_none());
+        assert!(config.env_poll.is_none());
+        assert_eq!(config.backend, WatchBackend::Auto);
+    }
+
+    #[test]
+    fn test_auto_backend_resolves() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.toml");
+        fs::write(&file_path, "value = \"test\"").unwrap();
+
+        let (tx, _rx) = bounded(16);
+        let watch_all = |watcher: &mut SharedWatcher| watch_path(watcher, &file_path);
+
+        let (_, backend, _) = start_backend(WatchBackend::Auto, &tx, &watch_all).unwrap();
+        assert_ne!(backend, WatchBackend::Auto);
+
+        let poll = WatchBackend::Poll {
+            interval: Duration::from_millis(50),
+        };
+        let (_, backend, _) = start_backend(poll, &tx, &watch_all).unwrap();
+        assert_eq!(backend, poll);
+    }
+
+    #[test]
+    fn test_reload_policy_backoff() {
+        let policy = ReloadPolicy {
+            min_interval: Duration::from_millis(10),
+            backoff: Some((Duration::from_millis(100), Duration::from_millis(250))),
+        };
+        let now = Instant::now();
+
+        // Only the minimum interval applies without failures
+        let stats = ReloadStats::default();
+        assert_eq!(policy.next_allowed(None, &stats), None);
+        assert_eq!(
+            policy.next_allowed(Some(now), &stats),
+            Some(now + Duration::from_millis(10))
+        );
+
+        // The delay doubles per consecutive failure, up to the maximum
+        let mut stats = ReloadStats {
+            last_failure: Some(now),
+            ..ReloadStats::default()
+        };
+        for (failures, delay) in [(1, 100), (2, 200), (3, 250), (40, 250)] {
+            stats.consecutive_failures = failures;
+            assert_eq!(
+                policy.next_allowed(Some(now), &stats),
+                Some(now + Duration::from_millis(delay))
+            );
+        }
+    }
+
+    #[test]
+    fn test_coalesce_lists_all_files() {
+        let mut env_sources = EnvSources {
+            dotenv: None,
+            dotenv_paths: HashSet::new(),
+            poller: None,
+        };
+        let triggers = vec![
+            ChangeTrigger::FileModified(PathBuf::from("a.toml")),
+            ChangeTrigger::EnvVarChanged("PORT".to_string()),
+            ChangeTrigger::FileCreated(PathBuf::from("b.toml")),
+            ChangeTrigger::FileModified(PathBuf::from("a.toml")),
+        ];
+
+        let (trigger, files) = coalesce(triggers, &mut env_sources).unwrap();
+        assert_eq!(
+            trigger,
+            ChangeTrigger::FileModified(PathBuf::from("a.toml"))
+        );
+        assert_eq!(files, [PathBuf::from("a.toml"), PathBuf::from("b.toml")]);
+
+        // Env-only windows merge all variable names
+        let triggers = vec![
+            ChangeTrigger::EnvVarChanged("PORT".to_string()),
+            ChangeTrigger::EnvVarsChanged(vec!["HOST".to_string(), "PORT".to_string()]),
+        ];
+        let (trigger, files) = coalesce(triggers, &mut env_sources).unwrap();
+        assert_eq!(trigger.env_vars(), ["HOST", "PORT"]);
+        assert!(files.is_empty());
+
+        // Provider changes outrank env vars
+        let provider = ChangeTrigger::ProviderChanged {
+            provider: "consul".to_string(),
+        };
+        let triggers = vec![
+            provider.clone(),
+            ChangeTrigger::EnvVarChanged("PORT".to_string()),
+        ];
+        let (trigger, _) = coalesce(triggers, &mut env_sources).unwrap();
+        assert_eq!(trigger, provider);
+
+        assert!(coalesce(Vec::new(), &mut env_sources).is_none());
+    }
+
+    #[test]
+    fn test_delete_policy_filters_deleted_files() {
+        let dir = tempdir().unwrap();
+        let existing = dir.path().join("existing.toml");
+        let missing = dir.path().join("missing.toml");
+        fs::write(&existing, "").unwrap();
+
+        let triggers = vec![
+            ChangeTrigger::FileDeleted(missing.clone()),
+            ChangeTrigger::FileDeleted(existing.clone()),
+            ChangeTrigger::FileModified(existing.clone()),
+        ];
+
+        // Reload keeps everything
+        let mut awaiting = AwaitingRecreate::default();
+        let mut reload = triggers.clone();
+        apply_delete_policy(DeletePolicy::Reload, &mut reload, &mut awaiting).unwrap();
+        assert_eq!(reload, triggers);
+
+        // Recreated files are not deleted; only the missing one is dropped
+        let mut keep = triggers.clone();
+        apply_delete_policy(DeletePolicy::KeepLast, &mut keep, &mut awaiting).unwrap();
+        assert_eq!(keep, triggers[1..]);
+        assert!(awaiting.next_deadline().is_none());
+
+        let policy = DeletePolicy::WaitForRecreate {
+            timeout: Duration::ZERO,
+        };
+        let mut wait = triggers.clone();
+        apply_delete_policy(policy, &mut wait, &mut awaiting).unwrap();
+        assert_eq!(awaiting.expired(), [missing.clone()]);
+
+        let mut fail = triggers;
+        assert_eq!(
+            apply_delete_policy(DeletePolicy::Fail, &mut fail, &mut awaiting),
+            Err(missing)
+        );
     }
 
     #[test]
@@ -417,4 +1566,48 @@ mod tests {
 
         assert!(!paths.is_empty());
     }
+
+    /// Records the paths watched (`true`) and unwatched (`false`).
+    #[derive(Clone, Default)]
+    struct RecordingWatcher(Arc<Mutex<Vec<(bool, PathBuf)>>>);
+
+    impl Watcher for RecordingWatcher {
+        fn new<F: notify::EventHandler>(_: F, _: notify::Config) -> notify::Result<Self> {
+            Ok(Self::default())
+        }
+
+        fn watch(&mut self, path: &Path, _: RecursiveMode) -> notify::Result<()> {
+            self.0.lock().push((true, path.to_path_buf()));
+            Ok(())
+        }
+
+        fn unwatch(&mut self, path: &Path) -> notify::Result<()> {
+            self.0.lock().push((false, path.to_path_buf()));
+            Ok(())
+        }
+
+        fn kind() -> notify::WatcherKind {
+            notify::WatcherKind::NullWatcher
+        }
+    }
+
+    #[test]
+    fn test_shared_watcher_unwatches_with_last_watch() {
+        let recorder = RecordingWatcher::default();
+        let mut watcher = SharedWatcher::new(Box::new(recorder.clone()));
+        let path = Path::new("/etc/config/..2024_01/config.toml");
+
+        // Two entries resolve the same symlink target
+        watcher.watch(path).unwrap();
+        watcher.watch(path).unwrap();
+        watcher.unwatch(path);
+        assert_eq!(*recorder.0.lock(), [(true, path.to_path_buf())]);
+
+        watcher.unwatch(path);
+        watcher.unwatch(path);
+        assert_eq!(
+            *recorder.0.lock(),
+            [(true, path.to_path_buf()), (false, path.to_path_buf())]
+        );
+    }
 }
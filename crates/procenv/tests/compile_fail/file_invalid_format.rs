@@ -0,0 +1,12 @@
+//! Test: file format must be a known format name
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(file = { path = "/etc/app/config", format = "xml" })]
+struct Config {
+    #[env(var = "PORT")]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+//! Test: global is rejected on generic structs
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(global)]
+struct Config<T: std::str::FromStr> {
+    #[env(var = "PORT")]
+    port: T,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+//! Test: global only accepts "panic" or "exit"
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_config(global = "abort")]
+struct Config {
+    #[env(var = "PORT")]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! Test: merge must be a known strategy
+
+use procenv::EnvConfig;
+
+#[derive(EnvConfig)]
+struct Config {
+    #[env(var = "PLUGINS", format = "json", merge = "prepend")]
+    plugins: Vec<String>,
+}
+
+fn main() {}
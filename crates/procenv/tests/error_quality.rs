@@ -288,6 +288,86 @@ fn test_error_is_multiple_variant() {
     );
 }
 
+// ============================================================================
+// Dotenv Syntax Errors
+// ============================================================================
+
+#[cfg(feature = "dotenv")]
+#[derive(EnvConfig)]
+#[env_config(dotenv = "/tmp/procenv_error_quality/malformed.env")]
+struct MalformedDotenvConfig {
+    #[env(var = "EQ_DOTENV_PORT", default = "8080")]
+    #[allow(dead_code)]
+    port: u16,
+}
+
+#[cfg(feature = "dotenv")]
+#[derive(EnvConfig)]
+#[env_config(dotenv = "/tmp/procenv_error_quality/valid.env")]
+struct ValidDotenvConfig {
+    #[env(var = "EQ_DOTENV_CERT")]
+    cert: String,
+
+    #[env(var = "EQ_DOTENV_URL")]
+    url: String,
+}
+
+#[cfg(feature = "dotenv")]
+#[test]
+#[serial]
+fn test_dotenv_syntax_error_points_at_line() {
+    std::fs::create_dir_all("/tmp/procenv_error_quality").unwrap();
+    std::fs::write(
+        "/tmp/procenv_error_quality/malformed.env",
+        "EQ_DOTENV_PORT=9000\nEQ_DOTENV_NAME=\"unterminated\n",
+    )
+    .unwrap();
+
+    let err = MalformedDotenvConfig::from_env().unwrap_err();
+    let Error::Dotenv { source } = &err else {
+        panic!("expected a dotenv error, got {err:?}");
+    };
+    assert!(
+        source.to_string().ends_with("at line 2, column 16"),
+        "{source}"
+    );
+
+    let rendered = format!("{:?}", miette::Report::new(err));
+    assert!(
+        rendered.contains("unterminated double-quoted value"),
+        "{rendered}"
+    );
+    assert!(
+        rendered.contains("procenv::dotenv::syntax_error"),
+        "{rendered}"
+    );
+
+    // Nothing is loaded from a malformed file
+    assert!(std::env::var("EQ_DOTENV_PORT").is_err());
+}
+
+#[cfg(feature = "dotenv")]
+#[test]
+#[serial]
+fn test_dotenv_full_syntax_via_derive() {
+    std::fs::create_dir_all("/tmp/procenv_error_quality").unwrap();
+    std::fs::write(
+        "/tmp/procenv_error_quality/valid.env",
+        concat!(
+            "export EQ_DOTENV_HOST=db.local # inline comment\n",
+            "EQ_DOTENV_URL=\"postgres://${EQ_DOTENV_HOST}/app\"\n",
+            "EQ_DOTENV_CERT=\"-----BEGIN-----\nabc\n-----END-----\"\n",
+        ),
+    )
+    .unwrap();
+
+    let config = ValidDotenvConfig::from_env().unwrap();
+    assert_eq!(config.url, "postgres://db.local/app");
+    assert_eq!(config.cert, "-----BEGIN-----\nabc\n-----END-----");
+
+    cleanup_vars(&["EQ_DOTENV_HOST", "EQ_DOTENV_URL", "EQ_DOTENV_CERT"]);
+}
+
 // ============================================================================
 // miette Diagnostic Codes
 // ============================================================================
@@ -1,4 +1,4 @@
-//! File format edge case tests (TOML, JSON, YAML).
+//! File format edge case tests (TOML, JSON, JSON5, YAML).
 //!
 //! Tests for file loading, parsing errors, and format-specific edge cases.
 //! Each test uses a unique file path to avoid race conditions.
@@ -157,6 +157,110 @@ fn test_json_special_characters() {
     cleanup_file("json_special.json");
 }
 
+// ============================================================================
+// JSON5 / JSONC Format Tests
+// ============================================================================
+
+#[test]
+fn test_jsonc_comments_and_trailing_commas() {
+    cleanup_env(&["JSONC_NAME", "JSONC_PORT"]);
+    cleanup_file("jsonc_basic.jsonc");
+
+    let content = r#"{
+    // Service name
+    "name": "jsonc-app",
+    /* Listen port */
+    "port": 4000,
+}"#;
+    write_file("jsonc_basic.jsonc", content);
+
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(
+        prefix = "JSONC_",
+        file_optional = "/tmp/procenv_fmt_tests/jsonc_basic.jsonc"
+    )]
+    struct JsoncConfig {
+        #[env(var = "NAME")]
+        name: String,
+
+        #[env(var = "PORT", default = "8080")]
+        port: u16,
+    }
+
+    let config = JsoncConfig::from_config().expect("should load JSONC");
+
+    assert_eq!(config.name, "jsonc-app");
+    assert_eq!(config.port, 4000);
+
+    cleanup_file("jsonc_basic.jsonc");
+}
+
+#[test]
+fn test_json5_unquoted_keys_and_single_quotes() {
+    cleanup_env(&["JSON5_NAME", "JSON5_PORT"]);
+    cleanup_file("json5_basic.json5");
+
+    let content = "{name: 'json5-app', port: 0x10,}";
+    write_file("json5_basic.json5", content);
+
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(
+        prefix = "JSON5_",
+        file_optional = "/tmp/procenv_fmt_tests/json5_basic.json5"
+    )]
+    struct Json5Config {
+        #[env(var = "NAME")]
+        name: String,
+
+        #[env(var = "PORT", default = "8080")]
+        port: u16,
+    }
+
+    let config = Json5Config::from_config().expect("should load JSON5");
+
+    assert_eq!(config.name, "json5-app");
+    assert_eq!(config.port, 16);
+
+    cleanup_file("json5_basic.json5");
+}
+
+#[test]
+fn test_jsonc_type_mismatch_points_at_value() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Database {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Config {
+        database: Database,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.jsonc");
+    let content = "{\n  // Primary database\n  database: {\n    host: 'db',\n    port: 'not-a-port',\n  },\n}";
+    fs::write(&path, content).unwrap();
+
+    let error = procenv::ConfigBuilder::new()
+        .file(&path)
+        .build::<Config>()
+        .unwrap_err();
+
+    let procenv::Error::File { source } = error else {
+        panic!("expected a file error, got {error:?}");
+    };
+    let procenv::file::FileError::TypeMismatch { span, .. } = source else {
+        panic!("expected a type mismatch, got {source:?}");
+    };
+    assert_eq!(
+        &content[span.offset()..span.offset() + span.len()],
+        "'not-a-port'"
+    );
+}
+
 // ============================================================================
 // YAML Format Tests
 // ============================================================================
@@ -453,6 +557,76 @@ fn test_source_attribution_from_file() {
     cleanup_file("source_attr.toml");
 }
 
+#[test]
+fn test_source_attribution_reports_line_and_column() {
+    cleanup_env(&["SRCL_NAME", "SRCL_PORT"]);
+    cleanup_file("source_line.toml");
+
+    write_file(
+        "source_line.toml",
+        "# service settings\n\nname = \"from-file\"\nport =   9000\n",
+    );
+
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(
+        prefix = "SRCL_",
+        file_optional = "/tmp/procenv_fmt_tests/source_line.toml"
+    )]
+    struct SourceLineConfig {
+        #[env(var = "NAME")]
+        name: String,
+
+        #[env(var = "PORT")]
+        port: u16,
+    }
+
+    let (_, sources) =
+        SourceLineConfig::from_config_with_sources().expect("should load with sources");
+
+    let port_source = sources.get("port").expect("should have port source");
+    let (procenv::Source::ConfigFile(Some(path)), Some(span)) =
+        (&port_source.source, port_source.span())
+    else {
+        panic!("port should come from a located ConfigFile");
+    };
+    assert!(path.ends_with("source_line.toml"));
+    assert_eq!((span.line, span.column), (4, 10));
+    assert!(port_source.to_string().ends_with("source_line.toml:4:10)"));
+
+    cleanup_file("source_line.toml");
+}
+
+#[test]
+fn test_span_of_follows_the_overriding_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("base.yaml");
+    let local = dir.path().join("local.json");
+
+    fs::write(&base, "name: base\ndb:\n  host: localhost\n  port: 5432\n").unwrap();
+    fs::write(&local, "{\n  \"db\": {\n    \"port\": 6543\n  }\n}\n").unwrap();
+
+    let (_, origins) = procenv::ConfigBuilder::new()
+        .file(&base)
+        .file(&local)
+        .into_value()
+        .unwrap();
+
+    let port = origins
+        .span_of("db.port")
+        .expect("db.port should be located");
+    assert_eq!((port.line, port.column), (3, 13));
+    assert_eq!(port.range, 24..28);
+    assert_eq!(origins.get_file_source("db.port"), Some(local));
+
+    let host = origins
+        .span_of("db.host")
+        .expect("db.host should be located");
+    assert_eq!((host.line, host.column), (3, 9));
+    assert_eq!(origins.get_file_source("db.host"), Some(base));
+
+    assert!(origins.span_of("missing").is_none());
+}
+
 // ============================================================================
 // Unicode in File Content
 // ============================================================================
@@ -583,3 +757,1144 @@ fn test_numeric_values_in_json() {
 
     cleanup_file("numbers_test.json");
 }
+
+// ============================================================================
+// Directory Fragment Tests
+// ============================================================================
+
+#[test]
+fn test_dir_fragments_merge_in_lexical_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let conf_d = dir.path().join("conf.d");
+    fs::create_dir(&conf_d).unwrap();
+
+    fs::write(
+        dir.path().join("config.toml"),
+        "port = 8000\nname = \"base\"",
+    )
+    .unwrap();
+    fs::write(conf_d.join("20-port.toml"), "port = 9000").unwrap();
+    fs::write(conf_d.join("10-port.toml"), "port = 8500\ndebug = true").unwrap();
+    fs::write(conf_d.join("README.md"), "not a fragment").unwrap();
+
+    #[derive(Deserialize)]
+    struct Config {
+        port: u16,
+        name: String,
+        debug: bool,
+    }
+
+    let (config, origins): (Config, _) = procenv::ConfigBuilder::new()
+        .file(dir.path().join("config.toml"))
+        .dir(&conf_d, "*.toml")
+        .build_with_origins()
+        .unwrap();
+
+    assert_eq!(config.port, 9000);
+    assert_eq!(config.name, "base");
+    assert!(config.debug);
+
+    // Each fragment is tracked separately
+    assert_eq!(
+        origins.get_file_source("port"),
+        Some(conf_d.join("20-port.toml"))
+    );
+    assert_eq!(
+        origins.get_file_source("debug"),
+        Some(conf_d.join("10-port.toml"))
+    );
+    assert_eq!(
+        origins.get_file_source("name"),
+        Some(dir.path().join("config.toml"))
+    );
+}
+
+#[test]
+fn test_dir_missing_contributes_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let (value, origins) = procenv::ConfigBuilder::new()
+        .defaults_value(serde_json::json!({"port": 8080}))
+        .dir(dir.path().join("conf.d"), "*.toml")
+        .into_value()
+        .unwrap();
+
+    assert_eq!(value["port"], 8080);
+    assert!(!origins.has_file_sources());
+}
+
+#[test]
+fn test_dir_invalid_glob_errors() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let result = procenv::ConfigBuilder::new()
+        .dir(dir.path(), "[*.toml")
+        .into_value();
+
+    assert!(matches!(
+        result,
+        Err(procenv::file::FileError::InvalidGlob { .. })
+    ));
+}
+
+// ============================================================================
+// Pluggable Formats
+// ============================================================================
+
+/// A `key: value` format, one entry per line.
+struct ColonFormat;
+
+impl procenv::file::FormatParser for ColonFormat {
+    fn name(&self) -> &'static str {
+        "colon"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["colon"]
+    }
+
+    fn parse(&self, content: &str) -> Result<serde_json::Value, procenv::file::FormatError> {
+        let mut map = serde_json::Map::new();
+        let mut offset = 0;
+
+        for line in content.split_inclusive('\n') {
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(procenv::file::FormatError::new("expected `key: value`")
+                    .with_span(offset..offset + line.trim_end().len()));
+            };
+            map.insert(key.trim().to_string(), value.trim().into());
+            offset += line.len();
+        }
+
+        Ok(map.into())
+    }
+}
+
+#[test]
+fn test_registered_format_parser() {
+    #[derive(Deserialize)]
+    struct Config {
+        name: String,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let good = dir.path().join("app.colon");
+    let bad = dir.path().join("bad.colon");
+    fs::write(&good, "name: colon-app\n").unwrap();
+    fs::write(&bad, "name: ok\nbroken line\n").unwrap();
+
+    let config: Config = procenv::ConfigBuilder::new()
+        .format(ColonFormat)
+        .file(&good)
+        .build()
+        .unwrap();
+    assert_eq!(config.name, "colon-app");
+
+    let error = procenv::ConfigBuilder::new()
+        .format(ColonFormat)
+        .file(&bad)
+        .into_value()
+        .unwrap_err();
+    let procenv::file::FileError::Parse {
+        format,
+        span,
+        message,
+        ..
+    } = error
+    else {
+        panic!("expected a parse error, got {error:?}");
+    };
+    assert_eq!(format, "colon");
+    assert_eq!((span.offset(), span.len()), (9, 11));
+    assert_eq!(message, "expected `key: value`");
+
+    // Without the parser the extension is unknown
+    let result = procenv::ConfigBuilder::new().file(&good).into_value();
+    assert!(matches!(
+        result,
+        Err(procenv::file::FileError::UnknownFormat { .. })
+    ));
+}
+
+#[test]
+fn test_ini_loading() {
+    cleanup_env(&["INI_NAME", "INI_PORT"]);
+    cleanup_file("ini_basic.ini");
+
+    write_file("ini_basic.ini", "name = ini-app\nport = 4000\n");
+
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(
+        prefix = "INI_",
+        file_optional = "/tmp/procenv_fmt_tests/ini_basic.ini"
+    )]
+    struct IniConfig {
+        #[env(var = "NAME")]
+        name: String,
+
+        #[env(var = "PORT", default = "8080")]
+        port: u16,
+    }
+
+    let config = IniConfig::from_config().expect("should load INI");
+
+    assert_eq!(config.name, "ini-app");
+    assert_eq!(config.port, 4000);
+
+    cleanup_file("ini_basic.ini");
+}
+
+#[test]
+fn test_properties_type_mismatch_points_at_value() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Database {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Config {
+        database: Database,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.properties");
+    let content = "database.host = localhost\ndatabase.port = not-a-port\n";
+    fs::write(&path, content).unwrap();
+
+    let error = procenv::ConfigBuilder::new()
+        .file(&path)
+        .build::<Config>()
+        .unwrap_err();
+
+    let procenv::Error::File { source } = error else {
+        panic!("expected a file error, got {error:?}");
+    };
+    let procenv::file::FileError::TypeMismatch { span, .. } = source else {
+        panic!("expected a type mismatch, got {source:?}");
+    };
+    assert_eq!(
+        &content[span.offset()..span.offset() + span.len()],
+        "not-a-port"
+    );
+}
+
+// ============================================================================
+// Explicit and Sniffed Formats
+// ============================================================================
+
+#[test]
+fn test_file_with_format_for_extensionless_file() {
+    #[derive(Deserialize)]
+    struct Config {
+        name: String,
+        port: u16,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config");
+    fs::write(&path, "name = \"explicit\"\nport = 7000\n").unwrap();
+
+    let result = procenv::ConfigBuilder::new().file(&path).into_value();
+    assert!(matches!(
+        result,
+        Err(procenv::file::FileError::UnknownFormat { .. })
+    ));
+
+    let config: Config = procenv::ConfigBuilder::new()
+        .file_with_format(&path, procenv::file::FileFormat::Toml)
+        .build()
+        .unwrap();
+    assert_eq!(config.name, "explicit");
+    assert_eq!(config.port, 7000);
+
+    // The override also wins over a misleading extension
+    let misnamed = dir.path().join("config.json");
+    fs::write(&misnamed, "port: 7001\n").unwrap();
+
+    let (value, _) = procenv::ConfigBuilder::new()
+        .file_with_format(&misnamed, procenv::file::FileFormat::Yaml)
+        .into_value()
+        .unwrap();
+    assert_eq!(value["port"], 7001);
+
+    let missing = dir.path().join("missing");
+    let (value, _) = procenv::ConfigBuilder::new()
+        .file_optional_with_format(&missing, procenv::file::FileFormat::Toml)
+        .into_value()
+        .unwrap();
+    assert_eq!(value, serde_json::json!({}));
+}
+
+#[test]
+fn test_sniff_formats_detects_content() {
+    let dir = tempfile::tempdir().unwrap();
+    let cases = [
+        ("json", "{\"port\": 1}", 1),
+        ("toml", "[server]\nport = 2\n", 2),
+        ("yaml", "server:\n  port: 3\n", 3),
+    ];
+
+    for (name, content, port) in cases {
+        let path = dir.path().join(name);
+        fs::write(&path, content).unwrap();
+
+        let (value, origins) = procenv::ConfigBuilder::new()
+            .sniff_formats(true)
+            .file(&path)
+            .into_value()
+            .unwrap();
+
+        let actual = value.get("port").or_else(|| value["server"].get("port"));
+        assert_eq!(actual, Some(&serde_json::json!(port)), "{name}");
+        assert_eq!(
+            origins.get_file_source(if name == "json" {
+                "port"
+            } else {
+                "server.port"
+            }),
+            Some(path.clone())
+        );
+    }
+}
+
+#[test]
+fn test_sniffed_format_points_type_mismatch_at_value() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Config {
+        port: u16,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("secret");
+    let content = "port = \"not-a-port\"\n";
+    fs::write(&path, content).unwrap();
+
+    let error = procenv::ConfigBuilder::new()
+        .sniff_formats(true)
+        .file(&path)
+        .build::<Config>()
+        .unwrap_err();
+
+    let procenv::Error::File { source } = error else {
+        panic!("expected a file error, got {error:?}");
+    };
+    let procenv::file::FileError::TypeMismatch { span, .. } = source else {
+        panic!("expected a type mismatch, got {source:?}");
+    };
+    assert_eq!(
+        &content[span.offset()..span.offset() + span.len()],
+        "\"not-a-port\""
+    );
+}
+
+#[test]
+fn test_file_format_in_derive() {
+    cleanup_env(&["FMTX_NAME", "FMTX_PORT"]);
+    write_file("explicit_config", "name = \"from-toml\"\n");
+    write_file("sniffed_config", "port: 6100\n");
+
+    #[derive(EnvConfig, Deserialize)]
+    #[env_config(
+        prefix = "FMTX_",
+        file = { path = "/tmp/procenv_fmt_tests/explicit_config", format = "toml" },
+        file_optional = [
+            "/tmp/procenv_fmt_tests/missing.toml",
+            { path = "/tmp/procenv_fmt_tests/sniffed_config", format = "auto" },
+        ]
+    )]
+    struct ExplicitConfig {
+        #[env(var = "NAME")]
+        name: String,
+
+        #[env(var = "PORT", default = "8080")]
+        port: u16,
+    }
+
+    let config = ExplicitConfig::from_config().expect("should load both files");
+
+    assert_eq!(config.name, "from-toml");
+    assert_eq!(config.port, 6100);
+
+    cleanup_file("explicit_config");
+    cleanup_file("sniffed_config");
+}
+
+// ============================================================================
+// Include Directives
+// ============================================================================
+
+#[test]
+fn test_includes_merge_beneath_including_file() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("common")).unwrap();
+    fs::create_dir_all(dir.path().join("app/db")).unwrap();
+
+    let base = dir.path().join("common/base.yaml");
+    let primary = dir.path().join("app/db/10-primary.toml");
+    let pool = dir.path().join("app/db/20-pool.toml");
+    let app = dir.path().join("app/service.toml");
+
+    fs::write(&base, "name: base\nport: 1000\nlog: info\n").unwrap();
+    fs::write(&primary, "[db]\nhost = \"primary\"\nsize = 1\n").unwrap();
+    fs::write(&pool, "[db]\nsize = 20\n").unwrap();
+    fs::write(
+        &app,
+        "_extends = \"../common/base.yaml\"\n_include = [\"db/*.toml\"]\nname = \"service\"\n",
+    )
+    .unwrap();
+
+    let (value, origins) = procenv::ConfigBuilder::new()
+        .file(&app)
+        .into_value()
+        .unwrap();
+
+    assert_eq!(
+        value,
+        serde_json::json!({
+            "name": "service",
+            "port": 1000,
+            "log": "info",
+            "db": {"host": "primary", "size": 20},
+        })
+    );
+
+    assert_eq!(origins.get_file_source("name"), Some(app.clone()));
+    // Included paths are reported as written, relative to the includer
+    assert_eq!(
+        origins.get_file_source("port"),
+        Some(dir.path().join("app/../common/base.yaml"))
+    );
+    assert_eq!(origins.get_file_source("db.host"), Some(primary.clone()));
+    assert_eq!(origins.get_file_source("db.size"), Some(pool.clone()));
+}
+
+#[test]
+fn test_included_type_mismatch_points_at_included_file() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Config {
+        name: String,
+        port: u16,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("base.toml");
+    let app = dir.path().join("app.json");
+    let base_content = "port = \"not-a-port\"\n";
+
+    fs::write(&base, base_content).unwrap();
+    fs::write(&app, r#"{"_include": "base.toml", "name": "app"}"#).unwrap();
+
+    let error = procenv::ConfigBuilder::new()
+        .file(&app)
+        .build::<Config>()
+        .unwrap_err();
+
+    let procenv::Error::File { source } = error else {
+        panic!("expected a file error, got {error:?}");
+    };
+    let procenv::file::FileError::TypeMismatch {
+        file_path, span, ..
+    } = source
+    else {
+        panic!("expected a type mismatch, got {source:?}");
+    };
+    assert_eq!(file_path, base.display().to_string());
+    assert_eq!(
+        &base_content[span.offset()..span.offset() + span.len()],
+        "\"not-a-port\""
+    );
+}
+
+#[test]
+fn test_include_cycle_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a.toml"), "_include = \"b.toml\"\n").unwrap();
+    fs::write(dir.path().join("b.toml"), "_extends = \"./a.toml\"\n").unwrap();
+
+    let error = procenv::ConfigBuilder::new()
+        .file(dir.path().join("a.toml"))
+        .into_value()
+        .unwrap_err();
+
+    let procenv::file::FileError::IncludeCycle { chain } = error else {
+        panic!("expected an include cycle, got {error:?}");
+    };
+    let files: Vec<&str> = chain
+        .split(" -> ")
+        .map(|path| path.rsplit('/').next().unwrap())
+        .collect();
+    assert_eq!(files, ["a.toml", "b.toml", "a.toml"]);
+}
+
+#[test]
+fn test_include_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let invalid = dir.path().join("invalid.toml");
+    let content = "name = \"app\"\n_include = 5\n";
+    fs::write(&invalid, content).unwrap();
+
+    let error = procenv::ConfigBuilder::new()
+        .file(&invalid)
+        .into_value()
+        .unwrap_err();
+    let procenv::file::FileError::InvalidInclude {
+        directive, span, ..
+    } = error
+    else {
+        panic!("expected an invalid include, got {error:?}");
+    };
+    assert_eq!(directive, "_include");
+    let span = span.expect("TOML locates the directive");
+    assert_eq!(&content[span.offset()..span.offset() + span.len()], "5");
+
+    // A missing include is an error, even from an optional file
+    let missing = dir.path().join("missing.toml");
+    fs::write(&missing, "_include = \"nowhere.toml\"\n").unwrap();
+
+    let result = procenv::ConfigBuilder::new()
+        .file_optional(&missing)
+        .into_value();
+    assert!(matches!(
+        result,
+        Err(procenv::file::FileError::NotFound { .. })
+    ));
+}
+
+// ============================================================================
+// Merge Strategies
+// ============================================================================
+
+#[test]
+fn test_merge_strategies_across_files() {
+    use procenv::file::Merge;
+
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("base.toml");
+    let local = dir.path().join("local.json");
+
+    fs::write(
+        &base,
+        "plugins = [\"auth\", \"metrics\"]\nports = [80]\n\n[cors]\norigins = [\"a.com\", \"b.com\"]\n\n[features]\nbeta = true\nlegacy = true\n",
+    )
+    .unwrap();
+    fs::write(
+        &local,
+        r#"{"plugins": ["debug"], "ports": [8080], "cors": {"origins": ["b.com", "c.com"]}, "features": {"legacy": null}}"#,
+    )
+    .unwrap();
+
+    let (value, _) = procenv::ConfigBuilder::new()
+        .merge_strategy("plugins", Merge::Append)
+        .merge_strategy("cors.origins", Merge::Union)
+        .merge_strategy("features", Merge::Patch)
+        .file(&base)
+        .file(&local)
+        .into_value()
+        .unwrap();
+
+    assert_eq!(
+        value,
+        serde_json::json!({
+            "plugins": ["auth", "metrics", "debug"],
+            "ports": [8080],
+            "cors": {"origins": ["a.com", "b.com", "c.com"]},
+            "features": {"beta": true},
+        })
+    );
+}
+
+#[test]
+fn test_appended_elements_keep_their_own_origin() {
+    use procenv::file::Merge;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Config {
+        ports: Vec<u16>,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("base.toml");
+    let local = dir.path().join("local.toml");
+    let base_content = "ports = [80, \"http\"]\n";
+    let local_content = "# local overrides\nports = [8080]\n";
+
+    fs::write(&base, base_content).unwrap();
+    fs::write(&local, local_content).unwrap();
+
+    let builder = || {
+        procenv::ConfigBuilder::new()
+            .merge_strategy("ports", Merge::Append)
+            .file(&base)
+            .file(&local)
+    };
+
+    let (_, origins) = builder().into_value().unwrap();
+    assert_eq!(origins.get_file_source("ports[1]"), Some(base.clone()));
+    assert_eq!(origins.get_file_source("ports[2]"), Some(local.clone()));
+
+    let span = origins.span_of("ports[2]").expect("TOML records spans");
+    assert_eq!((span.line, span.column), (2, 10));
+
+    // An invalid element from the earlier file is reported there
+    let error = builder().build::<Config>().unwrap_err();
+    let procenv::Error::File { source } = error else {
+        panic!("expected a file error, got {error:?}");
+    };
+    let procenv::file::FileError::TypeMismatch {
+        file_path, span, ..
+    } = source
+    else {
+        panic!("expected a type mismatch, got {source:?}");
+    };
+    assert_eq!(file_path, base.display().to_string());
+    assert_eq!(
+        &base_content[span.offset()..span.offset() + span.len()],
+        "\"http\""
+    );
+}
+
+#[test]
+fn test_merge_patch_for_whole_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("base.json");
+    let patch = dir.path().join("patch.json");
+
+    fs::write(&base, r#"{"name": "app", "db": {"host": "a", "port": 1}}"#).unwrap();
+    fs::write(
+        &patch,
+        r#"{"name": null, "db": {"port": null, "user": "x"}}"#,
+    )
+    .unwrap();
+
+    let (value, _) = procenv::ConfigBuilder::new()
+        .merge_strategy("", procenv::file::Merge::Patch)
+        .file(&base)
+        .file(&patch)
+        .into_value()
+        .unwrap();
+
+    assert_eq!(value, serde_json::json!({"db": {"host": "a", "user": "x"}}));
+}
+
+#[test]
+fn test_merge_strategy_in_derive() {
+    cleanup_env(&["MRG_NAME", "MRG_PLUGINS", "MRG_HOSTS"]);
+    write_file(
+        "merge_base.json",
+        r#"{"name": "base", "plugins": ["auth"], "cluster": {"hosts": ["a", "b"]}}"#,
+    );
+    write_file(
+        "merge_local.json",
+        r#"{"name": "local", "plugins": ["debug"], "cluster": {"hosts": ["b", "c"]}}"#,
+    );
+
+    #[derive(EnvConfig)]
+    struct ClusterConfig {
+        #[env(var = "MRG_HOSTS", format = "json", default = "[]", merge = "union")]
+        hosts: Vec<String>,
+    }
+
+    #[derive(EnvConfig)]
+    #[env_config(file = [
+        "/tmp/procenv_fmt_tests/merge_base.json",
+        "/tmp/procenv_fmt_tests/merge_local.json",
+    ])]
+    struct MergeConfig {
+        #[env(var = "MRG_NAME")]
+        name: String,
+
+        #[env(var = "MRG_PLUGINS", format = "json", default = "[]", merge = "append")]
+        plugins: Vec<String>,
+
+        #[env(flatten)]
+        cluster: ClusterConfig,
+    }
+
+    let config = MergeConfig::from_config().expect("should merge both files");
+
+    assert_eq!(config.name, "local");
+    assert_eq!(config.plugins, ["auth", "debug"]);
+    assert_eq!(config.cluster.hosts, ["a", "b", "c"]);
+
+    cleanup_file("merge_base.json");
+    cleanup_file("merge_local.json");
+}
+
+// ============================================================================
+// Environment Provenance
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct EnvProvenanceConfig {
+    name: String,
+    database: EnvProvenanceDatabase,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct EnvProvenanceDatabase {
+    port: u16,
+    password: u32,
+}
+
+#[test]
+fn test_env_type_mismatch_names_the_variable() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("config.toml");
+    fs::write(
+        &config,
+        "name = \"app\"\n\n[database]\nport = 5432\npassword = 1\n",
+    )
+    .unwrap();
+
+    let error = with_env(&[("ENVPROV_DATABASE__PORT", "not-a-port")], || {
+        let (_, origins) = procenv::ConfigBuilder::new()
+            .file(&config)
+            .env_prefix("ENVPROV_")
+            .env_separator("__")
+            .into_value()
+            .unwrap();
+
+        // The file no longer claims the overridden value
+        let env = origins
+            .env_source("database.port")
+            .expect("tracked env var");
+        assert_eq!(env.var, "ENVPROV_DATABASE__PORT");
+        assert_eq!(origins.get_file_source("database.port"), None);
+        assert!(origins.span_of("database.port").is_none());
+        assert_eq!(origins.get_file_source("name"), Some(config.clone()));
+
+        procenv::ConfigBuilder::new()
+            .file(&config)
+            .env_prefix("ENVPROV_")
+            .env_separator("__")
+            .build::<EnvProvenanceConfig>()
+            .unwrap_err()
+    });
+
+    let procenv::Error::File { source } = error else {
+        panic!("expected a file error, got {error:?}");
+    };
+    let procenv::file::FileError::EnvTypeMismatch {
+        ref var,
+        ref value,
+        ref dotenv_file,
+        ..
+    } = source
+    else {
+        panic!("expected an env type mismatch, got {source:?}");
+    };
+    assert_eq!(var, "ENVPROV_DATABASE__PORT");
+    assert_eq!(value.as_str(), Some("not-a-port"));
+    assert!(dotenv_file.is_none());
+    assert!(source.to_string().starts_with(
+        "type mismatch at `database.port` from ENVPROV_DATABASE__PORT=\"not-a-port\""
+    ));
+}
+
+#[cfg(feature = "dotenv")]
+#[test]
+fn test_env_type_mismatch_redacts_secrets_and_names_dotenv_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("config.toml");
+    let dotenv = dir.path().join(".env");
+    fs::write(&config, "name = \"app\"\n\n[database]\nport = 5432\n").unwrap();
+    fs::write(&dotenv, "ENVPROV_DB_PASSWORD=hunter2\n").unwrap();
+
+    let error = with_env(&[("ENVPROV_DB_PASSWORD", "hunter2")], || {
+        procenv::ConfigBuilder::new()
+            .file(&config)
+            .env_mapping("database.password", "ENVPROV_DB_PASSWORD")
+            .secret("database")
+            .dotenv_origin(dir.path().join("missing.env"))
+            .dotenv_origin(&dotenv)
+            .build::<EnvProvenanceConfig>()
+            .unwrap_err()
+    });
+
+    let procenv::Error::File { source } = error else {
+        panic!("expected a file error, got {error:?}");
+    };
+    let procenv::file::FileError::EnvTypeMismatch {
+        ref value,
+        ref dotenv_file,
+        ..
+    } = source
+    else {
+        panic!("expected an env type mismatch, got {source:?}");
+    };
+    assert!(value.is_redacted());
+    assert_eq!(
+        dotenv_file.as_deref(),
+        Some(dotenv.display().to_string().as_str())
+    );
+
+    let message = source.to_string();
+    assert!(!message.contains("hunter2"), "{message}");
+    assert!(
+        message.contains("ENVPROV_DB_PASSWORD=<redacted>"),
+        "{message}"
+    );
+    assert!(message.contains("expected u32"), "{message}");
+}
+
+// ============================================================================
+// Strict Mode
+// ============================================================================
+
+#[test]
+fn test_strict_rejects_unknown_keys_with_suggestions() {
+    cleanup_env(&["STRICT_PORT", "STRICT_PROT", "STRICT_DB_HOST"]);
+    cleanup_file("strict.toml");
+
+    write_file(
+        "strict.toml",
+        "name = \"app\"\n\n[databse]\nport = 5432\n\n[database]\nhost = \"localhost\"\n",
+    );
+
+    #[derive(EnvConfig)]
+    #[env_config(prefix = "STRICT_", strict)]
+    struct StrictDatabase {
+        #[env(var = "DB_HOST", default = "localhost")]
+        host: String,
+
+        #[env(var = "DB_PORT", default = "5432")]
+        port: u16,
+    }
+
+    #[derive(EnvConfig)]
+    #[env_config(
+        prefix = "STRICT_",
+        file = "/tmp/procenv_fmt_tests/strict.toml",
+        strict
+    )]
+    struct StrictConfig {
+        #[env(var = "NAME")]
+        name: String,
+
+        #[env(var = "PORT", default = "8080")]
+        port: u16,
+
+        #[env(flatten)]
+        database: StrictDatabase,
+    }
+
+    let error = with_env(&[("STRICT_PROT", "80"), ("STRICT_DB_HOST", "db")], || {
+        StrictConfig::from_config()
+            .err()
+            .expect("unknown keys should fail")
+    });
+
+    let procenv::Error::File { source } = error else {
+        panic!("expected a file error, got {error:?}");
+    };
+    let procenv::file::FileError::UnknownKeys { errors } = source else {
+        panic!("expected unknown keys, got {source:?}");
+    };
+    assert_eq!(errors.len(), 2, "{errors:?}");
+
+    let procenv::file::FileError::UnknownKey {
+        key, span, help, ..
+    } = &errors[0]
+    else {
+        panic!("expected an unknown file key, got {:?}", errors[0]);
+    };
+    assert_eq!(key, "databse.port");
+    assert_eq!(help.as_deref(), Some("did you mean `database.port`?"));
+    let span = span.expect("the key's value should be located");
+    assert_eq!(span.offset(), 31);
+
+    let procenv::file::FileError::UnknownEnvVar { var, help } = &errors[1] else {
+        panic!("expected an unknown env var, got {:?}", errors[1]);
+    };
+    assert_eq!(var, "STRICT_PROT");
+    assert_eq!(help.as_deref(), Some("did you mean `STRICT_PORT`?"));
+
+    cleanup_file("strict.toml");
+}
+
+#[test]
+fn test_known_keys_without_strict_lists_unknown_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("config.json");
+    fs::write(
+        &config,
+        r#"{"name": "app", "plugins": [{"any": 1}], "extra": [1, 2], "nested": {"a": {"b": 1}}}"#,
+    )
+    .unwrap();
+
+    let (value, origins) = procenv::ConfigBuilder::new()
+        .file(&config)
+        .known_keys(["name", "plugins"])
+        .into_value()
+        .unwrap();
+
+    assert_eq!(value["extra"], serde_json::json!([1, 2]));
+    assert_eq!(origins.unknown_keys(), ["extra", "nested.a.b"]);
+}
+
+// ============================================================================
+// Value Types
+// ============================================================================
+
+#[test]
+fn test_env_values_are_coerced_to_field_types() {
+    #[derive(EnvConfig)]
+    #[env_config(prefix = "VTYPES_")]
+    struct VtypesServer {
+        #[env(var = "HOST", default = "007")]
+        host: String,
+
+        #[env(var = "PORT", default = "8080")]
+        port: u16,
+    }
+
+    #[derive(EnvConfig)]
+    #[env_config(
+        prefix = "VTYPES_",
+        file_optional = "/tmp/procenv_fmt_tests/vtypes_missing.toml"
+    )]
+    struct VtypesConfig {
+        #[env(var = "VERSION")]
+        version: String,
+
+        #[env(var = "ZIP")]
+        zip: String,
+
+        #[env(var = "NAME")]
+        name: String,
+
+        #[env(var = "RATIO")]
+        ratio: f64,
+
+        #[env(var = "TAGS", format = "json")]
+        tags: Vec<String>,
+
+        #[env(flatten)]
+        server: VtypesServer,
+    }
+
+    let config = with_env(
+        &[
+            ("VTYPES_VERSION", "1.10"),
+            ("VTYPES_ZIP", "01234"),
+            ("VTYPES_NAME", "true"),
+            ("VTYPES_RATIO", "2"),
+            ("VTYPES_TAGS", r#"["a", "b"]"#),
+            ("VTYPES_PORT", "9090"),
+        ],
+        || VtypesConfig::from_config().unwrap(),
+    );
+
+    assert_eq!(config.version, "1.10");
+    assert_eq!(config.zip, "01234");
+    assert_eq!(config.name, "true");
+    assert_eq!(config.ratio, 2.0);
+    assert_eq!(config.tags, ["a", "b"]);
+    assert_eq!(config.server.host, "007");
+    assert_eq!(config.server.port, 9090);
+}
+
+#[test]
+fn test_env_list_items_are_coerced_to_item_types() {
+    #[derive(EnvConfig)]
+    #[env_config(
+        prefix = "VLIST_",
+        file_optional = "/tmp/procenv_fmt_tests/vlist_missing.toml"
+    )]
+    struct VlistConfig {
+        #[env(var = "TAGS", format = "json")]
+        tags: Vec<String>,
+
+        #[env(var = "PORTS", format = "json")]
+        ports: Vec<u16>,
+    }
+
+    let config = with_env(
+        &[("VLIST_TAGS", "01234, true"), ("VLIST_PORTS", "80, 0443")],
+        || VlistConfig::from_config().unwrap(),
+    );
+
+    assert_eq!(config.tags, ["01234", "true"]);
+    assert_eq!(config.ports, [80, 443]);
+}
+
+#[test]
+fn test_builder_value_types_and_guessing_fallback() {
+    use procenv::file::ValueType;
+
+    #[derive(Deserialize)]
+    struct Release {
+        version: String,
+        build: f64,
+        features: Vec<String>,
+    }
+
+    let (value, _) = with_env(
+        &[
+            ("VTBUILD_VERSION", "1.10"),
+            ("VTBUILD_BUILD", "1.10"),
+            ("VTBUILD_FEATURES", "tls, http2"),
+        ],
+        || {
+            procenv::ConfigBuilder::new()
+                .env_prefix("VTBUILD_")
+                .value_type("version", ValueType::String)
+                .value_type("features", ValueType::list(ValueType::String))
+                .into_value()
+                .unwrap()
+        },
+    );
+
+    // Paths without a type are still guessed
+    assert_eq!(value["build"], serde_json::json!(1.1));
+
+    let release: Release = serde_json::from_value(value).unwrap();
+    assert_eq!(release.version, "1.10");
+    assert_eq!(release.build, 1.1);
+    assert_eq!(release.features, ["tls", "http2"]);
+}
+
+// ============================================================================
+// Environment Paths
+// ============================================================================
+
+#[test]
+fn test_env_paths_index_lists_and_escape_separators() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("config.json");
+    fs::write(
+        &config,
+        r#"{"servers": [{"host": "a", "port": 1}, {"host": "b", "port": 2}]}"#,
+    )
+    .unwrap();
+
+    let (value, origins) = with_env(
+        &[
+            ("ENVPATH_SERVERS__1__HOST", "b2"),
+            ("ENVPATH_SERVERS__2__HOST", "c"),
+            ("ENVPATH_MAX_CONNECTIONS", "10"),
+            ("ENVPATH_LOG____LEVEL", "debug"),
+        ],
+        || {
+            procenv::ConfigBuilder::new()
+                .file(&config)
+                .env_prefix("ENVPATH_")
+                .env_separator("__")
+                .env_list_style(procenv::file::EnvListStyle::Indexed)
+                .into_value()
+                .unwrap()
+        },
+    );
+
+    assert_eq!(
+        value["servers"],
+        serde_json::json!([
+            {"host": "a", "port": 1},
+            {"host": "b2", "port": 2},
+            {"host": "c"}
+        ])
+    );
+    assert_eq!(value["max_connections"], serde_json::json!(10));
+    assert_eq!(value["log__level"], serde_json::json!("debug"));
+
+    let origin = origins.env_source("servers[1].host").unwrap();
+    assert_eq!(origin.var, "ENVPATH_SERVERS__1__HOST");
+    assert!(origins.env_source("servers[0].host").is_none());
+}
+
+#[test]
+fn test_env_numeric_segments_are_keys_by_default() {
+    let (value, origins) = with_env(
+        &[("ENVNUM_HTTP_2", "on"), ("ENVNUM_DB_REPLICA_1", "replica")],
+        || {
+            procenv::ConfigBuilder::new()
+                .env_prefix("ENVNUM_")
+                .into_value()
+                .unwrap()
+        },
+    );
+
+    assert_eq!(value["http"], serde_json::json!({"2": "on"}));
+    assert_eq!(
+        value["db"],
+        serde_json::json!({"replica": {"1": "replica"}})
+    );
+
+    let origin = origins.env_source("db.replica.1").unwrap();
+    assert_eq!(origin.var, "ENVNUM_DB_REPLICA_1");
+}
+
+#[test]
+fn test_env_path_conflicts_are_errors() {
+    let error = with_env(&[("ENVCONF_DB", "x"), ("ENVCONF_DB_HOST", "y")], || {
+        procenv::ConfigBuilder::new()
+            .env_prefix("ENVCONF_")
+            .into_value()
+            .unwrap_err()
+    });
+
+    let procenv::file::FileError::EnvConflict {
+        path_str,
+        var,
+        conflict,
+        help,
+    } = error
+    else {
+        panic!("expected an env conflict, got {error:?}");
+    };
+    assert_eq!(path_str, "db.host");
+    assert_eq!(var, "ENVCONF_DB_HOST");
+    assert_eq!(conflict.to_string(), "`db` is a string, not an object");
+    assert_eq!(
+        help.as_deref(),
+        Some("ENVCONF_DB sets `db`; remove one of the two variables")
+    );
+
+    let error = with_env(&[("ENVGAP_SERVERS_2_HOST", "c")], || {
+        procenv::ConfigBuilder::new()
+            .env_prefix("ENVGAP_")
+            .env_list_style(procenv::file::EnvListStyle::Indexed)
+            .into_value()
+            .unwrap_err()
+    });
+
+    assert_eq!(
+        error.to_string(),
+        "ENVGAP_SERVERS_2_HOST cannot set `servers[2].host`: \
+         `servers` has 0 items, so index 2 would leave a gap"
+    );
+}
+
+#[test]
+fn test_env_key_case_and_delimited_lists() {
+    use procenv::file::{EnvKeyCase, EnvListStyle, ValueType};
+
+    let (value, _) = with_env(
+        &[
+            ("ENVSTYLE_logLevel", "warn"),
+            ("ENVSTYLE_hosts", "a; b"),
+            ("ENVSTYLE_ports_0", "80"),
+        ],
+        || {
+            procenv::ConfigBuilder::new()
+                .env_prefix("ENVSTYLE_")
+                .env_key_case(EnvKeyCase::Preserve)
+                .env_list_style(EnvListStyle::Delimited(";"))
+                .value_type("hosts", ValueType::list(ValueType::String))
+                .into_value()
+                .unwrap()
+        },
+    );
+
+    assert_eq!(value["logLevel"], serde_json::json!("warn"));
+    assert_eq!(value["hosts"], serde_json::json!(["a", "b"]));
+    assert_eq!(value["ports"], serde_json::json!({"0": 80}));
+}
@@ -0,0 +1,73 @@
+//! Tests for the process-wide `global()` accessor.
+
+#![allow(clippy::pedantic)]
+
+use procenv::{EnvConfig, FailurePolicy, Global};
+use serial_test::serial;
+use std::env;
+use std::sync::Arc;
+
+// ============================================================================
+// Derived Accessor Tests
+// ============================================================================
+
+#[derive(EnvConfig)]
+#[env_config(global)]
+struct GlobalConfig {
+    #[env(var = "GLOBAL_TEST_PORT", default = "8080")]
+    port: u16,
+}
+
+#[derive(EnvConfig)]
+#[env_config(global = "panic")]
+struct MissingGlobalConfig {
+    #[env(var = "GLOBAL_TEST_MISSING_URL")]
+    url: String,
+}
+
+#[test]
+#[serial]
+fn test_global_loads_on_first_access() {
+    // SAFETY: Tests run serially and don't have concurrent access to env vars
+    unsafe { env::set_var("GLOBAL_TEST_PORT", "9000") };
+    let port = GlobalConfig::global().port;
+
+    // Later changes are not picked up without a watcher
+    // SAFETY: Tests run serially and don't have concurrent access to env vars
+    unsafe { env::set_var("GLOBAL_TEST_PORT", "9001") };
+    assert_eq!(port, 9000);
+    assert_eq!(GlobalConfig::global().port, 9000);
+    assert!(Arc::ptr_eq(
+        &GlobalConfig::global(),
+        &GlobalConfig::global()
+    ));
+
+    // SAFETY: Tests run serially and don't have concurrent access to env vars
+    unsafe { env::remove_var("GLOBAL_TEST_PORT") };
+}
+
+#[test]
+#[serial]
+fn test_global_panics_with_rendered_error() {
+    let panic = std::panic::catch_unwind(|| MissingGlobalConfig::global().url.clone()).unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+
+    assert!(message.contains("GLOBAL_TEST_MISSING_URL"), "{message}");
+    assert!(message.contains("procenv::missing_var"), "{message}");
+}
+
+// ============================================================================
+// Manual Global Tests
+// ============================================================================
+
+static MANUAL: Global<GlobalConfig> =
+    Global::with_policy(GlobalConfig::from_env, FailurePolicy::Exit);
+
+#[test]
+#[serial]
+fn test_manual_global() {
+    assert!(!MANUAL.is_loaded());
+    assert_eq!(MANUAL.get().port, 8080);
+    assert!(MANUAL.is_loaded());
+    assert!(format!("{MANUAL:?}").contains("Exit"));
+}
@@ -1,6 +1,6 @@
 //! Malformed file format tests.
 //!
-//! Tests for handling invalid/malformed TOML, JSON, and YAML files.
+//! Tests for handling invalid/malformed TOML, JSON, JSON5, and YAML files.
 
 #![allow(clippy::pedantic)]
 #![cfg(feature = "file-all")]
@@ -212,6 +212,62 @@ fn test_malformed_json_null_value() {
     cleanup_file("malformed.json");
 }
 
+// ============================================================================
+// Malformed JSON5 Tests
+// ============================================================================
+
+#[derive(EnvConfig, Deserialize)]
+#[env_config(
+    prefix = "MAL_JSON5_",
+    file_optional = "/tmp/procenv_malformed_tests/malformed.jsonc"
+)]
+struct MalformedJson5Config {
+    #[env(var = "NAME", default = "default")]
+    name: String,
+
+    #[env(var = "COUNT", default = "0")]
+    count: i32,
+}
+
+#[test]
+#[serial]
+fn test_malformed_json5_syntax_error() {
+    cleanup_env(&["MAL_JSON5_NAME", "MAL_JSON5_COUNT"]);
+    cleanup_file("malformed.jsonc");
+
+    // Invalid JSON5: missing value
+    let content = "{\n  // comment\n  name: 'test',\n  count: ,\n}";
+    write_file("malformed.jsonc", content);
+
+    let result = MalformedJson5Config::from_config();
+    let Err(procenv::Error::File {
+        source: procenv::file::FileError::Parse { format, span, .. },
+    }) = result
+    else {
+        panic!("Should error with a located JSON5 parse error");
+    };
+    assert_eq!(format, "JSON5");
+    assert_eq!(span.offset(), content.find(" ,").unwrap() + 1);
+
+    cleanup_file("malformed.jsonc");
+}
+
+#[test]
+#[serial]
+fn test_malformed_json5_wrong_type() {
+    cleanup_env(&["MAL_JSON5_NAME", "MAL_JSON5_COUNT"]);
+    cleanup_file("malformed.jsonc");
+
+    // Valid JSON5 but wrong type
+    let content = "{\n  name: 'test', // trailing comma below\n  count: 'not_a_number',\n}";
+    write_file("malformed.jsonc", content);
+
+    let result = MalformedJson5Config::from_config();
+    assert!(result.is_err(), "Should error on type mismatch");
+
+    cleanup_file("malformed.jsonc");
+}
+
 // ============================================================================
 // Malformed YAML Tests
 // ============================================================================
@@ -413,3 +469,50 @@ fn test_json_with_nested_unknown() {
 
     cleanup_file("malformed.json");
 }
+
+// ============================================================================
+// Format Sniffing Tests
+// ============================================================================
+
+#[test]
+fn test_sniff_failure_lists_attempts() {
+    write_file("unsniffable", "port = [1, 2\n");
+
+    let error = procenv::ConfigBuilder::new()
+        .sniff_formats(true)
+        .file(format!("{BASE_DIR}/unsniffable"))
+        .into_value()
+        .unwrap_err();
+
+    let procenv::file::FileError::UndetectedFormat {
+        path,
+        help,
+        attempts,
+    } = &error
+    else {
+        panic!("expected an undetected format error, got {error:?}");
+    };
+    assert_eq!(path, &format!("{BASE_DIR}/unsniffable"));
+    assert!(help.starts_with("tried JSON, JSON5, TOML, YAML"), "{help}");
+    assert_eq!(attempts.len(), 4);
+
+    let formats: Vec<&str> = attempts
+        .iter()
+        .map(|attempt| match attempt {
+            procenv::file::FileError::Parse { format, .. }
+            | procenv::file::FileError::ParseNoSpan { format, .. } => *format,
+            other => panic!("expected a parse error, got {other:?}"),
+        })
+        .collect();
+    assert_eq!(formats, ["JSON", "JSON5", "TOML", "YAML"]);
+
+    // Every attempt is rendered under the main diagnostic
+    let rendered = format!("{:?}", miette::Report::new(error));
+    assert!(
+        rendered.contains("could not detect the format"),
+        "{rendered}"
+    );
+    assert!(rendered.contains("TOML"), "{rendered}");
+
+    cleanup_file("unsniffable");
+}
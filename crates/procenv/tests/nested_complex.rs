@@ -4,6 +4,7 @@
 
 #![allow(clippy::pedantic)]
 #![allow(clippy::manual_strip)]
+#![allow(clippy::approx_constant)]
 
 use procenv::EnvConfig;
 use serial_test::serial;
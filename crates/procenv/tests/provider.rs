@@ -322,6 +322,56 @@ fn test_env_provider_missing_key() {
     assert!(result.is_none());
 }
 
+// ============================================================================
+// DotenvProvider Tests
+// ============================================================================
+
+#[test]
+#[cfg(feature = "dotenv")]
+fn test_loader_layered_dotenv_files() {
+    use procenv::dotenv::DotenvFile;
+
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join(".env");
+    let local = dir.path().join(".env.local");
+    std::fs::write(&base, "LAYERED_PORT=8080\nLAYERED_HOST=localhost\n").unwrap();
+    std::fs::write(&local, "LAYERED_PORT=9090\n").unwrap();
+
+    let mut loader = ConfigLoader::new()
+        .with_dotenv_files([DotenvFile::optional(&local), DotenvFile::required(&base)])
+        .unwrap();
+
+    let port = loader.get("LAYERED_PORT").unwrap();
+    assert_eq!(port.value, "9090");
+    assert_eq!(port.source.to_source(), Source::DotenvFile(Some(local)));
+
+    let host = loader.get("LAYERED_HOST").unwrap();
+    assert_eq!(host.source.to_source(), Source::DotenvFile(Some(base)));
+}
+
+#[test]
+#[cfg(feature = "dotenv")]
+fn test_loader_layered_dotenv_reports_all_files() {
+    use procenv::DotenvError;
+    use procenv::dotenv::DotenvFile;
+
+    let dir = tempfile::tempdir().unwrap();
+    let malformed = dir.path().join(".env");
+    std::fs::write(&malformed, "OK=1\n=missing name\n").unwrap();
+
+    let Err(error) = ConfigLoader::new().with_dotenv_files([
+        DotenvFile::required(dir.path().join(".env.missing")),
+        DotenvFile::required(&malformed),
+    ]) else {
+        panic!("expected the layered load to fail");
+    };
+
+    let DotenvError::Files { errors } = &error else {
+        panic!("expected errors for both files, got {error:?}");
+    };
+    assert_eq!(errors.len(), 2);
+}
+
 // ============================================================================
 // Integration with Existing API
 // ============================================================================
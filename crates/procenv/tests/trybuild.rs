@@ -18,6 +18,7 @@ fn compile_pass() {
     t.pass("tests/compile_pass/all_features.rs");
     t.pass("tests/compile_pass/source_attribution.rs");
     t.pass("tests/compile_pass/source_attribution_nested.rs");
+    #[cfg(feature = "dotenv")]
     t.pass("tests/compile_pass/prefix_support.rs");
     t.pass("tests/compile_pass/env_example_gen.rs");
     t.pass("tests/compile_pass/various_types.rs");
@@ -0,0 +1,46 @@
+//! Hot reload tests that change the working directory.
+//!
+//! The working directory is process-wide, so these tests live in their own
+//! test binary instead of `watch_tests.rs`.
+
+#![allow(clippy::pedantic)]
+#![cfg(feature = "watch")]
+
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+use procenv::{EnvConfig, WatchBuilder};
+use tempfile::tempdir;
+
+#[derive(EnvConfig, Clone)]
+#[env_config(dotenv = ".env")]
+struct ParentDotenvConfig {
+    #[env(var = "WATCH_CWD_PORT", default = "8080")]
+    port: u16,
+}
+
+#[test]
+fn test_dotenv_found_in_parent_dir_is_watched() {
+    let dir = tempdir().unwrap();
+    let dotenv_path = dir.path().join(".env");
+    fs::write(&dotenv_path, "WATCH_CWD_PORT=9000\n").unwrap();
+
+    // Run from a subdirectory: the .env is only found by searching parents
+    let subdir = dir.path().join("app");
+    fs::create_dir(&subdir).unwrap();
+    std::env::set_current_dir(&subdir).unwrap();
+
+    let handle =
+        ParentDotenvConfig::watch_with(WatchBuilder::new().debounce(Duration::from_millis(50)))
+            .unwrap();
+    assert_eq!(handle.get().port, 9000);
+
+    thread::sleep(Duration::from_millis(100));
+    fs::write(&dotenv_path, "WATCH_CWD_PORT=9001\n").unwrap();
+    thread::sleep(Duration::from_millis(400));
+
+    assert_eq!(handle.get().port, 9001);
+
+    handle.stop();
+}
@@ -7,12 +7,14 @@
 #![cfg(feature = "watch")]
 
 use std::fs;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-use procenv::{ConfigSources, EnvConfig, WatchBuilder, WatchError};
+use procenv::{
+    ChangeTrigger, ConfigSources, DeletePolicy, EnvConfig, WatchBackend, WatchBuilder, WatchError,
+};
 use tempfile::tempdir;
 
 // ============================================================================
@@ -401,6 +403,896 @@ fn test_watch_multiple_files() {
     handle.stop();
 }
 
+#[test]
+fn test_watch_dir_fragment_changes() {
+    let dir = tempdir().unwrap();
+    let conf_d = dir.path().join("conf.d");
+    fs::create_dir(&conf_d).unwrap();
+    fs::write(conf_d.join("10-base.toml"), "port = 8080").unwrap();
+
+    let trigger = Arc::new(Mutex::new(Vec::new()));
+    let trigger_clone = trigger.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_dir(&conf_d, "*.toml")
+        .debounce(Duration::from_millis(50))
+        .on_change(move |change| {
+            trigger_clone.lock().unwrap().push(change.trigger);
+        })
+        .build_sync(|| Ok((SimpleConfig::default(), ConfigSources::default())))
+        .unwrap();
+
+    // A new fragment triggers a reload
+    thread::sleep(Duration::from_millis(100));
+    fs::write(conf_d.join("20-override.toml"), "port = 9090").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(trigger.lock().unwrap().len(), 1);
+
+    // Non-matching files are ignored
+    fs::write(conf_d.join("notes.txt"), "ignored").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(trigger.lock().unwrap().len(), 1);
+
+    // Renaming and removing fragments trigger reloads
+    fs::rename(
+        conf_d.join("20-override.toml"),
+        conf_d.join("30-override.toml"),
+    )
+    .unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(trigger.lock().unwrap().len(), 2);
+
+    fs::remove_file(conf_d.join("30-override.toml")).unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(trigger.lock().unwrap().len(), 3);
+
+    handle.stop();
+}
+
+#[test]
+fn test_changes_are_coalesced() {
+    let dir = tempdir().unwrap();
+    let first = dir.path().join("first.toml");
+    let second = dir.path().join("second.toml");
+    fs::write(&first, "port = 8080").unwrap();
+    fs::write(&second, "port = 8080").unwrap();
+
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let changes_clone = changes.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_files([&first, &second])
+        .debounce(Duration::from_millis(100))
+        .on_change(move |change| {
+            changes_clone.lock().unwrap().push(change.changed_files);
+        })
+        .build_sync(|| Ok((SimpleConfig::default(), ConfigSources::default())))
+        .unwrap();
+
+    thread::sleep(Duration::from_millis(100));
+    fs::write(&first, "port = 9090").unwrap();
+    fs::write(&second, "port = 9090").unwrap();
+    thread::sleep(Duration::from_millis(500));
+
+    let changes = changes.lock().unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].len(), 2);
+
+    handle.stop();
+}
+
+#[test]
+fn test_min_interval_limits_reloads() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let reload_count = Arc::new(AtomicU32::new(0));
+    let reload_count_clone = reload_count.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_file(&config_path)
+        .debounce(Duration::from_millis(20))
+        .min_interval(Duration::from_secs(1))
+        .build_sync(move || {
+            reload_count_clone.fetch_add(1, Ordering::SeqCst);
+            Ok((SimpleConfig::default(), ConfigSources::default()))
+        })
+        .unwrap();
+
+    thread::sleep(Duration::from_millis(100));
+
+    // A rewrite loop: the first burst reloads, later ones wait for the interval
+    for i in 0..5 {
+        fs::write(&config_path, format!("port = {}", 9000 + i)).unwrap();
+        thread::sleep(Duration::from_millis(100));
+    }
+    assert_eq!(reload_count.load(Ordering::SeqCst), 2);
+
+    // The held back changes are reloaded once the interval elapsed
+    thread::sleep(Duration::from_millis(1200));
+    assert_eq!(reload_count.load(Ordering::SeqCst), 3);
+
+    handle.stop();
+}
+
+#[test]
+fn test_min_interval_ignores_noop_writes() {
+    let dir = tempdir().unwrap();
+    let dotenv_path = dir.path().join(".env");
+    fs::write(&dotenv_path, "WATCH_NOOP_PORT=8080\n").unwrap();
+
+    let reload_count = Arc::new(AtomicU32::new(0));
+    let reload_count_clone = reload_count.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_dotenv(&dotenv_path)
+        .debounce(Duration::from_millis(20))
+        .min_interval(Duration::from_secs(1))
+        .build_sync(move || {
+            reload_count_clone.fetch_add(1, Ordering::SeqCst);
+            Ok((SimpleConfig::default(), ConfigSources::default()))
+        })
+        .unwrap();
+
+    thread::sleep(Duration::from_millis(100));
+
+    // A comment-only write changes nothing, so the next edit isn't held back
+    fs::write(&dotenv_path, "# port\nWATCH_NOOP_PORT=8080\n").unwrap();
+    thread::sleep(Duration::from_millis(150));
+    assert_eq!(reload_count.load(Ordering::SeqCst), 1);
+
+    fs::write(&dotenv_path, "# port\nWATCH_NOOP_PORT=9090\n").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(reload_count.load(Ordering::SeqCst), 2);
+
+    handle.stop();
+}
+
+#[test]
+fn test_reload_stats_and_backoff() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let should_fail = Arc::new(AtomicBool::new(false));
+    let should_fail_clone = should_fail.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_file(&config_path)
+        .debounce(Duration::from_millis(20))
+        .backoff(Duration::from_millis(600), Duration::from_secs(5))
+        .build_sync(move || {
+            if should_fail_clone.load(Ordering::SeqCst) {
+                Err(procenv::Error::missing("PORT"))
+            } else {
+                Ok((SimpleConfig::default(), ConfigSources::default()))
+            }
+        })
+        .unwrap();
+
+    assert_eq!(handle.stats().attempts, 0);
+
+    // A failed reload is counted
+    thread::sleep(Duration::from_millis(100));
+    should_fail.store(true, Ordering::SeqCst);
+    fs::write(&config_path, "port = 9090").unwrap();
+    thread::sleep(Duration::from_millis(200));
+
+    let stats = handle.stats();
+    assert_eq!(stats.attempts, 1);
+    assert_eq!(stats.failures, 1);
+    assert_eq!(stats.consecutive_failures, 1);
+    assert!(stats.last_failure.is_some());
+
+    // The next reload waits for the backoff delay
+    should_fail.store(false, Ordering::SeqCst);
+    fs::write(&config_path, "port = 9091").unwrap();
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(handle.stats().attempts, 1);
+
+    thread::sleep(Duration::from_millis(600));
+    let stats = handle.stats();
+    assert_eq!(stats.attempts, 2);
+    assert_eq!(stats.consecutive_failures, 0);
+    assert!(stats.last_success.is_some());
+
+    handle.stop();
+}
+
+// ============================================================================
+// Delete Policy Tests
+// ============================================================================
+
+/// Read `port = N` from a file, falling back to the default if it is missing.
+#[allow(clippy::result_large_err)]
+fn read_port(path: &std::path::Path) -> Result<(u16, ConfigSources), procenv::Error> {
+    let port = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.trim().strip_prefix("port = ")?.parse().ok())
+        .unwrap_or(8080);
+    Ok((port, ConfigSources::default()))
+}
+
+/// Values recorded by a callback.
+type Recorded<T> = Arc<Mutex<Vec<T>>>;
+
+/// Start a watcher on `path` with the given delete policy, recording triggers and errors.
+#[allow(clippy::result_large_err)]
+fn watch_with_policy(
+    path: &std::path::Path,
+    policy: DeletePolicy,
+) -> (
+    procenv::ConfigHandle<u16>,
+    Recorded<ChangeTrigger>,
+    Recorded<WatchError>,
+) {
+    let triggers = Arc::new(Mutex::new(Vec::new()));
+    let errors = Arc::new(Mutex::new(Vec::new()));
+    let triggers_clone = triggers.clone();
+    let errors_clone = errors.clone();
+    let reload_path = path.to_path_buf();
+
+    let handle = WatchBuilder::<u16>::new()
+        .watch_file(path)
+        .debounce(Duration::from_millis(50))
+        .on_delete(policy)
+        .on_change(move |change| triggers_clone.lock().unwrap().push(change.trigger))
+        .on_error(move |err| errors_clone.lock().unwrap().push(err))
+        .build_sync(move || read_port(&reload_path))
+        .unwrap();
+
+    (handle, triggers, errors)
+}
+
+#[test]
+fn test_delete_reload_falls_back_and_rewatches() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "port = 9000").unwrap();
+
+    let (handle, triggers, _) = watch_with_policy(&path, DeletePolicy::Reload);
+    assert_eq!(*handle.get(), 9000);
+
+    // Deleting an optional file reloads with defaults
+    thread::sleep(Duration::from_millis(100));
+    fs::remove_file(&path).unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(*handle.get(), 8080);
+    assert!(matches!(
+        triggers.lock().unwrap().last(),
+        Some(ChangeTrigger::FileDeleted(_))
+    ));
+
+    // The recreated file is watched again, including later edits
+    fs::write(&path, "port = 9001").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(*handle.get(), 9001);
+
+    fs::write(&path, "port = 9002").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(*handle.get(), 9002);
+
+    handle.stop();
+}
+
+#[test]
+fn test_delete_keep_last() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "port = 9000").unwrap();
+
+    let (handle, triggers, errors) = watch_with_policy(&path, DeletePolicy::KeepLast);
+
+    thread::sleep(Duration::from_millis(100));
+    fs::remove_file(&path).unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(*handle.get(), 9000);
+    assert!(triggers.lock().unwrap().is_empty());
+
+    fs::write(&path, "port = 9001").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(*handle.get(), 9001);
+    assert!(errors.lock().unwrap().is_empty());
+
+    handle.stop();
+}
+
+#[test]
+fn test_delete_fail_stops_watcher() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "port = 9000").unwrap();
+
+    let (handle, _, errors) = watch_with_policy(&path, DeletePolicy::Fail);
+
+    thread::sleep(Duration::from_millis(100));
+    fs::remove_file(&path).unwrap();
+    thread::sleep(Duration::from_millis(300));
+
+    assert!(!handle.is_running());
+    assert_eq!(*handle.get(), 9000);
+    assert!(matches!(
+        errors.lock().unwrap().first(),
+        Some(WatchError::FileDeleted { .. })
+    ));
+}
+
+#[test]
+fn test_delete_wait_for_recreate() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "port = 9000").unwrap();
+
+    let policy = DeletePolicy::WaitForRecreate {
+        timeout: Duration::from_millis(500),
+    };
+    let (handle, _, errors) = watch_with_policy(&path, policy);
+
+    // Recreated within the timeout: reloaded, no error
+    thread::sleep(Duration::from_millis(100));
+    fs::remove_file(&path).unwrap();
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(*handle.get(), 9000);
+    fs::write(&path, "port = 9001").unwrap();
+    thread::sleep(Duration::from_millis(500));
+    assert_eq!(*handle.get(), 9001);
+    assert!(errors.lock().unwrap().is_empty());
+
+    // Still missing after the timeout: reported, last config kept
+    fs::remove_file(&path).unwrap();
+    thread::sleep(Duration::from_millis(800));
+    assert_eq!(*handle.get(), 9001);
+    assert!(matches!(
+        errors.lock().unwrap().as_slice(),
+        [WatchError::FileDeleted { .. }]
+    ));
+    assert!(handle.is_running());
+
+    // A late recreation is still picked up
+    fs::write(&path, "port = 9002").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(*handle.get(), 9002);
+
+    handle.stop();
+}
+
+#[test]
+fn test_optional_file_in_missing_directory() {
+    let dir = tempdir().unwrap();
+    let config_dir = dir.path().join("conf.d").join("local");
+    let path = config_dir.join("config.toml");
+
+    let (handle, triggers, errors) = watch_with_policy(&path, DeletePolicy::Reload);
+    assert_eq!(*handle.get(), 8080);
+    assert!(handle.is_running());
+
+    // The watch follows the directories down as they are created
+    thread::sleep(Duration::from_millis(100));
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(&path, "port = 9000").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(*handle.get(), 9000);
+    assert!(triggers.lock().unwrap().iter().all(|trigger| {
+        trigger
+            .file_path()
+            .is_some_and(|p| p.ends_with("config.toml"))
+    }));
+
+    fs::write(&path, "port = 9001").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(*handle.get(), 9001);
+    assert!(errors.lock().unwrap().is_empty());
+
+    handle.stop();
+}
+
+#[test]
+fn test_poll_backend_detects_changes() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let backend = WatchBackend::Poll {
+        interval: Duration::from_millis(50),
+    };
+    let reload_count = Arc::new(AtomicU32::new(0));
+    let reload_count_clone = reload_count.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_file(&config_path)
+        .backend(backend)
+        .debounce(Duration::from_millis(50))
+        .on_change(move |_| {
+            reload_count_clone.fetch_add(1, Ordering::SeqCst);
+        })
+        .build_sync(|| Ok((SimpleConfig::default(), ConfigSources::default())))
+        .unwrap();
+
+    assert_eq!(handle.backend(), backend);
+
+    // Same length, so only the content hash can tell the versions apart
+    thread::sleep(Duration::from_millis(150));
+    fs::write(&config_path, "port = 9090").unwrap();
+    thread::sleep(Duration::from_millis(400));
+    assert_eq!(reload_count.load(Ordering::SeqCst), 1);
+
+    handle.stop();
+}
+
+#[test]
+fn test_auto_backend_reported() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_file(&config_path)
+        .build_sync(|| Ok((SimpleConfig::default(), ConfigSources::default())))
+        .unwrap();
+
+    // Auto resolves to the backend actually in use
+    assert_ne!(handle.backend(), WatchBackend::Auto);
+
+    handle.stop();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_reload_on_sighup_without_files() {
+    use procenv::watch::SIGHUP;
+
+    let reload_count = Arc::new(AtomicU32::new(0));
+    let reload_count_clone = reload_count.clone();
+    let trigger = Arc::new(Mutex::new(Vec::new()));
+    let trigger_clone = trigger.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .reload_on_signal(SIGHUP)
+        .on_change(move |change| {
+            trigger_clone.lock().unwrap().push(change.trigger);
+        })
+        .build_sync(move || {
+            reload_count_clone.fetch_add(1, Ordering::SeqCst);
+            Ok((SimpleConfig::default(), ConfigSources::default()))
+        })
+        .unwrap();
+
+    let status = std::process::Command::new("kill")
+        .args(["-HUP", &std::process::id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(reload_count.load(Ordering::SeqCst), 2);
+    assert_eq!(*trigger.lock().unwrap(), [ChangeTrigger::Signal(SIGHUP)]);
+
+    handle.stop();
+}
+
+/// Swap a Kubernetes-style `..data` symlink to a new timestamped directory.
+#[cfg(unix)]
+fn swap_configmap(dir: &std::path::Path, old: &str, new: &str, contents: &str) {
+    use std::os::unix::fs::symlink;
+
+    fs::create_dir(dir.join(new)).unwrap();
+    fs::write(dir.join(new).join("config.toml"), contents).unwrap();
+    symlink(new, dir.join("..data_tmp")).unwrap();
+    fs::rename(dir.join("..data_tmp"), dir.join("..data")).unwrap();
+    fs::remove_dir_all(dir.join(old)).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_configmap_symlink_swap() {
+    use std::os::unix::fs::symlink;
+
+    // Layout of a mounted ConfigMap:
+    //   config.toml -> ..data/config.toml
+    //   ..data -> ..2024_01
+    //   ..2024_01/config.toml
+    let dir = tempdir().unwrap();
+    let mount = dir.path();
+    fs::create_dir(mount.join("..2024_01")).unwrap();
+    fs::write(mount.join("..2024_01/config.toml"), "port = 8080").unwrap();
+    symlink("..2024_01", mount.join("..data")).unwrap();
+    symlink("..data/config.toml", mount.join("config.toml")).unwrap();
+
+    let config_path = mount.join("config.toml");
+    let reload_path = config_path.clone();
+    let trigger = Arc::new(Mutex::new(Vec::new()));
+    let trigger_clone = trigger.clone();
+
+    let handle = WatchBuilder::<u16>::new()
+        .watch_file(&config_path)
+        .debounce(Duration::from_millis(50))
+        .on_change(move |change| {
+            trigger_clone.lock().unwrap().push(change.trigger);
+        })
+        .build_sync(move || {
+            let content = fs::read_to_string(&reload_path).unwrap_or_default();
+            let port = content
+                .trim()
+                .strip_prefix("port = ")
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(0);
+            Ok((port, ConfigSources::default()))
+        })
+        .unwrap();
+
+    assert_eq!(*handle.get(), 8080);
+
+    thread::sleep(Duration::from_millis(100));
+    swap_configmap(mount, "..2024_01", "..2024_02", "port = 9090");
+    thread::sleep(Duration::from_millis(400));
+    assert_eq!(*handle.get(), 9090);
+    assert!(matches!(
+        trigger.lock().unwrap().last(),
+        Some(ChangeTrigger::FileModified(path)) if *path == config_path
+    ));
+
+    // The new target keeps being watched after the swap
+    swap_configmap(mount, "..2024_02", "..2024_03", "port = 7070");
+    thread::sleep(Duration::from_millis(400));
+    assert_eq!(*handle.get(), 7070);
+
+    handle.stop();
+}
+
+// ============================================================================
+// Derive Integration Tests
+// ============================================================================
+
+#[derive(EnvConfig, Clone)]
+#[env_config(file_optional = "/tmp/procenv_watch_tests/derive_watch.json")]
+struct DeriveWatchConfig {
+    #[env(var = "WATCH_DERIVE_PORT", default = "8080")]
+    port: u16,
+}
+
+#[test]
+fn test_derive_watch_registers_files() {
+    let dir = std::path::Path::new("/tmp/procenv_watch_tests");
+    fs::create_dir_all(dir).unwrap();
+    let path = dir.join("derive_watch.json");
+    fs::write(&path, r#"{"port": 9000}"#).unwrap();
+
+    let reload_count = Arc::new(AtomicU32::new(0));
+    let reload_count_clone = reload_count.clone();
+
+    let handle = DeriveWatchConfig::watch_with(
+        WatchBuilder::new()
+            .debounce(Duration::from_millis(50))
+            .on_change(move |_| {
+                reload_count_clone.fetch_add(1, Ordering::SeqCst);
+            }),
+    )
+    .unwrap();
+
+    assert_eq!(handle.get().port, 9000);
+
+    thread::sleep(Duration::from_millis(100));
+    fs::write(&path, r#"{"port": 9001}"#).unwrap();
+    thread::sleep(Duration::from_millis(400));
+
+    assert!(reload_count.load(Ordering::SeqCst) >= 1);
+    assert_eq!(handle.get().port, 9001);
+
+    handle.stop();
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_derive_watch_without_sources_fails() {
+    // No files, no dotenv: nothing to watch unless the builder adds something
+    let result = SimpleConfig::watch();
+    assert!(matches!(result, Err(WatchError::InitFailed { .. })));
+}
+
+#[derive(EnvConfig, Clone)]
+#[env_config(file_optional = "/tmp/procenv_watch_tests/derive_audit.json")]
+struct DeriveAuditConfig {
+    #[env(var = "WATCH_AUDIT_PORT", default = "8080")]
+    port: u16,
+
+    #[env(var = "WATCH_AUDIT_API_KEY", secret, default = "unset")]
+    api_key: String,
+}
+
+#[test]
+fn test_audit_log_records_masked_changes() {
+    let dir = std::path::Path::new("/tmp/procenv_watch_tests");
+    fs::create_dir_all(dir).unwrap();
+    let path = dir.join("derive_audit.json");
+    fs::write(&path, r#"{"port": 9000, "api_key": "hunter2"}"#).unwrap();
+
+    let log_dir = tempdir().unwrap();
+    let log_path = log_dir.path().join("audit.jsonl");
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let changes_clone = changes.clone();
+
+    let handle = DeriveAuditConfig::watch_with(
+        WatchBuilder::new()
+            .debounce(Duration::from_millis(50))
+            .audit_log(&log_path)
+            .on_change(move |change| changes_clone.lock().unwrap().push(change.changed_fields)),
+    )
+    .unwrap();
+
+    // An applied reload lists the changed port, but not the secret
+    thread::sleep(Duration::from_millis(100));
+    fs::write(&path, r#"{"port": 9001, "api_key": "hunter3"}"#).unwrap();
+    thread::sleep(Duration::from_millis(400));
+    assert_eq!(handle.get().port, 9001);
+    assert_eq!(handle.get().api_key, "hunter3");
+    assert_eq!(*changes.lock().unwrap(), [vec!["port".to_string()]]);
+
+    // A rejected reload is logged too
+    fs::write(&path, r#"{"port": "not a port"}"#).unwrap();
+    thread::sleep(Duration::from_millis(400));
+    handle.stop();
+    fs::remove_file(&path).ok();
+
+    let log = fs::read_to_string(&log_path).unwrap();
+    assert!(!log.contains("hunter"));
+
+    let records: Vec<serde_json::Value> = log
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 2, "{log}");
+
+    assert_eq!(records[0]["event"], "applied");
+    assert_eq!(records[0]["trigger"]["kind"], "file_modified");
+    let field = &records[0]["changed_fields"][0];
+    assert_eq!(field["name"], "port");
+    assert_eq!(field["old_value"], "9000");
+    assert_eq!(field["new_value"], "9001");
+    assert_eq!(field["source"]["kind"], "config_file");
+    assert_eq!(
+        records[0]["sources"]["api_key"]["var_name"],
+        "WATCH_AUDIT_API_KEY"
+    );
+
+    assert_eq!(records[1]["event"], "rejected");
+    assert!(records[1]["timestamp"].as_str().unwrap().ends_with('Z'));
+}
+
+#[derive(EnvConfig, Clone)]
+#[env_config(file_optional = "/tmp/procenv_watch_tests/derive_global.json", global)]
+struct DeriveGlobalConfig {
+    #[env(var = "WATCH_GLOBAL_PORT", default = "8080")]
+    port: u16,
+}
+
+#[test]
+fn test_watch_global_serves_reloads() {
+    let dir = std::path::Path::new("/tmp/procenv_watch_tests");
+    fs::create_dir_all(dir).unwrap();
+    let path = dir.join("derive_global.json");
+    fs::write(&path, r#"{"port": 9000}"#).unwrap();
+
+    let handle = DeriveGlobalConfig::watch_global().unwrap();
+    assert_eq!(DeriveGlobalConfig::global().port, 9000);
+
+    thread::sleep(Duration::from_millis(100));
+    fs::write(&path, r#"{"port": 9001}"#).unwrap();
+    thread::sleep(Duration::from_millis(400));
+    assert_eq!(DeriveGlobalConfig::global().port, 9001);
+
+    // The last configuration is kept once the watcher stops
+    handle.stop();
+    fs::remove_file(&path).ok();
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(DeriveGlobalConfig::global().port, 9001);
+}
+
+// ============================================================================
+// Environment Change Tests
+// ============================================================================
+
+#[allow(dead_code)] // Only used for its generated dotenv_files()
+#[derive(EnvConfig, Clone)]
+#[env_config(dotenv = [".env", ".env.local"])]
+struct DotenvConfig {
+    #[env(var = "PORT", default = "8080")]
+    port: u16,
+}
+
+#[test]
+fn test_dotenv_files_generated() {
+    assert_eq!(DotenvConfig::dotenv_files(), [".env", ".env.local"]);
+    assert!(SimpleConfig::dotenv_files().is_empty());
+}
+
+#[test]
+fn test_dotenv_change_reports_env_vars() {
+    let dir = tempdir().unwrap();
+    let dotenv_path = dir.path().join(".env");
+    fs::write(
+        &dotenv_path,
+        "WATCH_DOTENV_PORT=8080\nWATCH_DOTENV_HOST=localhost\n",
+    )
+    .unwrap();
+
+    let trigger = Arc::new(Mutex::new(None));
+    let trigger_clone = trigger.clone();
+    let dotenv_clone = dotenv_path.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_dotenv(&dotenv_path)
+        .debounce(Duration::from_millis(50))
+        .on_change(move |change| {
+            *trigger_clone.lock().unwrap() = Some(change.trigger);
+        })
+        .build_sync(move || {
+            // Mimic the derive: dotenv loading never overrides existing variables
+            let _ = procenv::dotenv::load(&dotenv_clone);
+            let port = procenv::env::var("WATCH_DOTENV_PORT")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or_default();
+            let host = procenv::env::var("WATCH_DOTENV_HOST").unwrap_or_default();
+            Ok((SimpleConfig { port, host }, ConfigSources::default()))
+        })
+        .unwrap();
+
+    assert_eq!(handle.get().port, 8080);
+
+    // Change one value; a comment-only edit elsewhere must not matter
+    thread::sleep(Duration::from_millis(100));
+    fs::write(
+        &dotenv_path,
+        "# edited\nWATCH_DOTENV_PORT=9090\nWATCH_DOTENV_HOST=localhost\n",
+    )
+    .unwrap();
+    thread::sleep(Duration::from_millis(300));
+
+    assert_eq!(handle.get().port, 9090);
+    assert_eq!(handle.get().host, "localhost");
+    // The edit is seen by the reload without touching the process environment
+    assert_eq!(std::env::var("WATCH_DOTENV_PORT").unwrap(), "8080");
+    assert_eq!(
+        *trigger.lock().unwrap(),
+        Some(ChangeTrigger::EnvVarChanged(
+            "WATCH_DOTENV_PORT".to_string()
+        ))
+    );
+
+    handle.stop();
+}
+
+#[test]
+fn test_dotenv_reference_follows_edit() {
+    let dir = tempdir().unwrap();
+    let dotenv_path = dir.path().join(".env");
+    fs::write(
+        &dotenv_path,
+        "WATCH_DOTENV_REF_PORT=8080\nWATCH_DOTENV_REF_HOST=host-${WATCH_DOTENV_REF_PORT}\n",
+    )
+    .unwrap();
+
+    let trigger = Arc::new(Mutex::new(None));
+    let trigger_clone = trigger.clone();
+    let dotenv_clone = dotenv_path.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_dotenv(&dotenv_path)
+        .debounce(Duration::from_millis(50))
+        .on_change(move |change| {
+            *trigger_clone.lock().unwrap() = Some(change.trigger);
+        })
+        .build_sync(move || {
+            let _ = procenv::dotenv::load(&dotenv_clone);
+            let port = procenv::env::var("WATCH_DOTENV_REF_PORT")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or_default();
+            let host = procenv::env::var("WATCH_DOTENV_REF_HOST").unwrap_or_default();
+            Ok((SimpleConfig { port, host }, ConfigSources::default()))
+        })
+        .unwrap();
+
+    assert_eq!(handle.get().host, "host-8080");
+
+    // The reference must see the edited value, not the one the initial
+    // load left in the process environment
+    thread::sleep(Duration::from_millis(100));
+    fs::write(
+        &dotenv_path,
+        "WATCH_DOTENV_REF_PORT=9090\nWATCH_DOTENV_REF_HOST=host-${WATCH_DOTENV_REF_PORT}\n",
+    )
+    .unwrap();
+    thread::sleep(Duration::from_millis(300));
+
+    assert_eq!(handle.get().port, 9090);
+    assert_eq!(handle.get().host, "host-9090");
+    assert_eq!(
+        trigger
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(ChangeTrigger::env_vars),
+        Some(
+            &[
+                "WATCH_DOTENV_REF_HOST".to_string(),
+                "WATCH_DOTENV_REF_PORT".to_string()
+            ][..]
+        )
+    );
+
+    handle.stop();
+}
+
+#[test]
+fn test_dotenv_comment_edit_does_not_reload() {
+    let dir = tempdir().unwrap();
+    let dotenv_path = dir.path().join(".env");
+    fs::write(&dotenv_path, "WATCH_DOTENV_COMMENT=1\n").unwrap();
+
+    let reload_count = Arc::new(AtomicU32::new(0));
+    let reload_count_clone = reload_count.clone();
+
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .watch_dotenv(&dotenv_path)
+        .debounce(Duration::from_millis(50))
+        .build_sync(move || {
+            reload_count_clone.fetch_add(1, Ordering::SeqCst);
+            Ok((SimpleConfig::default(), ConfigSources::default()))
+        })
+        .unwrap();
+
+    thread::sleep(Duration::from_millis(100));
+    fs::write(&dotenv_path, "# just a comment\nWATCH_DOTENV_COMMENT=1\n").unwrap();
+    thread::sleep(Duration::from_millis(300));
+
+    assert_eq!(reload_count.load(Ordering::SeqCst), 1);
+
+    handle.stop();
+}
+
+#[test]
+fn test_poll_env_detects_set_var() {
+    let trigger = Arc::new(Mutex::new(None));
+    let trigger_clone = trigger.clone();
+
+    // No files needed when polling the environment
+    let handle = WatchBuilder::<SimpleConfig>::new()
+        .poll_env_vars(["WATCH_POLL_PORT"], Duration::from_millis(50))
+        .on_change(move |change| {
+            *trigger_clone.lock().unwrap() = Some(change.trigger);
+        })
+        .build_sync(|| {
+            let port = std::env::var("WATCH_POLL_PORT")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(8080);
+            Ok((
+                SimpleConfig {
+                    port,
+                    host: "localhost".to_string(),
+                },
+                ConfigSources::default(),
+            ))
+        })
+        .unwrap();
+
+    assert_eq!(handle.get().port, 8080);
+
+    unsafe { std::env::set_var("WATCH_POLL_PORT", "7070") };
+    thread::sleep(Duration::from_millis(300));
+
+    assert_eq!(handle.get().port, 7070);
+    assert_eq!(
+        trigger.lock().unwrap().as_ref().and_then(|t| t.env_var()),
+        Some("WATCH_POLL_PORT")
+    );
+
+    handle.stop();
+    unsafe { std::env::remove_var("WATCH_POLL_PORT") };
+}
+
 // ============================================================================
 // WatchError Tests
 // ============================================================================
@@ -441,3 +1333,230 @@ impl Default for ConfigWithSecret {
         }
     }
 }
+
+// ============================================================================
+// Registry Tests
+// ============================================================================
+
+#[test]
+fn test_registry_dispatches_by_file() {
+    let dir = tempdir().unwrap();
+    let server_path = dir.path().join("server.toml");
+    let db_path = dir.path().join("db.toml");
+    fs::write(&server_path, "port = 9000").unwrap();
+    fs::write(&db_path, "port = 5432").unwrap();
+
+    let registry = procenv::WatchRegistry::new().unwrap();
+
+    let server_reload = server_path.clone();
+    let server = WatchBuilder::<u16>::new()
+        .watch_file(&server_path)
+        .debounce(Duration::from_millis(50))
+        .build_in(&registry, move || read_port(&server_reload))
+        .unwrap();
+
+    let db_reload = db_path.clone();
+    let db = WatchBuilder::<String>::new()
+        .watch_file(&db_path)
+        .debounce(Duration::from_millis(50))
+        .build_in(&registry, move || {
+            let (port, sources) = read_port(&db_reload)?;
+            Ok((format!("db:{port}"), sources))
+        })
+        .unwrap();
+
+    assert_eq!(*server.get(), 9000);
+    assert_eq!(*db.get(), "db:5432");
+
+    // Only the handle watching the changed file reloads
+    thread::sleep(Duration::from_millis(100));
+    fs::write(&db_path, "port = 5433").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(*db.get(), "db:5433");
+    assert_eq!(server.epoch(), 0);
+
+    fs::write(&server_path, "port = 9001").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(*server.get(), 9001);
+
+    // Stopping the registry stops every handle
+    registry.stop();
+    thread::sleep(Duration::from_millis(100));
+    assert!(!registry.is_running());
+    assert!(!server.is_running());
+    assert!(!db.is_running());
+}
+
+#[test]
+fn test_registry_reloads_in_registration_order() {
+    let dir = tempdir().unwrap();
+    let shared = dir.path().join("shared.toml");
+    fs::write(&shared, "port = 9000").unwrap();
+
+    let registry = procenv::WatchRegistry::new().unwrap();
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    let handles: Vec<_> = ["first", "second", "third"]
+        .into_iter()
+        .map(|name| {
+            let order = order.clone();
+            let path = shared.clone();
+            WatchBuilder::<u16>::new()
+                .watch_file(&shared)
+                .debounce(Duration::from_millis(50))
+                .build_in(&registry, move || {
+                    order.lock().unwrap().push(name);
+                    read_port(&path)
+                })
+                .unwrap()
+        })
+        .collect();
+    order.lock().unwrap().clear();
+
+    thread::sleep(Duration::from_millis(100));
+    fs::write(&shared, "port = 9001").unwrap();
+    thread::sleep(Duration::from_millis(300));
+
+    assert_eq!(*order.lock().unwrap(), ["first", "second", "third"]);
+    assert!(handles.iter().all(|handle| *handle.get() == 9001));
+
+    // Stopping one handle leaves the others registered
+    handles[0].stop();
+    thread::sleep(Duration::from_millis(100));
+    fs::write(&shared, "port = 9002").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(*handles[0].get(), 9001);
+    assert_eq!(*handles[2].get(), 9002);
+    assert!(registry.is_running());
+}
+
+// ============================================================================
+// Provider Change Tests
+// ============================================================================
+
+/// In-memory provider that bumps its revision on every write.
+struct MemoryProvider {
+    values: Mutex<std::collections::HashMap<String, String>>,
+    revision: AtomicU32,
+}
+
+impl MemoryProvider {
+    fn set(&self, key: &str, value: &str) {
+        self.values
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value.to_string());
+        self.revision.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+impl procenv::Provider for MemoryProvider {
+    fn name(&self) -> &str {
+        "memory"
+    }
+
+    fn get(&self, key: &str) -> procenv::ProviderResult<procenv::ProviderValue> {
+        Ok(self.values.lock().unwrap().get(key).map(|value| {
+            procenv::ProviderValue::new(value, procenv::ProviderSource::custom("memory", None))
+        }))
+    }
+
+    fn revision(&self) -> Option<String> {
+        Some(self.revision.load(Ordering::SeqCst).to_string())
+    }
+}
+
+#[test]
+fn test_provider_revision_change_reloads() {
+    let provider = Arc::new(MemoryProvider {
+        values: Mutex::new(std::collections::HashMap::new()),
+        revision: AtomicU32::new(0),
+    });
+    provider.set("PORT", "9000");
+
+    let triggers = Arc::new(Mutex::new(Vec::new()));
+    let triggers_clone = triggers.clone();
+    let loader_provider = provider.clone();
+
+    let handle = WatchBuilder::<u16>::new()
+        .watch_provider(provider.clone(), Duration::from_millis(20))
+        .debounce(Duration::from_millis(20))
+        .on_change(move |change| triggers_clone.lock().unwrap().push(change.trigger))
+        .build_sync(move || {
+            let mut loader =
+                procenv::ConfigLoader::new().with_provider(Box::new(loader_provider.clone()));
+            let port = loader.get_parsed::<u16>("PORT")?.unwrap_or(8080);
+            Ok((port, loader.into_sources()))
+        })
+        .unwrap();
+
+    assert_eq!(*handle.get(), 9000);
+
+    // No reload while the revision is unchanged
+    thread::sleep(Duration::from_millis(150));
+    assert_eq!(handle.epoch(), 0);
+
+    provider.set("PORT", "9001");
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(*handle.get(), 9001);
+    assert_eq!(
+        *triggers.lock().unwrap(),
+        [ChangeTrigger::ProviderChanged {
+            provider: "memory".to_string()
+        }]
+    );
+
+    handle.stop();
+}
+
+/// Provider whose revision hangs while `hung` is set, like an unreachable
+/// remote store.
+struct HangingProvider {
+    hung: AtomicBool,
+}
+
+impl procenv::Provider for HangingProvider {
+    fn name(&self) -> &str {
+        "hanging"
+    }
+
+    fn get(&self, _key: &str) -> procenv::ProviderResult<procenv::ProviderValue> {
+        Ok(None)
+    }
+
+    fn revision(&self) -> Option<String> {
+        if self.hung.load(Ordering::SeqCst) {
+            thread::sleep(Duration::from_secs(5));
+        }
+        Some("1".to_string())
+    }
+}
+
+#[test]
+#[allow(clippy::result_large_err)]
+fn test_hanging_provider_does_not_stall_file_reloads() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+    fs::write(&config_path, "port = 8080").unwrap();
+
+    let provider = Arc::new(HangingProvider {
+        hung: AtomicBool::new(false),
+    });
+    let reload_path = config_path.clone();
+
+    let handle = WatchBuilder::<u16>::new()
+        .watch_file(&config_path)
+        .watch_provider(provider.clone(), Duration::from_millis(20))
+        .debounce(Duration::from_millis(20))
+        .build_sync(move || read_port(&reload_path))
+        .unwrap();
+
+    provider.hung.store(true, Ordering::SeqCst);
+    thread::sleep(Duration::from_millis(100));
+
+    fs::write(&config_path, "port = 9000").unwrap();
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(*handle.get(), 9000);
+
+    handle.stop();
+}
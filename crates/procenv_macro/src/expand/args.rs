@@ -164,7 +164,7 @@ pub fn generate_from_args_impl(
                     #(#env_var_names),*
                 ]
                 .iter()
-                .filter(|var| std::env::var(var).is_ok())
+                .filter(|var| ::procenv::env::var(var).is_ok())
                 .copied()
                 .collect();
 
@@ -316,7 +316,7 @@ fn generate_cli_aware_source_tracking(field: &dyn FieldGenerator) -> QuoteStream
                     #env_var,
                     ::procenv::Source::Profile(__profile.clone().unwrap_or_default())
                 )
-            } else if std::env::var(#env_var).is_ok() {
+            } else if ::procenv::env::var(#env_var).is_ok() {
                 if __dotenv_loaded && !__pre_dotenv_vars.contains(#env_var) {
                     ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(None))
                 } else {
@@ -346,7 +346,7 @@ fn generate_cli_aware_source_tracking(field: &dyn FieldGenerator) -> QuoteStream
         quote! {
             let #source_ident = if #from_cli_var {
                 ::procenv::ValueSource::new(#env_var, ::procenv::Source::Cli)
-            } else if std::env::var(#env_var).is_ok() {
+            } else if ::procenv::env::var(#env_var).is_ok() {
                 if __dotenv_loaded && !__pre_dotenv_vars.contains(#env_var) {
                     ::procenv::ValueSource::new(#env_var, ::procenv::Source::DotenvFile(None))
                 } else {
@@ -21,7 +21,7 @@
 //! ```rust,ignore
 //! pub fn from_config() -> Result<Self, Error> {
 //!     // Load dotenv first
-//!     let _ = dotenvy::dotenv();
+//!     ::procenv::dotenv::load_files(&[".env"])?;
 //!
 //!     let mut builder = ConfigBuilder::new();
 //!
@@ -47,11 +47,11 @@
 use std::string::String;
 
 use proc_macro2::TokenStream as QuoteStream;
-use quote::quote;
-use syn::{Generics, Ident};
+use quote::{format_ident, quote};
+use syn::{GenericArgument, Generics, Ident, PathArguments, Type};
 
 use crate::field::FieldGenerator;
-use crate::parse::EnvConfigAttr;
+use crate::parse::{EnvConfigAttr, FileFormatOverride};
 
 use super::env::generate_dotenv_load;
 
@@ -76,18 +76,52 @@ pub fn generate_from_config_impl(
         .iter()
         .map(|f| {
             let path = &f.path;
-            if f.required {
-                quote! {
-                    builder = builder.file(#path);
+
+            match (f.format, f.required) {
+                (Some(FileFormatOverride::Fixed(variant)), required) => {
+                    let format = format_ident!("{}", variant);
+                    let method = if required {
+                        quote! { file_with_format }
+                    } else {
+                        quote! { file_optional_with_format }
+                    };
+
+                    quote! {
+                        builder = builder.#method(#path, ::procenv::file::FileFormat::#format);
+                    }
                 }
-            } else {
-                quote! {
+
+                (_, true) => quote! {
+                    builder = builder.file(#path);
+                },
+
+                (_, false) => quote! {
                     builder = builder.file_optional(#path);
-                }
+                },
             }
         })
         .collect();
 
+    // Check keys against the struct's in strict mode
+    let strict = env_config_attr.strict.then(|| {
+        let profile_var = env_config_attr
+            .profile_env
+            .as_ref()
+            .map(|var| quote! { builder = builder.known_env_var(#var); });
+
+        quote! {
+            builder = builder.known_keys(Self::__known_keys()).strict(true);
+            #profile_var
+        }
+    });
+
+    // Sniff formats if any file asks for it with `format = "auto"`
+    let sniff_formats = env_config_attr
+        .files
+        .iter()
+        .any(|f| f.format == Some(FileFormatOverride::Auto))
+        .then(|| quote! { builder = builder.sniff_formats(true); });
+
     // Generate env prefix setup
     let env_prefix = env_config_attr.prefix.as_ref().map_or_else(
         || quote! {},
@@ -143,7 +177,7 @@ pub fn generate_from_config_impl(
             #(#env_var_names),*
         ]
         .iter()
-        .filter(|var| std::env::var(var).is_ok())
+        .filter(|var| ::procenv::env::var(var).is_ok())
         .copied()
         .collect();
     };
@@ -167,10 +201,11 @@ pub fn generate_from_config_impl(
             let json_key = field_name;
 
             g.default_value().map(|default| {
+                let coerced = coerce_to_field(g.as_ref(), default);
                 quote! {
                     __defaults.insert(
                         #json_key.to_string(),
-                        ::procenv::FileUtils::coerce_value(#default)
+                        #coerced
                     );
                 }
             })
@@ -281,7 +316,7 @@ pub fn generate_from_config_impl(
                             // 3. Config file (check origin tracker)
                             // 4. Profile/Default (requires nested metadata - not yet available)
                             // 5. NotSet
-                            let source = if std::env::var(&expected_env_var).is_ok() {
+                            let source = if ::procenv::env::var(&expected_env_var).is_ok() {
                                 // Value came from environment
                                 if __dotenv_loaded && !__pre_dotenv_vars.contains(expected_env_var.as_str()) {
                                     // Env var was loaded from .env file
@@ -300,9 +335,14 @@ pub fn generate_from_config_impl(
                                 ::procenv::Source::NotSet
                             };
 
+                            let span = match source {
+                                ::procenv::Source::ConfigFile(_) => __origins.span_of(&full_path),
+                                _ => None,
+                            };
+
                             __sources.add(
                                 full_path,
-                                ::procenv::ValueSource::new(&expected_env_var, source)
+                                ::procenv::ValueSource::new(&expected_env_var, source).with_span(span)
                             );
                         }
 
@@ -329,6 +369,7 @@ pub fn generate_from_config_impl(
                                 __sources.add(
                                     full_path,
                                     ::procenv::ValueSource::new(tracked_path, source)
+                                        .with_span(__origins.span_of(tracked_path))
                                 );
                             }
                         }
@@ -349,7 +390,7 @@ pub fn generate_from_config_impl(
                         // 4. Profile default (if profile is active AND field has profile config)
                         // 5. Regular default
                         // 6. NotSet (for optional fields without value)
-                        let source = if std::env::var(#env_var).is_ok() {
+                        let source = if ::procenv::env::var(#env_var).is_ok() {
                             // Value came from environment variable
                             if __dotenv_loaded && !__pre_dotenv_vars.contains(#env_var) {
                                 // Var was loaded from .env file (not set before dotenv)
@@ -373,9 +414,14 @@ pub fn generate_from_config_impl(
                             ::procenv::Source::NotSet
                         };
 
+                        let span = match source {
+                            ::procenv::Source::ConfigFile(_) => __origins.span_of(#field_name),
+                            _ => None,
+                        };
+
                         __sources.add(
                             #field_name,
-                            ::procenv::ValueSource::new(#env_var, source)
+                            ::procenv::ValueSource::new(#env_var, source).with_span(span)
                         );
                     }
                 }
@@ -395,6 +441,18 @@ pub fn generate_from_config_impl(
 
                 #defaults_setup
 
+                for (__path, __strategy) in Self::__merge_strategies() {
+                    builder = builder.merge_strategy(__path, __strategy);
+                }
+
+                for (__path, __type) in Self::__value_types() {
+                    builder = builder.value_type(__path, __type);
+                }
+
+                #sniff_formats
+
+                #strict
+
                 #(#file_loads)*
 
                 #env_prefix
@@ -419,6 +477,18 @@ pub fn generate_from_config_impl(
 
                 #defaults_setup
 
+                for (__path, __strategy) in Self::__merge_strategies() {
+                    builder = builder.merge_strategy(__path, __strategy);
+                }
+
+                for (__path, __type) in Self::__value_types() {
+                    builder = builder.value_type(__path, __type);
+                }
+
+                #sniff_formats
+
+                #strict
+
                 #(#file_loads)*
 
                 #env_prefix
@@ -752,7 +822,7 @@ fn generate_profile_defaults_for_config(
 
     let profile_setup = quote! {
         // Read profile from environment variable
-        let __profile: std::option::Option<std::string::String> = std::env::var(#profile_env).ok();
+        let __profile: std::option::Option<std::string::String> = ::procenv::env::var(#profile_env).ok();
         #validation
     };
 
@@ -785,11 +855,12 @@ fn generate_profile_defaults_for_config(
                 .values
                 .iter()
                 .map(|(profile_name, value)| {
+                    let coerced = coerce_to_field(g.as_ref(), value);
                     quote! {
                         std::option::Option::Some(#profile_name) => {
                             __defaults.insert(
                                 #field_name.to_string(),
-                                ::procenv::FileUtils::coerce_value(#value)
+                                #coerced
                             );
                         }
                     }
@@ -831,10 +902,11 @@ pub fn generate_config_defaults_impl(
 
             let field_name = g.name().to_string();
             g.default_value().map(|default| {
+                let coerced = coerce_to_field(g.as_ref(), default);
                 quote! {
                     __map.insert(
                         #field_name.to_string(),
-                        ::procenv::FileUtils::coerce_value(#default)
+                        #coerced
                     );
                 }
             })
@@ -878,11 +950,12 @@ pub fn generate_config_defaults_impl(
                 .values
                 .iter()
                 .map(|(profile_name, value)| {
+                    let coerced = coerce_to_field(g.as_ref(), value);
                     quote! {
                         std::option::Option::Some(#profile_name) => {
                             __map.insert(
                                 #field_name.to_string(),
-                                ::procenv::FileUtils::coerce_value(#value)
+                                #coerced
                             );
                         }
                     }
@@ -954,6 +1027,82 @@ pub fn generate_config_defaults_impl(
         __mappings
     };
 
+    // Generate known key entries for __known_keys() method
+    let known_key_entries: Vec<QuoteStream> = generators
+        .iter()
+        .map(|g| {
+            let field_name = g.name().to_string();
+
+            if g.is_flatten()
+                && let Some(ty) = g.field_type()
+            {
+                // For flatten fields, include the nested keys under the field's path
+                return quote! {
+                    for nested_key in <#ty>::__known_keys() {
+                        __keys.push(format!("{}.{}", #field_name, nested_key));
+                    }
+                };
+            }
+
+            quote! {
+                __keys.push(#field_name.to_string());
+            }
+        })
+        .collect();
+
+    // Generate merge strategy entries for __merge_strategies() method
+    let merge_strategy_pairs: Vec<QuoteStream> = generators
+        .iter()
+        .filter_map(|g| {
+            let field_name = g.name().to_string();
+
+            if g.is_flatten() {
+                // For flatten fields, include nested strategies under the field's path
+                let ty = g.field_type()?;
+                return Some(quote! {
+                    for (nested_path, strategy) in <#ty>::__merge_strategies() {
+                        __strategies.push((format!("{}.{}", #field_name, nested_path), strategy));
+                    }
+                });
+            }
+
+            let strategy = match g.merge_strategy()? {
+                "append" => quote! { Append },
+                "replace" => quote! { Replace },
+                "union" => quote! { Union },
+                "patch" => quote! { Patch },
+                _ => unreachable!("Merge strategy validated at parse time"),
+            };
+
+            Some(quote! {
+                __strategies.push((#field_name.to_string(), ::procenv::file::Merge::#strategy));
+            })
+        })
+        .collect();
+
+    // Generate value type entries for __value_types() method
+    let value_type_pairs: Vec<QuoteStream> = generators
+        .iter()
+        .filter_map(|g| {
+            let field_name = g.name().to_string();
+
+            if g.is_flatten() {
+                // For flatten fields, include nested types under the field's path
+                let ty = g.field_type()?;
+                return Some(quote! {
+                    for (nested_path, value_type) in <#ty>::__value_types() {
+                        __types.push((format!("{}.{}", #field_name, nested_path), value_type));
+                    }
+                });
+            }
+
+            let value_type = value_type(g.as_ref())?;
+            Some(quote! {
+                __types.push((#field_name.to_string(), #value_type));
+            })
+        })
+        .collect();
+
     quote! {
         // Only generate __config_defaults when file feature is enabled
         #[cfg(feature = "file")]
@@ -986,10 +1135,121 @@ pub fn generate_config_defaults_impl(
             pub fn __env_mappings() -> std::vec::Vec<(&'static str, &'static str)> {
                 #env_mapping_entries
             }
+
+            /// Returns the dotted paths of all fields, including nested ones.
+            /// Used by `from_config()` in strict mode and by parent configs.
+            #[doc(hidden)]
+            pub fn __known_keys() -> std::vec::Vec<std::string::String> {
+                let mut __keys = std::vec::Vec::new();
+                #(#known_key_entries)*
+                __keys
+            }
+
+            /// Returns field-path merge strategies from `#[env(merge = "...")]`.
+            /// Used by `from_config()` and by parent configs for nested fields.
+            #[doc(hidden)]
+            pub fn __merge_strategies() -> std::vec::Vec<(std::string::String, ::procenv::file::Merge)> {
+                let mut __strategies = std::vec::Vec::new();
+                #(#merge_strategy_pairs)*
+                __strategies
+            }
+
+            /// Returns the types of fields that environment values are coerced to.
+            /// Used by `from_config()` and by parent configs for nested fields.
+            #[doc(hidden)]
+            pub fn __value_types() -> std::vec::Vec<(std::string::String, ::procenv::file::ValueType)> {
+                let mut __types = std::vec::Vec::new();
+                #(#value_type_pairs)*
+                __types
+            }
+        }
+    }
+}
+
+/// The `ValueType` a field's environment values and defaults are coerced to.
+///
+/// Returns `None` for flatten fields, fields parsed with a format other than
+/// JSON, and types that do not map onto one, whose values are guessed.
+fn value_type(g: &dyn FieldGenerator) -> Option<QuoteStream> {
+    if g.is_flatten() || g.format_config().is_some_and(|format| format != "json") {
+        return None;
+    }
+
+    // Only `SecretString` fields have no stored type
+    let string = || Some(quote! { ::procenv::file::ValueType::String });
+    g.field_type().map_or_else(string, value_type_of)
+}
+
+/// The `ValueType` for a type, judged by its name.
+///
+/// Lists carry the type of their items; items that aren't scalars are
+/// guessed.
+fn value_type_of(ty: &Type) -> Option<QuoteStream> {
+    if let Some(variant) = scalar_type_of(ty) {
+        let variant = format_ident!("{}", variant);
+        return Some(quote! { ::procenv::file::ValueType::#variant });
+    }
+
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+
+    match segment.ident.to_string().as_str() {
+        "Vec" | "VecDeque" | "HashSet" | "BTreeSet" => {
+            let item = item_type_of(&segment.arguments)
+                .and_then(scalar_type_of)
+                .unwrap_or("Any");
+            let item = format_ident!("{}", item);
+
+            Some(quote! {
+                ::procenv::file::ValueType::list(::procenv::file::ValueType::#item)
+            })
         }
+        "HashMap" | "BTreeMap" => Some(quote! { ::procenv::file::ValueType::Map }),
+        _ => None,
     }
 }
 
+/// The scalar `ValueType` variant for a type, judged by its name.
+fn scalar_type_of(ty: &Type) -> Option<&'static str> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+
+    let variant = match segment.ident.to_string().as_str() {
+        "String" | "PathBuf" | "OsString" | "char" | "SecretString" => "String",
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+        | "usize" => "Integer",
+        "f32" | "f64" => "Float",
+        "bool" => "Bool",
+        _ => return None,
+    };
+
+    Some(variant)
+}
+
+/// The first type argument of a collection, e.g. `T` in `Vec<T>`.
+fn item_type_of(arguments: &PathArguments) -> Option<&Type> {
+    let PathArguments::AngleBracketed(args) = arguments else {
+        return None;
+    };
+
+    match args.args.first()? {
+        GenericArgument::Type(item) => Some(item),
+        _ => None,
+    }
+}
+
+/// Coerce a default value string to the field's type, or guess one.
+fn coerce_to_field(g: &dyn FieldGenerator, value: &str) -> QuoteStream {
+    value_type(g).map_or_else(
+        || quote! { ::procenv::FileUtils::coerce_value(#value) },
+        |value_type| quote! { #value_type.coerce(#value) },
+    )
+}
+
 /// Generate the `__from_json_value()` method for serde-free deserialization.
 ///
 /// This method is generated for ALL `EnvConfig` structs so they can be used
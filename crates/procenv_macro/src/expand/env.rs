@@ -8,6 +8,7 @@
 //! - [`generate_from_env_impl`] - Main `from_env()` method
 //! - [`generate_profile_setup`] - Profile environment variable handling
 //! - [`generate_dotenv_load`] - `.env` file loading code
+//! - [`generate_dotenv_files_impl`] - `dotenv_files()` for hot reload watching
 //! - [`generate_field_loader`] - Per-field loading with profile/format support
 //! - [`generate_from_env_with_external_prefix_impl`] - Prefixed loading for nested structs
 //!
@@ -87,7 +88,7 @@ pub fn generate_from_env_impl(
             /// if any values fail to parse. All errors are accumulated
             /// and returned together.
             pub fn from_env() -> std::result::Result<Self, ::procenv::Error> {
-                // Load .env file(s) if configured (errors are silently ignored)
+                // Load .env file(s) if configured (missing files are skipped)
                 #dotenv_load
 
                 // Define external prefix as None for regular from_env calls
@@ -152,7 +153,7 @@ pub fn generate_profile_setup(env_config_attr: &EnvConfigAttr) -> QuoteStream {
 
     quote! {
         // Read profile from environment variable, reporting UTF-8 errors
-        let __profile: std::option::Option<std::string::String> = match std::env::var(#profile_env) {
+        let __profile: std::option::Option<std::string::String> = match ::procenv::env::var(#profile_env) {
             std::result::Result::Ok(val) => std::option::Option::Some(val),
             std::result::Result::Err(std::env::VarError::NotPresent) => std::option::Option::None,
             std::result::Result::Err(std::env::VarError::NotUnicode(_)) => {
@@ -261,7 +262,7 @@ pub fn generate_field_loader(
 
         // Get value to parse: env var > profile > default
         let (__value_to_parse, #profile_used_ident): (std::option::Option<std::string::String>, bool) =
-            match std::env::var(#env_var) {
+            match ::procenv::env::var(#env_var) {
                 std::result::Result::Ok(val) => {
                     (std::option::Option::Some(val), false)
                 }
@@ -306,34 +307,48 @@ pub fn generate_field_loader(
 }
 
 /// Generate code to load .env file(s) based on configuration.
+///
+/// Files are parsed by `procenv::dotenv`; missing files are skipped, but a
+/// malformed line fails the load with a diagnostic pointing at it.
 pub fn generate_dotenv_load(dotenv_config: Option<&DotenvConfig>) -> QuoteStream {
-    match dotenv_config {
-        None => quote! {},
+    let paths: Vec<&str> = match dotenv_config {
+        None => return quote! {},
+        Some(DotenvConfig::Default) => vec![".env"],
+        Some(DotenvConfig::Custom(path)) => vec![path.as_str()],
+        Some(DotenvConfig::Multiple(paths)) => paths.iter().map(String::as_str).collect(),
+    };
 
-        Some(DotenvConfig::Default) => {
-            quote! {
-                let _ = ::dotenvy::dotenv();
-            }
-        }
+    quote! {
+        ::procenv::dotenv::load_files(&[#(#paths),*])?;
+    }
+}
 
-        Some(DotenvConfig::Custom(path)) => {
-            quote! {
-                let _ = ::dotenvy::from_filename(#path);
-            }
-        }
+/// Generate the `dotenv_files()` method listing configured dotenv paths.
+///
+/// Used by the hot reload watcher to watch the same files the loader reads.
+/// The default `dotenv` setting maps to `.env` in the working directory.
+pub fn generate_dotenv_files_impl(
+    struct_name: &Ident,
+    generics: &Generics,
+    dotenv_config: Option<&DotenvConfig>,
+) -> QuoteStream {
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
 
-        Some(DotenvConfig::Multiple(paths)) => {
-            let load_calls: Vec<QuoteStream> = paths
-                .iter()
-                .map(|p| {
-                    quote! {
-                        let _ = ::dotenvy::from_filename(#p);
-                    }
-                })
-                .collect();
+    let paths: Vec<&str> = match dotenv_config {
+        None => Vec::new(),
+        Some(DotenvConfig::Default) => vec![".env"],
+        Some(DotenvConfig::Custom(path)) => vec![path.as_str()],
+        Some(DotenvConfig::Multiple(paths)) => paths.iter().map(String::as_str).collect(),
+    };
+    let num_paths = paths.len();
 
-            quote! {
-                #(#load_calls)*
+    quote! {
+        impl #impl_generics #struct_name #type_generics #where_clause {
+            /// Returns the dotenv files configured via `#[env_config(dotenv = ...)]`,
+            /// in load order.
+            pub fn dotenv_files() -> &'static [&'static str] {
+                static DOTENV_FILES: [&str; #num_paths] = [#(#paths),*];
+                &DOTENV_FILES
             }
         }
     }
@@ -399,7 +414,7 @@ pub fn generate_from_env_with_external_prefix_impl(
                 // Track pre-dotenv env vars using the EFFECTIVE (prefixed) names
                 let __pre_dotenv_vars: std::collections::HashSet<std::string::String> = __effective_env_vars
                     .iter()
-                    .filter(|var| std::env::var(var).is_ok())
+                    .filter(|var| ::procenv::env::var(var).is_ok())
                     .cloned()
                     .collect();
 
@@ -489,7 +504,7 @@ fn generate_simple_source_tracking(field: &dyn FieldGenerator) -> QuoteStream {
                         &#effective_var_ident,
                         ::procenv::Source::Profile(__profile.clone().unwrap_or_default())
                     )
-                } else if std::env::var(&#effective_var_ident).is_ok() {
+                } else if ::procenv::env::var(&#effective_var_ident).is_ok() {
                     if __dotenv_loaded && !__pre_dotenv_vars.contains(&#effective_var_ident) {
                         ::procenv::ValueSource::new(&#effective_var_ident, ::procenv::Source::DotenvFile(None))
                     } else {
@@ -522,7 +537,7 @@ fn generate_simple_source_tracking(field: &dyn FieldGenerator) -> QuoteStream {
                     std::option::Option::None => #env_var.to_string(),
                 };
 
-                let #source_ident = if std::env::var(&#effective_var_ident).is_ok() {
+                let #source_ident = if ::procenv::env::var(&#effective_var_ident).is_ok() {
                     if __dotenv_loaded && !__pre_dotenv_vars.contains(&#effective_var_ident) {
                         ::procenv::ValueSource::new(&#effective_var_ident, ::procenv::Source::DotenvFile(None))
                     } else {
@@ -645,7 +660,7 @@ fn generate_field_loader_with_prefix(field: &dyn FieldGenerator) -> QuoteStream
 
         // Get value to parse: env var > profile > default
         let (__value_to_parse, #profile_used_ident): (std::option::Option<std::string::String>, bool) =
-            match std::env::var(&#effective_var_ident) {
+            match ::procenv::env::var(&#effective_var_ident) {
                 std::result::Result::Ok(val) => {
                     (std::option::Option::Some(val), false)
                 }
@@ -768,7 +783,7 @@ fn generate_format_loader_with_prefix(field: &dyn FieldGenerator, format: &str)
         let #profile_used_ident: bool = false;
         let mut #used_default_ident: bool = false;
 
-        let #name = match std::env::var(&#effective_var_ident) {
+        let #name = match ::procenv::env::var(&#effective_var_ident) {
             std::result::Result::Ok(val) => {
                 match #deserialize_call {
                     std::result::Result::Ok(v) => std::option::Option::Some(v),
@@ -0,0 +1,82 @@
+//! Process-wide accessor code generation.
+//!
+//! With `#[env_config(global)]`, this module generates `global()`, which
+//! loads the struct into a `procenv::Global` on first access, and, with the
+//! `watch` feature, `watch_global()`, which keeps that value up to date.
+//!
+//! # Loader
+//!
+//! | Files configured | `validate` | Loads via |
+//! |------------------|------------|-----------|
+//! | Yes | No | `from_config()` |
+//! | Yes | Yes | `from_config_validated()` |
+//! | No | No | `from_env()` |
+//! | No | Yes | `from_env_validated()` |
+
+use proc_macro2::TokenStream as QuoteStream;
+use quote::quote;
+use syn::Ident;
+
+use crate::parse::{EnvConfigAttr, GlobalPolicy};
+
+/// Generate the `global()` and `watch_global()` methods.
+///
+/// Returns nothing unless `global` is set. The parser rejects `global` on
+/// generic structs, since the `static` holding the value can't name the
+/// type parameters.
+pub fn generate_global_impl(struct_name: &Ident, env_config_attr: &EnvConfigAttr) -> QuoteStream {
+    let Some(policy) = env_config_attr.global else {
+        return quote! {};
+    };
+
+    let policy = match policy {
+        GlobalPolicy::Panic => quote! { ::procenv::FailurePolicy::Panic },
+        GlobalPolicy::Exit => quote! { ::procenv::FailurePolicy::Exit },
+    };
+
+    let load_fn = match (env_config_attr.files.is_empty(), env_config_attr.validate) {
+        (false, false) => quote! { #struct_name::from_config },
+        (false, true) => quote! { #struct_name::from_config_validated },
+        (true, false) => quote! { #struct_name::from_env },
+        (true, true) => quote! { #struct_name::from_env_validated },
+    };
+
+    quote! {
+        impl #struct_name {
+            /// The process-wide value behind `global()`.
+            #[doc(hidden)]
+            pub fn __procenv_global() -> &'static ::procenv::Global<#struct_name> {
+                static __GLOBAL: ::procenv::Global<#struct_name> =
+                    ::procenv::Global::with_policy(#load_fn, #policy);
+
+                &__GLOBAL
+            }
+
+            /// Get the process-wide configuration, loading it on first access.
+            ///
+            /// Load failures are reported with the rendered diagnostic, by
+            /// panicking or exiting as set by `#[env_config(global = ...)]`.
+            pub fn global() -> ::std::sync::Arc<Self> {
+                Self::__procenv_global().get()
+            }
+        }
+
+        // Only generate the hot reload upgrade when the watch feature is enabled
+        #[cfg(feature = "watch")]
+        impl #struct_name {
+            /// Watch the configured files and serve reloads through `global()`.
+            ///
+            /// Returns the handle controlling the watcher; `global()` keeps
+            /// the last configuration once it is stopped.
+            pub fn watch_global() -> std::result::Result<::procenv::ConfigHandle<Self>, ::procenv::WatchError>
+            where
+                for<'__procenv> Self: ::core::clone::Clone,
+            {
+                let __handle = Self::watch()?;
+                Self::__procenv_global().watch(&__handle);
+
+                Ok(__handle)
+            }
+        }
+    }
+}
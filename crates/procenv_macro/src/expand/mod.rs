@@ -18,6 +18,9 @@
 //! | `from_config()` | [`config::generate_from_config_impl`] |
 //! | `from_args()` | [`args::generate_from_args_impl`] |
 //! | `env_example()` | [`example::generate_env_example_impl`] |
+//! | `dotenv_files()` | [`env::generate_dotenv_files_impl`] |
+//! | `watch()` / `watch_with()` | [`watch::generate_watch_impl`] |
+//! | `global()` / `watch_global()` | [`global::generate_global_impl`] |
 //! | `impl Debug` | [`debug::generate_debug_impl`] |
 //!
 //! # Error Accumulation Pattern
@@ -41,9 +44,11 @@ pub mod config;
 pub mod debug;
 pub mod env;
 pub mod example;
+pub mod global;
 pub mod runtime;
 pub mod sources;
 pub mod validation;
+pub mod watch;
 
 /// The main orchestrator for macro expansion.
 pub struct Expander;
@@ -117,6 +122,16 @@ impl Expander {
             &env_config_attr,
         );
 
+        // Generate dotenv file listing (used by the hot reload watcher)
+        let dotenv_files_impl =
+            env::generate_dotenv_files_impl(struct_name, generics, env_config_attr.dotenv.as_ref());
+
+        // Generate hot reload entry points (gated on the watch feature)
+        let watch_impl = watch::generate_watch_impl(struct_name, generics, &env_config_attr);
+
+        // Generate the process-wide accessor if global is set
+        let global_impl = global::generate_global_impl(struct_name, &env_config_attr);
+
         // Generate runtime access methods
         let runtime_access_impl =
             runtime::generate_runtime_access_impl(struct_name, generics, &generators);
@@ -131,6 +146,9 @@ impl Expander {
             #file_config_impl
             #validated_impl
             #external_prefix_impl
+            #dotenv_files_impl
+            #watch_impl
+            #global_impl
             #runtime_access_impl
         };
 
@@ -67,8 +67,8 @@ pub fn generate_runtime_access_impl(
             let prefix = format!("{name_str}.");
 
             quote! {
-                key if key.starts_with(#prefix) => {
-                    self.#name.get_str(&key[#prefix.len()..])
+                if let std::option::Option::Some(rest) = key.strip_prefix(#prefix) {
+                    return self.#name.get_str(rest);
                 }
             }
         })
@@ -84,8 +84,8 @@ pub fn generate_runtime_access_impl(
             let prefix = format!("{name_str}.");
 
             Some(quote! {
-                if key.starts_with(#prefix) {
-                    return <#ty>::has_key(&key[#prefix.len()..]);
+                if let std::option::Option::Some(rest) = key.strip_prefix(#prefix) {
+                    return <#ty>::has_key(rest);
                 }
             })
         })
@@ -102,9 +102,11 @@ pub fn generate_runtime_access_impl(
             /// Gets field value as string by key.
             /// Secret fields return "<redacted>".
             pub fn get_str(&self, key: &str) -> Option<String> {
+                // Delegate nested keys to flatten fields
+                #(#flatten_get_str_arms)*
+
                 match key {
                     #(#get_str_arms)*
-                    #(#flatten_get_str_arms)*
                     _ => None,
                 }
             }
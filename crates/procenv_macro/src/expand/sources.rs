@@ -62,7 +62,7 @@ pub fn generate_from_env_with_sources_impl(
             #(#env_var_names),*
         ]
         .iter()
-        .filter(|var| std::env::var(var).is_ok())
+        .filter(|var| ::procenv::env::var(var).is_ok())
         .copied()
         .collect();
     };
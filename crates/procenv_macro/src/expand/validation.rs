@@ -8,6 +8,8 @@
 //! - [`generate_validated_impl`] - Generates:
 //!   - `from_env_validated()` - Load and validate from environment
 //!   - `from_env_validated_with_sources()` - With source attribution
+//!   - `from_config_validated_with_sources()` - Layered loading with
+//!     validation (when files are configured)
 //!
 //! # Requirements
 //!
@@ -61,7 +63,7 @@ pub fn generate_validated_impl(
     struct_name: &Ident,
     generics: &Generics,
     generators: &[Box<dyn FieldGenerator>],
-    _env_config_attr: &EnvConfigAttr,
+    env_config_attr: &EnvConfigAttr,
 ) -> QuoteStream {
     let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
 
@@ -89,6 +91,40 @@ pub fn generate_validated_impl(
         })
         .collect();
 
+    // Shared validation step, run on `__config`
+    let validation_checks = quote! {
+        let mut __validation_errors: Vec<::procenv::ValidationFieldError> = Vec::new();
+
+        if let Err(e) = ::procenv::Validate::validate(&__config) {
+            __validation_errors.extend(::procenv::validation_errors_to_procenv(&e));
+        }
+
+        #(#custom_validations)*
+
+        if !__validation_errors.is_empty() {
+            return Err(::procenv::Error::Validation {
+                errors: __validation_errors,
+            });
+        }
+    };
+
+    // Validated layered loading, used by `watch()` when files are configured
+    let config_validated = if env_config_attr.files.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            /// Load configuration from files and environment variables with
+            /// validation and source attribution.
+            pub fn from_config_validated_with_sources() -> std::result::Result<(Self, ::procenv::ConfigSources), ::procenv::Error> {
+                let (__config, __sources) = Self::from_config_with_sources()?;
+
+                #validation_checks
+
+                Ok((__config, __sources))
+            }
+        }
+    };
+
     quote! {
         impl #impl_generics #struct_name #type_generics #where_clause
         where
@@ -98,19 +134,7 @@ pub fn generate_validated_impl(
             pub fn from_env_validated() -> std::result::Result<Self, ::procenv::Error> {
                 let __config = Self::from_env()?;
 
-                let mut __validation_errors: Vec<::procenv::ValidationFieldError> = Vec::new();
-
-                if let Err(e) = ::procenv::Validate::validate(&__config) {
-                    __validation_errors.extend(::procenv::validation_errors_to_procenv(&e));
-                }
-
-                #(#custom_validations)*
-
-                if !__validation_errors.is_empty() {
-                    return Err(::procenv::Error::Validation {
-                        errors: __validation_errors,
-                    });
-                }
+                #validation_checks
 
                 Ok(__config)
             }
@@ -118,22 +142,12 @@ pub fn generate_validated_impl(
             pub fn from_env_validated_with_sources() -> std::result::Result<(Self, ::procenv::ConfigSources), ::procenv::Error> {
                 let (__config, __sources) = Self::from_env_with_sources()?;
 
-                let mut __validation_errors: Vec<::procenv::ValidationFieldError> = Vec::new();
-
-                if let Err(e) = ::procenv::Validate::validate(&__config) {
-                    __validation_errors.extend(::procenv::validation_errors_to_procenv(&e));
-                }
-
-                #(#custom_validations)*
-
-                if !__validation_errors.is_empty() {
-                    return Err(::procenv::Error::Validation {
-                        errors: __validation_errors,
-                    });
-                }
+                #validation_checks
 
                 Ok((__config, __sources))
             }
+
+            #config_validated
         }
     }
 }
@@ -0,0 +1,91 @@
+//! Hot reload code generation.
+//!
+//! This module generates `watch()` and `watch_with()`, which start a file
+//! watcher for the files and dotenv paths declared in `#[env_config(...)]`,
+//! so they don't have to be repeated in a `WatchBuilder`. Changed fields
+//! are reported through the generated `keys()` and `get_str()`, which mask
+//! secrets.
+//!
+//! # Reload Function
+//!
+//! | Files configured | `validate` | Reloads via |
+//! |------------------|------------|-------------|
+//! | Yes | No | `from_config_with_sources()` |
+//! | Yes | Yes | `from_config_validated_with_sources()` |
+//! | No | No | `from_env_with_sources()` |
+//! | No | Yes | `from_env_validated_with_sources()` |
+
+use proc_macro2::TokenStream as QuoteStream;
+use quote::quote;
+use syn::{Generics, Ident};
+
+use crate::parse::EnvConfigAttr;
+
+/// Generate the `watch()` and `watch_with()` methods.
+///
+/// The methods are gated on the `watch` feature, like the file config
+/// helpers are on `file`. Their `Clone + Send + Sync` requirement is written
+/// as a higher-ranked bound, which defers the check to call sites: structs
+/// that are not `Clone` still compile, they just can't be watched.
+pub fn generate_watch_impl(
+    struct_name: &Ident,
+    generics: &Generics,
+    env_config_attr: &EnvConfigAttr,
+) -> QuoteStream {
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    let file_paths: Vec<&str> = env_config_attr
+        .files
+        .iter()
+        .map(|f| f.path.as_str())
+        .collect();
+
+    let reload_fn = match (file_paths.is_empty(), env_config_attr.validate) {
+        (false, false) => quote! { Self::from_config_with_sources },
+        (false, true) => quote! { Self::from_config_validated_with_sources },
+        (true, false) => quote! { Self::from_env_with_sources },
+        (true, true) => quote! { Self::from_env_validated_with_sources },
+    };
+
+    let validate_bound = if env_config_attr.validate {
+        quote! { + ::procenv::Validate }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        // Only generate watch methods when the watch feature is enabled
+        #[cfg(feature = "watch")]
+        impl #impl_generics #struct_name #type_generics #where_clause {
+            /// Watch the configured files and dotenv files, reloading on changes.
+            ///
+            /// Equivalent to `watch_with(WatchBuilder::new())`.
+            pub fn watch() -> std::result::Result<::procenv::ConfigHandle<Self>, ::procenv::WatchError>
+            where
+                for<'__procenv> Self: ::core::clone::Clone + Send + Sync + 'static #validate_bound,
+            {
+                Self::watch_with(::procenv::WatchBuilder::new())
+            }
+
+            /// Watch the configured files and dotenv files using `builder`.
+            ///
+            /// Use this to set the debounce, callbacks, signals or extra
+            /// paths; the struct's own files are registered automatically,
+            /// and changed fields are reported via `keys()` and `get_str()`.
+            pub fn watch_with(
+                builder: ::procenv::WatchBuilder<Self>,
+            ) -> std::result::Result<::procenv::ConfigHandle<Self>, ::procenv::WatchError>
+            where
+                for<'__procenv> Self: ::core::clone::Clone + Send + Sync + 'static #validate_bound,
+            {
+                let __files: &[&str] = &[#(#file_paths),*];
+
+                builder
+                    .watch_files(__files)
+                    .watch_dotenv_files(Self::dotenv_files())
+                    .diff_fields(Self::keys(), Self::get_str)
+                    .build_sync(#reload_fn)
+            }
+        }
+    }
+}
@@ -78,6 +78,9 @@ pub struct DefaultField {
     pub format: Option<String>,
 
     pub validate: Option<String>,
+
+    /// Merge strategy for config file layering
+    pub merge: Option<String>,
 }
 
 impl FieldGenerator for DefaultField {
@@ -94,7 +97,7 @@ impl FieldGenerator for DefaultField {
             let mut #used_default_ident = false;
 
             let #field_name: std::option::Option<#ty> = (|| {
-                let val = match std::env::var(#env_var) {
+                let val = match ::procenv::env::var(#env_var) {
                     std::result::Result::Ok(v) => v,
 
                     std::result::Result::Err(std::env::VarError::NotPresent) => {
@@ -160,7 +163,7 @@ impl FieldGenerator for DefaultField {
             let #profile_used_ident: bool = false;
 
             let #field_name: std::option::Option<#ty> = (|| {
-                let val = match std::env::var(&#effective_var_ident) {
+                let val = match ::procenv::env::var(&#effective_var_ident) {
                     std::result::Result::Ok(v) => v,
 
                     std::result::Result::Err(std::env::VarError::NotPresent) => {
@@ -223,7 +226,7 @@ impl FieldGenerator for DefaultField {
 
                 // Get value to parse: env var > profile default > compile-time default
                 let (val, #profile_used_ident): (std::string::String, bool) =
-                    match std::env::var(&#effective_var_ident) {
+                    match ::procenv::env::var(&#effective_var_ident) {
                         std::result::Result::Ok(v) => (v, false),
                         std::result::Result::Err(std::env::VarError::NotPresent) => {
                             match __profile_default {
@@ -364,7 +367,7 @@ impl FieldGenerator for DefaultField {
             let mut #used_default_ident = false;
 
             let #field_name = (|| {
-                let val = match std::env::var(#env_var) {
+                let val = match ::procenv::env::var(#env_var) {
                     std::result::Result::Ok(v) => v,
 
                     std::result::Result::Err(std::env::VarError::NotPresent) => {
@@ -424,6 +427,10 @@ impl FieldGenerator for DefaultField {
         self.validate.as_deref()
     }
 
+    fn merge_strategy(&self) -> Option<&str> {
+        self.merge.as_deref()
+    }
+
     fn field_type(&self) -> Option<&Type> {
         Some(&self.ty)
     }
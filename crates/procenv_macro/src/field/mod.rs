@@ -205,7 +205,7 @@ pub trait FieldGenerator {
     /// Generate code to load this field's value from the environment.
     ///
     /// The generated code should:
-    /// 1. Call `std::env::var()` to read the environment variable
+    /// 1. Call `procenv::env::var()` to read the environment variable
     /// 2. Parse the value using `.parse::<T>()`
     /// 3. Push any errors to `__errors` vector
     /// 4. Store the result in a local variable as `Option<T>`
@@ -342,6 +342,11 @@ pub trait FieldGenerator {
         None
     }
 
+    /// Returns the merge strategy for config file layering if specified.
+    fn merge_strategy(&self) -> Option<&str> {
+        None
+    }
+
     /// Generate clap Arg definition for this field (if CLI-enabled).
     fn generate_clap_arg(&self) -> Option<QuoteStream> {
         let cli = self.cli_config()?;
@@ -500,6 +505,7 @@ impl FieldFactory {
         let profile = env_attr.profile;
         let format = env_attr.format;
         let validate = env_attr.validate;
+        let merge = env_attr.merge;
 
         // Choose the appropriate field generator based on attributes
         if env_attr.optional {
@@ -520,6 +526,7 @@ impl FieldFactory {
                 profile,
                 format,
                 validate,
+                merge,
             }))
         } else if let Some(default) = env_attr.default {
             // Default field
@@ -534,6 +541,7 @@ impl FieldFactory {
                 profile,
                 format,
                 validate,
+                merge,
             }))
         } else {
             // Required field (the default)
@@ -547,6 +555,7 @@ impl FieldFactory {
                 profile,
                 format,
                 validate,
+                merge,
             }))
         }
     }
@@ -72,6 +72,9 @@ pub struct OptionalField {
     pub format: Option<String>,
 
     pub validate: Option<String>,
+
+    /// Merge strategy for config file layering
+    pub merge: Option<String>,
 }
 
 impl FieldGenerator for OptionalField {
@@ -85,7 +88,7 @@ impl FieldGenerator for OptionalField {
         quote! {
             // WARN: The local variable is Option<inner_type>, not Option<Option<inner_type>>
             // The assignment will use this directly since the field is already Option<T>
-            let #name: std::option::Option<#inner> = match std::env::var(#env_var) {
+            let #name: std::option::Option<#inner> = match ::procenv::env::var(#env_var) {
                 std::result::Result::Ok(val) => {
                     match val.parse::<#inner>() {
                         std::result::Result::Ok(v) => std::option::Option::Some(v),
@@ -143,7 +146,7 @@ impl FieldGenerator for OptionalField {
             // No profile for this field
             let #profile_used_ident: bool = false;
 
-            let #name: std::option::Option<#inner> = match std::env::var(&#effective_var_ident) {
+            let #name: std::option::Option<#inner> = match ::procenv::env::var(&#effective_var_ident) {
                 std::result::Result::Ok(val) => {
                     match val.parse::<#inner>() {
                         std::result::Result::Ok(v) => std::option::Option::Some(v),
@@ -201,7 +204,7 @@ impl FieldGenerator for OptionalField {
 
                 // Get value to parse: env var > profile default > None
                 let (__value_to_parse, #profile_used_ident): (std::option::Option<std::string::String>, bool) =
-                    match std::env::var(&#effective_var_ident) {
+                    match ::procenv::env::var(&#effective_var_ident) {
                         std::result::Result::Ok(val) => {
                             (std::option::Option::Some(val), false)
                         }
@@ -343,7 +346,7 @@ impl FieldGenerator for OptionalField {
         let format_name = format.to_uppercase();
 
         quote! {
-            let #name: std::option::Option<#inner> = match std::env::var(#env_var) {
+            let #name: std::option::Option<#inner> = match ::procenv::env::var(#env_var) {
                 std::result::Result::Ok(val) => {
                     match #deserialize_call {
                         std::result::Result::Ok(v) => std::option::Option::Some(v),
@@ -397,6 +400,10 @@ impl FieldGenerator for OptionalField {
         self.validate.as_deref()
     }
 
+    fn merge_strategy(&self) -> Option<&str> {
+        self.merge.as_deref()
+    }
+
     fn is_optional(&self) -> bool {
         true
     }
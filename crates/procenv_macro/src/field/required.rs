@@ -75,6 +75,9 @@ pub struct RequiredField {
 
     /// Custom Validation function name
     pub validate: Option<String>,
+
+    /// Merge strategy for config file layering
+    pub merge: Option<String>,
 }
 
 impl FieldGenerator for RequiredField {
@@ -93,7 +96,7 @@ impl FieldGenerator for RequiredField {
         // with user code that might have imported different items
         quote! {
             // Try to read the environment variable
-            let #name: std::option::Option<#ty> = match std::env::var(#env_var) {
+            let #name: std::option::Option<#ty> = match ::procenv::env::var(#env_var) {
                 // Env var exists try to parse it
                 std::result::Result::Ok(val) => {
                     match val.parse::<#ty>() {
@@ -160,7 +163,7 @@ impl FieldGenerator for RequiredField {
             // No profile for this field
             let #profile_used_ident: bool = false;
 
-            let #name: std::option::Option<#ty> = match std::env::var(&#effective_var_ident) {
+            let #name: std::option::Option<#ty> = match ::procenv::env::var(&#effective_var_ident) {
                 std::result::Result::Ok(val) => {
                     match val.parse::<#ty>() {
                         std::result::Result::Ok(v) => std::option::Option::Some(v),
@@ -221,7 +224,7 @@ impl FieldGenerator for RequiredField {
 
                 // Get value to parse: env var > profile default > error
                 let (__value_to_parse, #profile_used_ident): (std::option::Option<std::string::String>, bool) =
-                    match std::env::var(&#effective_var_ident) {
+                    match ::procenv::env::var(&#effective_var_ident) {
                         std::result::Result::Ok(val) => {
                             (std::option::Option::Some(val), false)
                         }
@@ -396,7 +399,7 @@ impl FieldGenerator for RequiredField {
         let format_name = format.to_uppercase();
 
         quote! {
-            let #name = match std::env::var(#env_var) {
+            let #name = match ::procenv::env::var(#env_var) {
                 std::result::Result::Ok(val) => {
                     match #deserialize_call {
                         std::result::Result::Ok(v) => std::option::Option::Some(v),
@@ -454,6 +457,10 @@ impl FieldGenerator for RequiredField {
         self.validate.as_deref()
     }
 
+    fn merge_strategy(&self) -> Option<&str> {
+        self.merge.as_deref()
+    }
+
     fn field_type(&self) -> Option<&Type> {
         Some(&self.ty)
     }
@@ -67,7 +67,7 @@ impl FieldGenerator for SecretStringField {
         let env_var = &self.env_var;
 
         quote! {
-            let #name: std::option::Option<::procenv::SecretString> = match std::env::var(#env_var) {
+            let #name: std::option::Option<::procenv::SecretString> = match ::procenv::env::var(#env_var) {
                 std::result::Result::Ok(val) => {
                     std::option::Option::Some(::procenv::SecretString::from(val))
                 }
@@ -104,7 +104,7 @@ impl FieldGenerator for SecretStringField {
                 #base_var
             );
 
-            let #name: std::option::Option<::procenv::SecretString> = match std::env::var(&#effective_var_ident) {
+            let #name: std::option::Option<::procenv::SecretString> = match ::procenv::env::var(&#effective_var_ident) {
                 std::result::Result::Ok(val) => {
                     std::option::Option::Some(::procenv::SecretString::from(val))
                 }
@@ -230,7 +230,7 @@ impl FieldGenerator for SecretBoxField {
         let type_name = quote!(#inner).to_string();
 
         quote! {
-            let #name: std::option::Option<::procenv::SecretBox<#inner>> = match std::env::var(#env_var) {
+            let #name: std::option::Option<::procenv::SecretBox<#inner>> = match ::procenv::env::var(#env_var) {
                 std::result::Result::Ok(val) => {
                     match val.parse::<#inner>() {
                         std::result::Result::Ok(v) => {
@@ -286,7 +286,7 @@ impl FieldGenerator for SecretBoxField {
                 #base_var
             );
 
-            let #name: std::option::Option<::procenv::SecretBox<#inner>> = match std::env::var(&#effective_var_ident) {
+            let #name: std::option::Option<::procenv::SecretBox<#inner>> = match ::procenv::env::var(&#effective_var_ident) {
                 std::result::Result::Ok(val) => {
                     match val.parse::<#inner>() {
                         std::result::Result::Ok(v) => {
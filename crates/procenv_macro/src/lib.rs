@@ -26,6 +26,8 @@
 //! | `from_config()` | Load from files + env (requires `file` feature) |
 //! | `from_args()` | Load from CLI + env (requires CLI attributes) |
 //! | `env_example()` | Generate `.env.example` template |
+//! | `dotenv_files()` | List configured `.env` files (for hot reload) |
+//! | `watch()` | Hot reload the configured files (requires `watch` feature) |
 //!
 //! It also generates a custom `Debug` implementation that masks secret fields.
 
@@ -60,6 +62,7 @@ mod parse;
 /// | `no_prefix` | Skip struct-level prefix for this field |
 /// | `flatten` | Embed a nested config struct |
 /// | `format = "json"` | Parse value as JSON/TOML/YAML |
+/// | `merge = "append"` | Combine config file layers (append/replace/union/patch) |
 /// | `arg = "name"` | CLI argument name (enables `from_args()`) |
 /// | `short = 'n'` | CLI short flag (requires `arg`) |
 ///
@@ -74,6 +77,7 @@ mod parse;
 /// | `dotenv = ".env.local"` | Load specific dotenv file |
 /// | `file = "config.toml"` | Load required config file |
 /// | `file_optional = "..."` | Load optional config file |
+/// | `strict` | Reject unknown keys in config files and prefixed env vars |
 /// | `profile_env = "APP_ENV"` | Env var for profile selection |
 /// | `profiles = ["dev", "prod"]` | Valid profile names |
 ///
@@ -158,6 +162,8 @@ mod parse;
 /// - `from_config_with_sources()` - Layered loading with sources
 /// - `from_args()` - Load from CLI + env (when `arg` attributes present)
 /// - `env_example()` - Generate `.env.example` template
+/// - `dotenv_files()` - List configured `.env` files
+/// - `watch()` / `watch_with()` - Hot reload the configured files
 /// - Custom `Debug` impl with secret masking
 #[proc_macro_derive(EnvConfig, attributes(env, env_config, profile))]
 pub fn derive_env_config(input: TokenStream) -> TokenStream {
@@ -76,11 +76,12 @@
 use std::collections::{HashMap, HashSet};
 
 use syn::meta::ParseNestedMeta;
+use syn::parse::ParseStream;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::{
     Attribute, DeriveInput, Error as SynError, Expr, ExprLit, Field, Lit, LitStr, Meta,
-    Result as SynResult, bracketed,
+    MetaNameValue, Result as SynResult, braced, bracketed,
 };
 
 /// Extract doc comments from a field's attributes.
@@ -234,6 +235,7 @@ pub struct ProfileAttr {
 /// | `arg` | Optional | CLI argument name |
 /// | `short` | Optional | CLI short flag |
 /// | `format` | Optional | Serde format (json/toml/yaml) |
+/// | `merge` | Optional | How config files combine the value (append/replace/union/patch) |
 pub struct EnvAttr {
     /// The name of the environment variable to read (required).
     /// Example: `var = "DATABASE_URL"` → `var_name = "DATABASE_URL"`
@@ -271,6 +273,11 @@ pub struct EnvAttr {
     /// Custom validation function name.
     /// Example: `#[env(var = "...", validate = "my_validator")]`
     pub validate: Option<String>,
+
+    /// Merge strategy for the field's value across config files.
+    /// Example: `#[env(var = "PLUGINS", merge = "append")]`
+    /// Supported: "append", "replace", "union", "patch"
+    pub merge: Option<String>,
 }
 
 /// Builder pattern parser for `#[env(...)]` attributes.
@@ -374,6 +381,9 @@ pub struct Parser {
 
     /// Custom validation function (from `validate = "..."`).
     validate: Option<String>,
+
+    /// Merge strategy (from `merge = "append"`).
+    merge: Option<String>,
 }
 
 impl Parser {
@@ -421,6 +431,7 @@ impl Parser {
             "short" => "short",
             "format" => "format",
             "validate" => "validate",
+            "merge" => "merge",
             _ => return Err(meta.error(format!("Unknown option `{name}`"))),
         };
 
@@ -503,6 +514,24 @@ impl Parser {
                 self.validate = Some(lit_str.value());
             }
 
+            // merge = "append" - how config file layers combine the value
+            "merge" => {
+                let lit_str: LitStr = meta.value()?.parse()?;
+                let merge_val = lit_str.value();
+                match merge_val.as_str() {
+                    "append" | "replace" | "union" | "patch" => {}
+                    _ => {
+                        return Err(SynError::new_spanned(
+                            lit_str,
+                            format!(
+                                "Unknown merge strategy '{merge_val}'. Supported: append, replace, union, patch"
+                            ),
+                        ));
+                    }
+                }
+                self.merge = Some(merge_val);
+            }
+
             // We validated the key above
             _ => unreachable!(),
         }
@@ -582,6 +611,7 @@ impl Parser {
             profile: None, // Parsed separately via #[profile(...)] attribute
             format: self.format,
             validate: self.validate,
+            merge: self.merge,
         })
     }
 
@@ -700,6 +730,7 @@ impl Parser {
                 (self.arg_long.is_some() || self.arg_short.is_some()).then_some("arg/short"),
                 self.format.is_some().then_some("format"),
                 self.validate.is_some().then_some("validate"),
+                self.merge.is_some().then_some("merge"),
             ]
             .into_iter()
             .flatten()
@@ -735,7 +766,7 @@ impl Parser {
 // Struct-level Attribute Parsing (#[env_config(...)])
 // ============================================================================
 
-/// Configuration for dotenvy integration.
+/// Configuration for dotenv file loading.
 ///
 /// Specifies how `.env` files should be loaded before reading environment variables.
 #[derive(Clone, Debug)]
@@ -761,6 +792,10 @@ pub struct FileConfig {
 
     /// Whether this file is required (error if missing) or optional
     pub required: bool,
+
+    /// Format override: `None` chooses the format from the extension.
+    /// Generated from: `file = { path = "...", format = "toml" }`
+    pub format: Option<FileFormatOverride>,
 }
 
 impl FileConfig {
@@ -769,6 +804,7 @@ impl FileConfig {
         Self {
             path,
             required: true,
+            format: None,
         }
     }
 
@@ -777,10 +813,36 @@ impl FileConfig {
         Self {
             path,
             required: false,
+            format: None,
         }
     }
 }
 
+/// Format override for a config file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileFormatOverride {
+    /// Parse as the named `procenv::file::FileFormat` variant.
+    /// Generated from: `format = "json"`, `"json5"`, `"toml"` or `"yaml"`
+    Fixed(&'static str),
+
+    /// Detect the format from the file content.
+    /// Generated from: `format = "auto"`
+    Auto,
+}
+
+/// Failure policy for the generated `global()` accessor.
+///
+/// Mirrors `procenv::FailurePolicy`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GlobalPolicy {
+    /// Panic with the rendered diagnostic (`global` or `global = "panic"`).
+    #[default]
+    Panic,
+
+    /// Print the rendered diagnostic and exit (`global = "exit"`).
+    Exit,
+}
+
 /// The parsed result of an `#[env_config(...)]` struct-level attribute.
 ///
 /// This struct holds configuration that applies to the entire struct,
@@ -796,8 +858,11 @@ impl FileConfig {
 /// | `dotenv = ".env.local"` | Load specific dotenv file |
 /// | `file = "config.toml"` | Load required config file |
 /// | `file_optional = "..."` | Load optional config file |
+/// | `file = { path = "...", format = "toml" }` | Config file with an explicit format (`"auto"` sniffs it) |
 /// | `profile_env = "APP_ENV"` | Env var for profile selection |
 /// | `profiles = ["dev", "prod"]` | Valid profile names |
+/// | `global` | Generate a lazily loaded `global()` accessor |
+/// | `global = "exit"` | Same, exiting instead of panicking on failure |
 ///
 /// # Example
 ///
@@ -845,6 +910,14 @@ pub struct EnvConfigAttr {
     /// Enable automatic validation after loading.
     /// Generated from: `#[env_config(validate)]`
     pub validate: bool,
+
+    /// Generate `global()`, failing according to the policy.
+    /// Generated from: `#[env_config(global)]` or `#[env_config(global = "exit")]`
+    pub global: Option<GlobalPolicy>,
+
+    /// Reject unknown keys in config files and unused prefixed env vars.
+    /// Generated from: `#[env_config(strict)]`
+    pub strict: bool,
 }
 
 impl EnvConfigAttr {
@@ -859,6 +932,8 @@ impl EnvConfigAttr {
     /// #[env_config(file = "config.toml")]                // Single config file
     /// #[env_config(file = ["config.toml", "config.local.toml"])]  // Multiple files
     /// #[env_config(file_optional = "config.local.toml")] // Optional config file
+    /// #[env_config(file = { path = "/etc/app/config", format = "toml" })]  // Explicit format
+    /// #[env_config(strict)]                              // Reject unknown keys
     /// ```
     pub fn parse_from_struct(input: &DeriveInput) -> SynResult<Self> {
         let mut result = Self::default();
@@ -900,6 +975,31 @@ impl EnvConfigAttr {
                 } else if meta.path.is_ident("validate") {
                     result.validate = true;
 
+                    Ok(())
+                } else if meta.path.is_ident("strict") {
+                    result.strict = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("global") {
+                    // Just the flag panics: #[env_config(global)]
+                    let policy = if meta.input.peek(syn::Token![=]) {
+                        let lit_str: LitStr = meta.value()?.parse()?;
+
+                        match lit_str.value().as_str() {
+                            "panic" => GlobalPolicy::Panic,
+                            "exit" => GlobalPolicy::Exit,
+                            _ => {
+                                return Err(SynError::new_spanned(
+                                    lit_str,
+                                    "global must be \"panic\" or \"exit\"",
+                                ));
+                            }
+                        }
+                    } else {
+                        GlobalPolicy::Panic
+                    };
+
+                    result.global = Some(policy);
                     Ok(())
                 } else if meta.path.is_ident("prefix") {
                     let lit_str: LitStr = meta.value()?.parse()?;
@@ -949,6 +1049,14 @@ impl EnvConfigAttr {
             })?;
         }
 
+        if result.global.is_some() && !input.generics.params.is_empty() {
+            // The generated static can't depend on type parameters
+            return Err(SynError::new_spanned(
+                &input.generics,
+                "global is not supported on generic structs",
+            ));
+        }
+
         if result.profiles.is_some() && result.profile_env.is_none() {
             // profiles without profile_env doesn't make sense
             return Err(SynError::new_spanned(
@@ -966,6 +1074,8 @@ impl EnvConfigAttr {
     /// - `file = "config.toml"` → single required file
     /// - `file = ["a.toml", "b.toml"]` → multiple required files
     /// - `file_optional = "..."` → single optional file
+    /// - `file = { path = "...", format = "toml" }` → file with a format override,
+    ///   also allowed as an array element
     ///
     /// # DRY Optimization
     ///
@@ -1004,15 +1114,91 @@ impl EnvConfigAttr {
             let content;
             bracketed!(content in meta.input);
 
-            let paths: Punctuated<LitStr, Comma> = Punctuated::parse_terminated(&content)?;
-            // Use extend + map for efficient batch insertion
-            files.extend(paths.iter().map(|lit| make_config(lit.value())));
+            while !content.is_empty() {
+                files.push(Self::parse_file_entry(&content, make_config)?);
+
+                if !content.is_empty() {
+                    let _comma: Comma = content.parse()?;
+                }
+            }
         } else {
-            // String: file = "config.toml"
-            let lit_str: LitStr = meta.input.parse()?;
-            files.push(make_config(lit_str.value()));
+            // String or table: file = "config.toml"
+            files.push(Self::parse_file_entry(meta.input, make_config)?);
         }
 
         Ok(())
     }
+
+    /// Parse one file entry: `"config.toml"` or `{ path = "...", format = "..." }`.
+    fn parse_file_entry(
+        input: ParseStream,
+        make_config: fn(String) -> FileConfig,
+    ) -> SynResult<FileConfig> {
+        if !input.peek(syn::token::Brace) {
+            let lit_str: LitStr = input.parse()?;
+            return Ok(make_config(lit_str.value()));
+        }
+
+        let content;
+        let brace = braced!(content in input);
+
+        let mut path = None;
+        let mut format = None;
+
+        let options: Punctuated<MetaNameValue, Comma> = Punctuated::parse_terminated(&content)?;
+
+        for option in options {
+            let Expr::Lit(ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            }) = &option.value
+            else {
+                return Err(SynError::new_spanned(
+                    &option.value,
+                    "expected a string literal",
+                ));
+            };
+
+            if option.path.is_ident("path") {
+                if path.replace(lit_str.value()).is_some() {
+                    return Err(SynError::new_spanned(&option.path, "duplicate `path`"));
+                }
+            } else if option.path.is_ident("format") {
+                let parsed = match lit_str.value().as_str() {
+                    "json" => FileFormatOverride::Fixed("Json"),
+                    "json5" => FileFormatOverride::Fixed("Json5"),
+                    "toml" => FileFormatOverride::Fixed("Toml"),
+                    "yaml" => FileFormatOverride::Fixed("Yaml"),
+                    "auto" => FileFormatOverride::Auto,
+                    _ => {
+                        return Err(SynError::new_spanned(
+                            lit_str,
+                            "format must be \"json\", \"json5\", \"toml\", \"yaml\" or \"auto\"",
+                        ));
+                    }
+                };
+
+                if format.replace(parsed).is_some() {
+                    return Err(SynError::new_spanned(&option.path, "duplicate `format`"));
+                }
+            } else {
+                return Err(SynError::new_spanned(
+                    &option.path,
+                    "unknown file option, expected `path` or `format`",
+                ));
+            }
+        }
+
+        let Some(path) = path else {
+            return Err(SynError::new(
+                brace.span.join(),
+                "file entry requires `path = \"...\"`",
+            ));
+        };
+
+        Ok(FileConfig {
+            format,
+            ..make_config(path)
+        })
+    }
 }